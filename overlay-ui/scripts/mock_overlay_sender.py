@@ -37,7 +37,7 @@ STATES = [
 
 def send(payload: dict) -> None:
     with socket.socket(socket.AF_INET, socket.SOCK_DGRAM) as sock:
-        sock.sendto(json.dumps(payload).encode("utf-8"), TARGET)
+        sock.sendto(json.dumps({"kind": "state", **payload}).encode("utf-8"), TARGET)
 
 
 def main() -> None:
@@ -0,0 +1,157 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, State};
+
+use crate::{
+    config::update_config,
+    lock_config,
+    timeline::{ChangeSource, SourceScope},
+    update_overlay_state, OverlayState, SharedOverlayState,
+};
+
+const MAX_ATTENTION_COOLDOWN_MS: u64 = 600_000;
+
+#[derive(Debug, Clone)]
+struct PendingAck {
+    id: Option<String>,
+    message: String,
+    /// Whether this hold flashes; false when it arrived within the attention cooldown.
+    flash: bool,
+}
+
+/// Holds a `requires_ack` message on screen, past auto-clear and later
+/// messages, until `acknowledge_message` or `dismiss_overlay` releases it.
+#[derive(Default)]
+pub(crate) struct MessageAck {
+    pending: Mutex<Option<PendingAck>>,
+    last_flash_at: Mutex<Option<Instant>>,
+}
+
+impl MessageAck {
+    pub(crate) fn is_pending(&self) -> bool {
+        self.pending.lock().map(|pending| pending.is_some()).unwrap_or(false)
+    }
+
+    /// Whether a new hold may flash, starting the cooldown when it does.
+    pub(crate) fn admit_flash(&self, cooldown: Duration, now: Instant) -> bool {
+        let Ok(mut last_flash_at) = self.last_flash_at.lock() else {
+            return true;
+        };
+        if last_flash_at.is_some_and(|at| now.duration_since(at) < cooldown) {
+            return false;
+        }
+        *last_flash_at = Some(now);
+        true
+    }
+
+    fn cooldown_remaining(&self, cooldown: Duration, now: Instant) -> Duration {
+        self.last_flash_at
+            .lock()
+            .ok()
+            .and_then(|at| *at)
+            .map(|at| cooldown.saturating_sub(now.duration_since(at)))
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn filter(&self, next: &mut OverlayState, attention_cooldown: Duration, now: Instant) {
+        let Ok(mut pending) = self.pending.lock() else {
+            return;
+        };
+        if std::mem::take(&mut next.requires_ack) {
+            if let Some(message) = next.message.clone() {
+                // A newer critical message takes over the hold; within the cooldown it shows without flashing.
+                *pending = Some(PendingAck {
+                    id: next.message_id.clone(),
+                    message,
+                    flash: self.admit_flash(attention_cooldown, now),
+                });
+            }
+        }
+        next.awaiting_ack = pending.is_some();
+        next.attention = pending.as_ref().is_some_and(|held| held.flash);
+        if let Some(held) = pending.as_ref() {
+            next.message = Some(held.message.clone());
+            next.message_id = held.id.clone();
+            next.visible = true;
+        }
+    }
+
+    fn release(&self, id: Option<&str>) -> Result<(), String> {
+        let mut pending = self
+            .pending
+            .lock()
+            .map_err(|_| "acknowledgment lock poisoned".to_string())?;
+        match pending.as_ref() {
+            None => Err("no message is awaiting acknowledgment".to_string()),
+            Some(held) if id.is_some() && held.id.as_deref() != id => {
+                Err(format!("message {:?} is not the one awaiting acknowledgment", id.unwrap_or_default()))
+            }
+            Some(_) => {
+                *pending = None;
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn clear(&self) {
+        if let Ok(mut pending) = self.pending.lock() {
+            *pending = None;
+        }
+    }
+}
+
+/// Releases the message awaiting acknowledgment; `id`, when given, must match its `message_id`.
+#[tauri::command]
+pub(crate) fn acknowledge_message(
+    id: Option<String>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    shared.message_ack.release(id.as_deref())?;
+    let _source = SourceScope::enter(ChangeSource::Command);
+    update_overlay_state(&app, shared.inner(), |state| {
+        state.message = None;
+        state.message_id = None;
+    })
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct AttentionCooldown {
+    cooldown_ms: u64,
+    /// Time left before another hold may flash.
+    remaining_ms: u64,
+}
+
+/// Sets how long after an attention flash further `requires_ack` holds show without flashing.
+#[tauri::command]
+pub(crate) fn set_attention_cooldown(
+    cooldown_ms: u64,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if cooldown_ms > MAX_ATTENTION_COOLDOWN_MS {
+        return Err(format!(
+            "attention cooldown must be at most {} ms",
+            MAX_ATTENTION_COOLDOWN_MS
+        ));
+    }
+    update_config(&app, shared.inner(), |config| config.attention_cooldown_ms = cooldown_ms)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn get_attention_cooldown(shared: State<'_, Arc<SharedOverlayState>>) -> Result<AttentionCooldown, String> {
+    let cooldown_ms = lock_config(shared.inner())?.attention_cooldown_ms;
+    let remaining = shared
+        .message_ack
+        .cooldown_remaining(Duration::from_millis(cooldown_ms), shared.scheduler.clock().now());
+    Ok(AttentionCooldown {
+        cooldown_ms,
+        remaining_ms: remaining.as_millis() as u64,
+    })
+}
@@ -0,0 +1,135 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use crate::{
+    config::update_config, update_overlay_state, OverlayState, SharedOverlayState,
+};
+
+const ACTIVITY_DECAY_TICK: Duration = Duration::from_millis(33);
+const ACTIVITY_EPSILON: f64 = 0.001;
+const MAX_ACTIVITY_DECAY_MS: u64 = 10_000;
+
+/// How much each signal contributes to `activity`; the sum is clamped to 1.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default, rename_all = "snake_case")]
+pub(crate) struct ActivityWeights {
+    listening: f64,
+    processing: f64,
+    level: f64,
+}
+
+impl Default for ActivityWeights {
+    fn default() -> Self {
+        Self {
+            listening: 0.3,
+            processing: 0.7,
+            level: 0.7,
+        }
+    }
+}
+
+impl ActivityWeights {
+    fn validate(&self) -> Result<(), String> {
+        if [self.listening, self.processing, self.level]
+            .iter()
+            .all(|weight| weight.is_finite() && *weight >= 0.0)
+        {
+            Ok(())
+        } else {
+            Err("activity weights must be non-negative numbers".to_string())
+        }
+    }
+
+    fn score(&self, state: &OverlayState) -> f64 {
+        let listening = if state.listening == "listening" { self.listening } else { 0.0 };
+        let processing = if state.processing == "processing" { self.processing } else { 0.0 };
+        (listening + processing + self.level * state.level).clamp(0.0, 1.0)
+    }
+}
+
+/// Combines `listening`, `processing` and `level` into one `activity` value
+/// that rises at once and falls exponentially with `activity_decay_ms`.
+pub(crate) struct ActivityMeter {
+    /// The last `activity` and when it was set; `None` before the first update.
+    last: Mutex<(f64, Option<Instant>)>,
+    decaying: AtomicBool,
+    ticking: AtomicBool,
+}
+
+impl Default for ActivityMeter {
+    fn default() -> Self {
+        Self {
+            last: Mutex::new((0.0, None)),
+            decaying: AtomicBool::new(false),
+            ticking: AtomicBool::new(false),
+        }
+    }
+}
+
+impl ActivityMeter {
+    pub(crate) fn filter(
+        &self,
+        app: &AppHandle,
+        shared: &Arc<SharedOverlayState>,
+        next: &mut OverlayState,
+        weights: &ActivityWeights,
+        decay: Duration,
+    ) {
+        let Ok(mut last) = self.last.lock() else {
+            return;
+        };
+        let now = shared.scheduler.clock().now();
+        let target = weights.score(next);
+        let decayed = match last.1 {
+            Some(at) if !decay.is_zero() => {
+                last.0 * (-now.duration_since(at).as_secs_f64() / decay.as_secs_f64()).exp()
+            }
+            _ => 0.0,
+        };
+        next.activity = if decayed > target + ACTIVITY_EPSILON { decayed } else { target };
+        *last = (next.activity, Some(now));
+        let decaying = next.activity > target;
+        self.decaying.store(decaying, Ordering::Relaxed);
+        if decaying && !self.ticking.swap(true, Ordering::Relaxed) {
+            start_decay(app.clone(), shared.clone());
+        }
+    }
+}
+
+/// Re-runs the policies on a timer so `activity` keeps falling without new datagrams.
+fn start_decay(app: AppHandle, shared: Arc<SharedOverlayState>) {
+    shared.scheduler.repeat("activity_decay", ACTIVITY_DECAY_TICK, move |shared| {
+        let failed = update_overlay_state(&app, shared, |_| {}).is_err();
+        if failed || !shared.activity_meter.decaying.load(Ordering::Relaxed) {
+            shared.activity_meter.ticking.store(false, Ordering::Relaxed);
+            return None;
+        }
+        Some(ACTIVITY_DECAY_TICK)
+    });
+}
+
+#[tauri::command]
+pub(crate) fn set_activity_weights(
+    weights: ActivityWeights,
+    decay_ms: u64,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    weights.validate()?;
+    if decay_ms > MAX_ACTIVITY_DECAY_MS {
+        return Err(format!("activity decay must be at most {} ms", MAX_ACTIVITY_DECAY_MS));
+    }
+    update_config(&app, shared.inner(), |config| {
+        config.activity_weights = weights;
+        config.activity_decay_ms = decay_ms;
+    })?;
+    Ok(())
+}
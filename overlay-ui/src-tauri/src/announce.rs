@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::{
+    config::update_config,
+    severity::{message_settled, message_severity, Severity},
+    OverlayState, SharedOverlayState,
+};
+
+/// ARIA live-region politeness the frontend announces with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Politeness {
+    Polite,
+    Assertive,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+struct Announcement<'a> {
+    text: &'a str,
+    politeness: Politeness,
+}
+
+/// Emits `overlay://announce` when a new message settles; errors are assertive.
+/// Streamed messages are announced once, when complete.
+pub(crate) fn announce_message(app: &AppHandle, enabled: bool, previous: &OverlayState, next: &OverlayState) {
+    if !enabled || !message_settled(previous, next) {
+        return;
+    }
+    let Some(text) = next.message.as_deref() else {
+        return;
+    };
+    let politeness = match message_severity(next) {
+        Severity::Error => Politeness::Assertive,
+        Severity::Info => Politeness::Polite,
+    };
+    let _ = app.emit("overlay://announce", Announcement { text, politeness });
+}
+
+#[tauri::command]
+pub(crate) fn set_accessibility_announcements(
+    enabled: bool,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    update_config(&app, shared.inner(), |config| config.accessibility_announcements = enabled)?;
+    Ok(())
+}
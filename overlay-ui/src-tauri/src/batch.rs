@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use serde::Deserialize;
+use tauri::{AppHandle, State};
+
+use crate::{
+    lock_config, main_window, replace_state,
+    timeline::{ChangeSource, SourceScope},
+    update_overlay_state, OverlayPatch, OverlayState, SharedOverlayState,
+};
+
+const MAX_BATCH_COMMANDS: usize = 32;
+
+/// One step of `apply_commands`, tagged by `"command"`.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub(crate) enum BatchCommand {
+    /// As `set_overlay_state`.
+    SetState { state: Box<OverlayState> },
+    /// As a bridge patch payload.
+    Patch { patch: Box<OverlayPatch> },
+    /// Click-through off (true) or on (false), without the interactive-mode timeout.
+    SetInteractive { interactive: bool },
+}
+
+/// Runs `commands` in order against one state lock and emits once; if any step
+/// fails nothing is applied.
+#[tauri::command]
+pub(crate) fn apply_commands(
+    commands: Vec<BatchCommand>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if commands.len() > MAX_BATCH_COMMANDS {
+        return Err(format!("at most {} commands per batch", MAX_BATCH_COMMANDS));
+    }
+    let interactive = commands.iter().fold(None, |interactive, command| match command {
+        BatchCommand::SetInteractive { interactive } => Some(*interactive),
+        _ => interactive,
+    });
+    // The only fallible step touches the window, so it runs first and is undone on failure.
+    let window = main_window(&app)?;
+    if let Some(interactive) = interactive {
+        window
+            .set_ignore_cursor_events(!interactive)
+            .map_err(|error| error.to_string())?;
+    }
+    let sticky_fields = lock_config(shared.inner())?.sticky_fields.clone();
+    let _source = SourceScope::enter(ChangeSource::Command);
+    let result = update_overlay_state(&app, shared.inner(), |state| {
+        for command in commands {
+            match command {
+                BatchCommand::SetState { state: next } => replace_state(state, *next, &sticky_fields),
+                BatchCommand::Patch { patch } => patch.apply(state),
+                BatchCommand::SetInteractive { interactive } => state.interactive = interactive,
+            }
+        }
+    });
+    if result.is_err() {
+        if let Some(interactive) = interactive {
+            let _ = window.set_ignore_cursor_events(interactive);
+        }
+    }
+    result
+}
@@ -0,0 +1,153 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, State, WebviewWindow};
+
+use crate::{
+    config::{update_config, OverlayConfig},
+    effects, heartbeat, hotkey, layout, lock_config, main_window,
+    position::PositionTrigger,
+    replace_state,
+    timeline::{ChangeSource, SourceScope},
+    update_overlay_state, OverlayState, SharedOverlayState,
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct Bootstrap {
+    config: OverlayConfig,
+    state: OverlayState,
+}
+
+fn validate(config: &OverlayConfig) -> Result<(), String> {
+    if !config.text_scale.is_finite() {
+        return Err("text scale must be a finite number".to_string());
+    }
+    config.geometry.validate()?;
+    config.collapsed_geometry.validate()
+}
+
+/// Rebinds the hotkey, re-lays out the window and re-applies the effect for `next`.
+fn apply_config_effects(
+    app: &AppHandle,
+    window: &WebviewWindow,
+    previous: &OverlayConfig,
+    next: &OverlayConfig,
+) -> Result<(), String> {
+    hotkey::rebind_toggle_hotkey(app, previous.toggle_hotkey.as_deref(), next.toggle_hotkey.as_deref())?;
+    layout::apply_overlay_layout(window, next, PositionTrigger::Layout).map_err(|error| error.to_string())?;
+    effects::apply_background_effect(window, next.background_effect).map_err(|error| error.to_string())?;
+    Ok(())
+}
+
+/// What applying a changed field does beyond storing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ConfigEffect {
+    RebindHotkey,
+    /// Resizes and re-anchors the window.
+    Reposition,
+    BackgroundEffect,
+    /// Read once at startup; takes effect after a restart.
+    Restart,
+}
+
+/// Side effects of `apply_config_effects`, plus fields only read at startup.
+const CONFIG_EFFECTS: &[(&str, ConfigEffect)] = &[
+    ("toggle_hotkey", ConfigEffect::RebindHotkey),
+    ("text_scale", ConfigEffect::Reposition),
+    ("geometry", ConfigEffect::Reposition),
+    ("collapsed_geometry", ConfigEffect::Reposition),
+    ("auto_expand", ConfigEffect::Reposition),
+    ("status_dot", ConfigEffect::Reposition),
+    ("display_form", ConfigEffect::Reposition),
+    ("background_effect", ConfigEffect::BackgroundEffect),
+    ("render_adapter", ConfigEffect::Restart),
+    ("http_port", ConfigEffect::Restart),
+    ("unix_socket_path", ConfigEffect::Restart),
+    ("source_file", ConfigEffect::Restart),
+    ("osc_addr", ConfigEffect::Restart),
+    ("bridge_poll_ms", ConfigEffect::Restart),
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct ConfigChange {
+    field: String,
+    current: Value,
+    candidate: Value,
+    effects: Vec<ConfigEffect>,
+}
+
+/// Fields where `candidate` differs from the live config, and what applying
+/// each would do; nothing is applied.
+#[tauri::command]
+pub(crate) fn diff_config(
+    candidate: OverlayConfig,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<Vec<ConfigChange>, String> {
+    let current = serde_json::to_value(&*lock_config(shared.inner())?).map_err(|error| error.to_string())?;
+    let candidate = serde_json::to_value(&candidate).map_err(|error| error.to_string())?;
+    let (Value::Object(current), Value::Object(mut candidate)) = (current, candidate) else {
+        return Err("config did not serialize to an object".to_string());
+    };
+    Ok(current
+        .into_iter()
+        .filter_map(|(field, current)| {
+            let candidate = candidate.remove(&field).unwrap_or(Value::Null);
+            if candidate == current {
+                return None;
+            }
+            let effects = CONFIG_EFFECTS
+                .iter()
+                .filter(|(name, _)| *name == field)
+                .map(|(_, effect)| *effect)
+                .collect();
+            Some(ConfigChange {
+                field,
+                current,
+                candidate,
+                effects,
+            })
+        })
+        .collect())
+}
+
+/// Replaces the config, then the state, emitting once; when any config side
+/// effect fails, the previous config is restored and the state is left untouched.
+#[tauri::command]
+pub(crate) fn apply_bootstrap(
+    bootstrap: Bootstrap,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    let Bootstrap { config, state: next } = bootstrap;
+    validate(&config)?;
+    let window = main_window(&app)?;
+    let shared = shared.inner();
+    let previous_config = lock_config(shared)?.clone();
+    update_config(&app, shared, |current| *current = config.clone())?;
+    if let Err(error) = apply_config_effects(&app, &window, &previous_config, &config) {
+        if let Err(rollback) = update_config(&app, shared, |current| *current = previous_config.clone())
+            .and_then(|_| apply_config_effects(&app, &window, &config, &previous_config))
+        {
+            log::warn!("bootstrap rollback incomplete: {}", rollback);
+        }
+        return Err(error);
+    }
+    shared.level_ingest.set_max_hz(config.max_level_hz);
+
+    {
+        let _source = SourceScope::enter(ChangeSource::Command);
+        update_overlay_state(&app, shared, |state| {
+            replace_state(state, next, &config.sticky_fields);
+            state.text_scale = layout::clamp_text_scale(config.text_scale);
+            state.corner_radius = config.geometry.corner_radius;
+            state.display_form = config.display_form;
+            state.power_mode = config.power_mode.resolve();
+        })?;
+    }
+    heartbeat::start_connection_watch(app, shared.clone());
+    Ok(())
+}
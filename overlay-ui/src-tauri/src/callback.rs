@@ -0,0 +1,119 @@
+use std::{
+    net::{SocketAddr, ToSocketAddrs, UdpSocket},
+    sync::{
+        mpsc::{self, Sender},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use tauri::{AppHandle, State};
+
+use crate::{config::update_config, OverlayState, SharedOverlayState};
+
+/// Target that replies to whoever sent the last datagram.
+pub(crate) const LAST_SENDER: &str = "sender";
+
+/// Sends `{"event":"visibility"}` datagrams back over the bridge socket.
+#[derive(Default)]
+pub(crate) struct VisibilityCallback {
+    socket: Mutex<Option<UdpSocket>>,
+    last_sender: Mutex<Option<SocketAddr>>,
+    /// `(host:port, payload)` pairs for the sender thread, which resolves the
+    /// address so a slow DNS lookup never runs under the state lock.
+    outbox: Mutex<Option<Sender<(String, String)>>>,
+}
+
+impl VisibilityCallback {
+    pub(crate) fn attach(&self, socket: &UdpSocket) {
+        let (handle, outgoing) = match (socket.try_clone(), socket.try_clone()) {
+            (Ok(handle), Ok(outgoing)) => (handle, outgoing),
+            (Err(error), _) | (_, Err(error)) => {
+                log::warn!("visibility callback unavailable: {}", error);
+                return;
+            }
+        };
+        if let Ok(mut current) = self.socket.lock() {
+            *current = Some(handle);
+        }
+        let (sender, receiver) = mpsc::channel::<(String, String)>();
+        thread::spawn(move || {
+            for (target, payload) in receiver {
+                let Some(address) = resolve(&target) else {
+                    log::warn!("failed to resolve datagram target {}", target);
+                    continue;
+                };
+                if let Err(error) = outgoing.send_to(payload.as_bytes(), address) {
+                    log::warn!("failed to send datagram to {}: {}", address, error);
+                }
+            }
+        });
+        if let Ok(mut outbox) = self.outbox.lock() {
+            *outbox = Some(sender);
+        }
+    }
+
+    /// Applies to the bridge socket itself, since the attached handle is a clone of it.
+    pub(crate) fn set_read_timeout(&self, timeout: Duration) -> Result<(), String> {
+        let socket = self.socket.lock().map_err(|_| "bridge socket lock poisoned".to_string())?;
+        let socket = socket.as_ref().ok_or_else(|| "UDP bridge is not running".to_string())?;
+        socket
+            .set_read_timeout(Some(timeout))
+            .map_err(|error| format!("failed to set bridge read timeout: {}", error))
+    }
+
+    pub(crate) fn record_sender(&self, sender: SocketAddr) {
+        if let Ok(mut last_sender) = self.last_sender.lock() {
+            *last_sender = Some(sender);
+        }
+    }
+
+    /// Notifies `target` when `visible` changed; `None` leaves the callback off.
+    pub(crate) fn notify(&self, target: Option<&str>, previous: &OverlayState, next: &OverlayState) {
+        let Some(target) = target else {
+            return;
+        };
+        if previous.visible == next.visible {
+            return;
+        }
+        let payload = serde_json::json!({ "event": "visibility", "visible": next.visible }).to_string();
+        self.send(target, &payload);
+    }
+
+    /// Queues `payload` for the bridge socket to `"sender"` or a `host:port`.
+    pub(crate) fn send(&self, target: &str, payload: &str) {
+        let target = if target == LAST_SENDER {
+            match self.last_sender.lock().ok().and_then(|sender| *sender) {
+                Some(sender) => sender.to_string(),
+                None => return,
+            }
+        } else {
+            target.to_string()
+        };
+        if let Some(outbox) = self.outbox.lock().ok().as_ref().and_then(|outbox| outbox.as_ref()) {
+            let _ = outbox.send((target, payload.to_string()));
+        }
+    }
+}
+
+pub(crate) fn resolve(target: &str) -> Option<SocketAddr> {
+    target.to_socket_addrs().ok().and_then(|mut addresses| addresses.next())
+}
+
+/// `address` is `"sender"`, a `host:port`, or `null` to turn the callback off.
+#[tauri::command]
+pub(crate) fn set_visibility_callback(
+    address: Option<String>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    let address = address.map(|address| address.trim().to_string());
+    if let Some(address) = address.as_deref() {
+        if address != LAST_SENDER && resolve(address).is_none() {
+            return Err(format!("invalid visibility callback address: {}", address));
+        }
+    }
+    update_config(&app, shared.inner(), |config| config.visibility_callback = address)?;
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use tauri::WebviewWindow;
+
+/// A captured RGBA frame of the overlay's on-screen rectangle.
+pub(crate) struct Frame {
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) rgba: Vec<u8>,
+}
+
+/// Captures what is composited on screen under the overlay window, including
+/// whatever shows through its transparent regions.
+pub(crate) fn capture_window(window: &WebviewWindow) -> Result<Frame, String> {
+    let position = window.outer_position().map_err(|error| error.to_string())?;
+    let size = window.outer_size().map_err(|error| error.to_string())?;
+    if size.width == 0 || size.height == 0 {
+        return Err("overlay window has no visible area".to_string());
+    }
+    capture_screen_rect(position.x, position.y, size.width, size.height)
+}
+
+#[cfg(windows)]
+fn capture_screen_rect(x: i32, y: i32, width: u32, height: u32) -> Result<Frame, String> {
+    use std::ptr::null_mut;
+    use windows_sys::Win32::Graphics::Gdi::{
+        BitBlt, CreateCompatibleBitmap, CreateCompatibleDC, DeleteDC, DeleteObject, GetDC,
+        GetDIBits, ReleaseDC, SelectObject, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, CAPTUREBLT,
+        DIB_RGB_COLORS, SRCCOPY,
+    };
+
+    let mut rgba = vec![0_u8; width as usize * height as usize * 4];
+    // SAFETY: every handle is checked before use and released before returning;
+    // `rgba` is sized for a top-down 32bpp DIB of `width` x `height`.
+    let (copied, lines) = unsafe {
+        let screen = GetDC(null_mut());
+        if screen.is_null() {
+            return Err("failed to acquire the screen device context".to_string());
+        }
+        let memory = CreateCompatibleDC(screen);
+        let bitmap = CreateCompatibleBitmap(screen, width as i32, height as i32);
+        let previous = SelectObject(memory, bitmap);
+        let copied = BitBlt(
+            memory,
+            0,
+            0,
+            width as i32,
+            height as i32,
+            screen,
+            x,
+            y,
+            SRCCOPY | CAPTUREBLT,
+        );
+        // GetDIBits requires the bitmap to be deselected first.
+        SelectObject(memory, previous);
+        let mut info = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: width as i32,
+                biHeight: -(height as i32),
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: BI_RGB,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let lines = GetDIBits(
+            memory,
+            bitmap,
+            0,
+            height,
+            rgba.as_mut_ptr().cast(),
+            &mut info,
+            DIB_RGB_COLORS,
+        );
+        DeleteObject(bitmap);
+        DeleteDC(memory);
+        ReleaseDC(null_mut(), screen);
+        (copied, lines)
+    };
+    if copied == 0 || lines == 0 {
+        return Err("screen capture failed".to_string());
+    }
+    for pixel in rgba.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+        pixel[3] = 255;
+    }
+    Ok(Frame { width, height, rgba })
+}
+
+#[cfg(not(windows))]
+fn capture_screen_rect(_x: i32, _y: i32, _width: u32, _height: u32) -> Result<Frame, String> {
+    Err("screen capture is only supported on Windows".to_string())
+}
@@ -0,0 +1,55 @@
+use crate::OverlayPatch;
+
+/// Initial-state overrides parsed from the launch command line.
+#[derive(Debug, Default)]
+pub(crate) struct StartupArgs {
+    pub(crate) patch: OverlayPatch,
+    pub(crate) ignored: Vec<String>,
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+/// Accepts `--target <value>`, `--connection <value>` and `--visible [bool]`,
+/// each also in `--flag=value` form. Anything else is collected in `ignored`.
+pub(crate) fn parse_startup_args(args: impl IntoIterator<Item = String>) -> StartupArgs {
+    let mut parsed = StartupArgs::default();
+    let mut args = args.into_iter().peekable();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+            None => (arg.clone(), None),
+        };
+        match flag.as_str() {
+            "--target" | "--connection" => {
+                let value = inline.or_else(|| args.next_if(|next| !next.starts_with("--")));
+                match (flag.as_str(), value) {
+                    ("--target", Some(value)) => parsed.patch.target = Some(value),
+                    (_, Some(value)) => parsed.patch.connection = Some(value),
+                    (_, None) => parsed.ignored.push(arg),
+                }
+            }
+            "--visible" => {
+                let value = match inline {
+                    Some(value) => parse_bool(&value),
+                    None => Some(
+                        args.next_if(|next| parse_bool(next).is_some())
+                            .and_then(|next| parse_bool(&next))
+                            .unwrap_or(true),
+                    ),
+                };
+                match value {
+                    Some(value) => parsed.patch.visible = Some(value),
+                    None => parsed.ignored.push(arg),
+                }
+            }
+            _ => parsed.ignored.push(arg),
+        }
+    }
+    parsed
+}
@@ -0,0 +1,92 @@
+#[cfg(test)]
+use std::{sync::Mutex, time::Duration};
+use std::{
+    sync::Arc,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// Time source for timer-driven features: the scheduler (decays, TTLs,
+/// countdowns) and the heartbeat watch read time only through this.
+pub(crate) trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+    fn unix_millis(&self) -> u64;
+}
+
+pub(crate) struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn unix_millis(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or(0)
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct SharedClock(Arc<dyn Clock>);
+
+impl Default for SharedClock {
+    fn default() -> Self {
+        Self(Arc::new(SystemClock))
+    }
+}
+
+/// Swaps in another time source, e.g. a manually advanced one in tests.
+impl From<Arc<dyn Clock>> for SharedClock {
+    fn from(clock: Arc<dyn Clock>) -> Self {
+        Self(clock)
+    }
+}
+
+impl SharedClock {
+    pub(crate) fn now(&self) -> Instant {
+        self.0.now()
+    }
+
+    pub(crate) fn unix_millis(&self) -> u64 {
+        self.0.unix_millis()
+    }
+}
+
+/// A clock that only moves when told to, for driving timers in tests.
+#[cfg(test)]
+pub(crate) struct MockClock {
+    start: Instant,
+    start_unix_millis: u64,
+    elapsed: Mutex<Duration>,
+}
+
+#[cfg(test)]
+impl MockClock {
+    pub(crate) fn new() -> Arc<Self> {
+        Arc::new(Self {
+            start: Instant::now(),
+            start_unix_millis: SystemClock.unix_millis(),
+            elapsed: Mutex::default(),
+        })
+    }
+
+    pub(crate) fn advance(&self, by: Duration) {
+        *self.elapsed.lock().unwrap() += by;
+    }
+
+    fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.start + self.elapsed()
+    }
+
+    fn unix_millis(&self) -> u64 {
+        self.start_unix_millis + self.elapsed().as_millis() as u64
+    }
+}
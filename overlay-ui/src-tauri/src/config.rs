@@ -0,0 +1,479 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tauri::{AppHandle, Manager, State};
+
+use crate::{
+    activity::ActivityWeights,
+    connection::ConnectionBehavior,
+    context::DEFAULT_CONTEXT_TEMPLATE,
+    effects::BackgroundEffect,
+    entrance::ShowAnimation,
+    gpu::RenderAdapter,
+    ingest::{default_source_priority, Transport},
+    layout::{DisplayForm, OverlayGeometry},
+    lock_config,
+    logsample::LogSamplingPolicy,
+    logtap::EventLogLevel,
+    meters::MasterMeterMode,
+    placement::Placement,
+    power::PowerMode,
+    presets::PRESETS_FILE_NAME,
+    levelgate::VisibilityLevelGate,
+    quarantine::QuarantinePolicy,
+    saferect::SafeRect,
+    sequence::SequenceConflict,
+    session::LockBehavior,
+    statusdot::DotCorner,
+    severity::SeverityPolicy,
+    stateline::{default_state_line_fields, StateLineField},
+    rules::TransitionRule,
+    sticky::StateField,
+    unix_millis, OverlayState, SharedOverlayState,
+};
+
+const CONFIG_FILE_NAME: &str = "overlay-config.json";
+
+/// How state changes reach the frontend: `overlay://state` events, polling
+/// `get_overlay_state`, or both.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum DeliveryMode {
+    #[default]
+    Push,
+    Pull,
+    Both,
+}
+
+impl DeliveryMode {
+    pub(crate) fn pushes(self) -> bool {
+        self != DeliveryMode::Pull
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "snake_case")]
+pub(crate) struct OverlayConfig {
+    pub(crate) toggle_hotkey: Option<String>,
+    pub(crate) text_scale: f64,
+    pub(crate) geometry: OverlayGeometry,
+    /// Resize between `collapsed_geometry` and `geometry` as the message is cleared and set.
+    pub(crate) auto_expand: bool,
+    pub(crate) collapsed_geometry: OverlayGeometry,
+    /// Corner of the status dot shown while hidden; off when unset.
+    pub(crate) status_dot: Option<DotCorner>,
+    pub(crate) display_form: DisplayForm,
+    pub(crate) show_animation: ShowAnimation,
+    /// Length of the show and hide animation.
+    pub(crate) show_animation_ms: u64,
+    /// Re-apply the logical layout when the overlay lands on a monitor with another DPI.
+    pub(crate) follow_dpi: bool,
+    /// Window moves from monitor changes are coalesced to one per this many ms; 0 moves at once.
+    pub(crate) reposition_throttle_ms: u64,
+    /// Region the window is clamped into after anchoring; unset leaves it free.
+    pub(crate) safe_rect: Option<SafeRect>,
+    /// Sit in the bars around a reported `video_region` instead of at the anchor.
+    pub(crate) letterbox_aware: bool,
+    /// Transcript segments kept; the oldest are evicted first.
+    pub(crate) max_segments: usize,
+    pub(crate) delivery_mode: DeliveryMode,
+    /// Fields `overlay://state` carries; empty carries them all.
+    pub(crate) emit_projection: Vec<String>,
+    /// Fields stripped from the HTTP, SSE and Unix socket mirrors; the frontend still gets them.
+    #[cfg_attr(not(any(feature = "http", feature = "unixsocket")), allow(dead_code))]
+    pub(crate) private_fields: Vec<String>,
+    /// Emit `overlay://delta` with changed fields instead of the full state.
+    pub(crate) delta_emit: bool,
+    /// How often a full `overlay://state` keyframe goes out in delta mode.
+    pub(crate) delta_keyframe_ms: u64,
+    /// How long `listening` must request `ready` before it is shown; 0 disables the hysteresis.
+    pub(crate) listening_debounce_ms: u64,
+    pub(crate) power_mode: PowerMode,
+    /// GPU preference written to Windows graphics settings for this executable.
+    pub(crate) render_adapter: RenderAdapter,
+    pub(crate) sound_enabled: bool,
+    /// Play `done`/`error` cues on `processing` transitions while sound is enabled.
+    pub(crate) auto_sound_cues: bool,
+    /// Loopback port for the read-only HTTP mirror; 0 disables it.
+    #[cfg_attr(not(feature = "http"), allow(dead_code))]
+    pub(crate) http_port: u16,
+    /// Path of the Unix domain socket bridge for WSL senders; off when unset.
+    #[cfg_attr(not(feature = "unixsocket"), allow(dead_code))]
+    pub(crate) unix_socket_path: Option<String>,
+    /// JSONL file whose appended lines are applied as bridge payloads; off when unset.
+    #[cfg_attr(not(feature = "file_source"), allow(dead_code))]
+    pub(crate) source_file: Option<String>,
+    /// UDP address the OSC bridge binds, e.g. `0.0.0.0:9000`; off when unset.
+    #[cfg_attr(not(feature = "osc"), allow(dead_code))]
+    pub(crate) osc_addr: Option<String>,
+    /// Frames per second `start_obs_mirror` writes at most.
+    #[cfg_attr(not(feature = "obs"), allow(dead_code))]
+    pub(crate) obs_mirror_fps: u32,
+    /// Minimum level forwarded to the Windows Application event log.
+    #[cfg_attr(not(feature = "eventlog"), allow(dead_code))]
+    pub(crate) event_log_level: EventLogLevel,
+    /// Event source name the records are reported under.
+    #[cfg_attr(not(feature = "eventlog"), allow(dead_code))]
+    pub(crate) event_log_source: String,
+    /// Patch fields shown from launch until the first bridge update; unset shows the defaults.
+    pub(crate) splash_state: Option<Map<String, Value>>,
+    pub(crate) splash_timeout_ms: u64,
+    /// Bridge silence before the connection grace period starts; 0 disables the watch.
+    pub(crate) heartbeat_timeout_ms: u64,
+    /// Further silence before `connection` is committed to `offline`.
+    pub(crate) connection_grace_ms: u64,
+    /// Show `connection: "reconnecting"` during the grace period.
+    pub(crate) show_reconnecting: bool,
+    /// Targets the overlay may be shown for; empty allows any.
+    pub(crate) visible_only_for_targets: Vec<String>,
+    /// Whether a locked session hides the overlay, also pauses the bridge, or neither.
+    pub(crate) lock_behavior: LockBehavior,
+    /// Requested effect; `none` is applied instead where the OS lacks support.
+    pub(crate) background_effect: BackgroundEffect,
+    /// Layered, click-through, tool-window styles for fullscreen games (Windows only).
+    pub(crate) game_overlay_mode: bool,
+    /// Keep `WS_EX_NOACTIVATE` on the window so it never takes focus.
+    pub(crate) no_activate: bool,
+    /// Where visibility changes are reported: `"sender"`, a `host:port`, or off when unset.
+    pub(crate) visibility_callback: Option<String>,
+    /// Longest continuous visible stretch before a force-hide; 0 disables the cap.
+    pub(crate) max_visible_ms: u64,
+    /// Shows the overlay on sustained `level`; off when unset.
+    pub(crate) visibility_level_gate: Option<VisibilityLevelGate>,
+    /// Levels kept for `get_level_history`; 0 turns the history off.
+    pub(crate) level_history_len: usize,
+    pub(crate) level_history_sample_ms: u64,
+    /// Shortest visible stretch once shown; earlier hides are deferred. 0 disables it.
+    pub(crate) min_visible_ms: u64,
+    /// Log each emitted state as a `key=value` line at `info`.
+    pub(crate) state_line_logging: bool,
+    pub(crate) state_line_fields: Vec<StateLineField>,
+    pub(crate) sequence_conflict: SequenceConflict,
+    /// Most level-only UDP patches applied per second; 0 is unlimited.
+    pub(crate) max_level_hz: u32,
+    /// Read `{"level": n}` payloads without the full JSON parser.
+    pub(crate) fast_level_parse: bool,
+    /// Reject bridge payloads carrying keys no field matches.
+    pub(crate) strict_fields: bool,
+    pub(crate) quarantine_policy: QuarantinePolicy,
+    pub(crate) log_sampling: LogSamplingPolicy,
+    /// Bridge transports, highest priority first.
+    pub(crate) source_priority: Vec<Transport>,
+    /// How long an update locks out lower-priority transports; 0 disables arbitration.
+    pub(crate) source_ownership_ms: u64,
+    /// Identical `overlay://error` events within this window are coalesced; 0 disables it.
+    pub(crate) error_dedupe_window_ms: u64,
+    /// Most `overlay://error` events emitted per second; 0 is unlimited.
+    pub(crate) max_error_events_per_sec: u32,
+    /// Fields a full-state update keeps when it carries them at their default.
+    pub(crate) sticky_fields: Vec<StateField>,
+    /// How `meters` combine into `level`.
+    pub(crate) master_meter: MasterMeterMode,
+    /// How long the peak marker holds before falling.
+    pub(crate) peak_hold_ms: u64,
+    /// Level units per second the peak marker falls after the hold.
+    pub(crate) peak_fall_per_sec: f64,
+    pub(crate) activity_weights: ActivityWeights,
+    /// Time constant of the `activity` fall; 0 drops it at once.
+    pub(crate) activity_decay_ms: u64,
+    /// How long `clipping` stays set after the last full-scale level; 0 disables it.
+    pub(crate) clip_hold_ms: u64,
+    pub(crate) transition_rules: Vec<TransitionRule>,
+    /// Read timeout of the UDP bridge socket.
+    pub(crate) bridge_poll_ms: u64,
+    pub(crate) placements: BTreeMap<String, Placement>,
+    /// Emit `overlay://announce` for screen readers when the message changes.
+    pub(crate) accessibility_announcements: bool,
+    pub(crate) severity_policy: SeverityPolicy,
+    /// `requires_ack` holds within this many ms of the last flash show without flashing.
+    pub(crate) attention_cooldown_ms: u64,
+    /// Behavior applied on entering each `connection` value; empty only changes the label.
+    pub(crate) connection_behaviors: BTreeMap<String, ConnectionBehavior>,
+    /// Format of the emitted `context` line, with `{target}` and `{message}` placeholders.
+    pub(crate) context_template: String,
+    /// Text with `{field}` placeholders rendered into `rendered_text`; off when unset.
+    pub(crate) display_template: Option<String>,
+}
+
+impl Default for OverlayConfig {
+    fn default() -> Self {
+        Self {
+            toggle_hotkey: None,
+            text_scale: 1.0,
+            geometry: OverlayGeometry::default(),
+            auto_expand: false,
+            collapsed_geometry: OverlayGeometry::collapsed(),
+            status_dot: None,
+            display_form: DisplayForm::default(),
+            show_animation: ShowAnimation::default(),
+            show_animation_ms: 200,
+            follow_dpi: true,
+            reposition_throttle_ms: 300,
+            safe_rect: None,
+            letterbox_aware: false,
+            max_segments: 100,
+            delivery_mode: DeliveryMode::default(),
+            emit_projection: Vec::new(),
+            private_fields: Vec::new(),
+            delta_emit: false,
+            delta_keyframe_ms: 1_000,
+            listening_debounce_ms: 0,
+            power_mode: PowerMode::default(),
+            render_adapter: RenderAdapter::default(),
+            sound_enabled: false,
+            auto_sound_cues: true,
+            http_port: 38486,
+            unix_socket_path: None,
+            source_file: None,
+            osc_addr: None,
+            obs_mirror_fps: 5,
+            event_log_level: EventLogLevel::default(),
+            event_log_source: "VoiceKey Overlay".to_string(),
+            splash_state: None,
+            splash_timeout_ms: 5_000,
+            heartbeat_timeout_ms: 0,
+            connection_grace_ms: 0,
+            show_reconnecting: true,
+            visible_only_for_targets: Vec::new(),
+            lock_behavior: LockBehavior::default(),
+            background_effect: BackgroundEffect::default(),
+            game_overlay_mode: false,
+            no_activate: true,
+            visibility_callback: None,
+            max_visible_ms: 0,
+            visibility_level_gate: None,
+            level_history_len: 0,
+            level_history_sample_ms: 50,
+            min_visible_ms: 0,
+            state_line_logging: false,
+            state_line_fields: default_state_line_fields(),
+            sequence_conflict: SequenceConflict::default(),
+            max_level_hz: 0,
+            fast_level_parse: true,
+            strict_fields: false,
+            quarantine_policy: QuarantinePolicy::default(),
+            log_sampling: LogSamplingPolicy::default(),
+            source_priority: default_source_priority(),
+            source_ownership_ms: 0,
+            error_dedupe_window_ms: 1_000,
+            max_error_events_per_sec: 10,
+            sticky_fields: Vec::new(),
+            master_meter: MasterMeterMode::default(),
+            peak_hold_ms: 1_000,
+            peak_fall_per_sec: 0.5,
+            clip_hold_ms: 1_500,
+            activity_weights: ActivityWeights::default(),
+            activity_decay_ms: 400,
+            transition_rules: Vec::new(),
+            bridge_poll_ms: 250,
+            placements: BTreeMap::new(),
+            accessibility_announcements: true,
+            severity_policy: SeverityPolicy::default(),
+            attention_cooldown_ms: 0,
+            connection_behaviors: BTreeMap::new(),
+            context_template: DEFAULT_CONTEXT_TEMPLATE.to_string(),
+            display_template: None,
+        }
+    }
+}
+
+fn config_file_path(app: &AppHandle, file_name: &str) -> Result<PathBuf, String> {
+    app.path()
+        .app_config_dir()
+        .map(|dir| dir.join(file_name))
+        .map_err(|error| format!("failed to resolve overlay config directory: {}", error))
+}
+
+/// The previous good copy, kept by each write.
+fn backup_path(path: &Path) -> PathBuf {
+    path.with_extension("json.bak")
+}
+
+/// `None` when the file is missing, or unreadable or invalid (logged).
+fn read_json_file<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    match fs::read_to_string(path) {
+        Ok(text) => serde_json::from_str(&text)
+            .map_err(|error| log::warn!("ignored invalid {}: {}", path.display(), error))
+            .ok(),
+        Err(error) if error.kind() == ErrorKind::NotFound => None,
+        Err(error) => {
+            log::warn!("failed to read {}: {}", path.display(), error);
+            None
+        }
+    }
+}
+
+/// Reads a JSON file from the app config dir, falling back to its backup and
+/// then to the default when it is missing or invalid.
+pub(crate) fn read_config_file<T: DeserializeOwned + Default>(app: &AppHandle, file_name: &str) -> T {
+    let path = match config_file_path(app, file_name) {
+        Ok(path) => path,
+        Err(error) => {
+            log::warn!("{}", error);
+            return T::default();
+        }
+    };
+    read_with_backup(&path)
+}
+
+/// `path`, else its backup, else the default.
+fn read_with_backup<T: DeserializeOwned + Default>(path: &Path) -> T {
+    if let Some(value) = read_json_file(path) {
+        return value;
+    }
+    let backup = backup_path(path);
+    if let Some(value) = read_json_file(&backup) {
+        log::warn!("loaded {} from its backup {}", path.display(), backup.display());
+        return value;
+    }
+    if path.exists() {
+        log::warn!("no usable backup of {}; using defaults", path.display());
+    }
+    T::default()
+}
+
+/// Writes through a temporary file and renames it into place, so a crash
+/// mid-write leaves the old file; the old file, if valid, becomes the backup.
+pub(crate) fn write_config_file<T: Serialize>(app: &AppHandle, file_name: &str, value: &T) -> Result<(), String> {
+    let path = config_file_path(app, file_name)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("failed to create {}: {}", parent.display(), error))?;
+    }
+    let text = serde_json::to_string_pretty(value).map_err(|error| error.to_string())?;
+    let staging = path.with_extension("json.tmp");
+    fs::write(&staging, text).map_err(|error| format!("failed to write {}: {}", staging.display(), error))?;
+    if read_json_file::<Value>(&path).is_some() {
+        let backup = backup_path(&path);
+        if let Err(error) = fs::rename(&path, &backup) {
+            log::warn!("failed to back up {}: {}", path.display(), error);
+        }
+    }
+    fs::rename(&staging, &path).map_err(|error| format!("failed to replace {}: {}", path.display(), error))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum RepairOutcome {
+    Missing,
+    Valid,
+    /// The corrupt file was set aside and its backup put in its place.
+    RestoredFromBackup,
+    /// The corrupt file was set aside with no usable backup; defaults load next time.
+    Reset,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct RepairReport {
+    file: &'static str,
+    outcome: RepairOutcome,
+    /// Where a corrupt file was moved.
+    corrupt_copy: Option<String>,
+}
+
+fn repair_file<T: DeserializeOwned>(app: &AppHandle, file: &'static str) -> Result<RepairReport, String> {
+    repair_path::<T>(&config_file_path(app, file)?, file, unix_millis())
+}
+
+/// Repairs one file at `path`; `now_ms` names the set-aside corrupt copy.
+fn repair_path<T: DeserializeOwned>(path: &Path, file: &'static str, now_ms: u64) -> Result<RepairReport, String> {
+    let report = |outcome, corrupt_copy| RepairReport {
+        file,
+        outcome,
+        corrupt_copy,
+    };
+    if !path.exists() {
+        return Ok(report(RepairOutcome::Missing, None));
+    }
+    if read_json_file::<T>(path).is_some() {
+        return Ok(report(RepairOutcome::Valid, None));
+    }
+    let corrupt = path.with_extension(format!("json.corrupt-{}", now_ms));
+    fs::rename(path, &corrupt).map_err(|error| format!("failed to set aside {}: {}", path.display(), error))?;
+    log::warn!("moved corrupt {} to {}", path.display(), corrupt.display());
+    let corrupt_copy = Some(corrupt.display().to_string());
+    let backup = backup_path(path);
+    if read_json_file::<T>(&backup).is_some() {
+        fs::copy(&backup, path).map_err(|error| format!("failed to restore {}: {}", path.display(), error))?;
+        log::info!("restored {} from {}", path.display(), backup.display());
+        return Ok(report(RepairOutcome::RestoredFromBackup, corrupt_copy));
+    }
+    Ok(report(RepairOutcome::Reset, corrupt_copy))
+}
+
+/// Checks the persisted config and presets; a corrupt file is kept aside as
+/// `*.corrupt-<unix ms>` and replaced by its backup, or dropped for defaults.
+#[tauri::command]
+pub(crate) fn repair_persisted_state(app: AppHandle) -> Result<Vec<RepairReport>, String> {
+    Ok(vec![
+        repair_file::<OverlayConfig>(&app, CONFIG_FILE_NAME)?,
+        repair_file::<BTreeMap<String, OverlayState>>(&app, PRESETS_FILE_NAME)?,
+    ])
+}
+
+pub(crate) fn load_config(app: &AppHandle) -> OverlayConfig {
+    read_config_file(app, CONFIG_FILE_NAME)
+}
+
+pub(crate) fn save_config(app: &AppHandle, config: &OverlayConfig) -> Result<(), String> {
+    write_config_file(app, CONFIG_FILE_NAME, config)
+}
+
+#[tauri::command]
+pub(crate) fn set_delivery_mode(
+    mode: DeliveryMode,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    update_config(&app, shared.inner(), |config| config.delivery_mode = mode)?;
+    Ok(())
+}
+
+/// Mutates the live config and persists the result.
+pub(crate) fn update_config(
+    app: &AppHandle,
+    shared: &Arc<SharedOverlayState>,
+    update: impl FnOnce(&mut OverlayConfig),
+) -> Result<OverlayConfig, String> {
+    let mut config = lock_config(shared)?;
+    update(&mut config);
+    save_config(app, &config)?;
+    Ok(config.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, fs};
+
+    use super::{backup_path, read_with_backup, repair_path, RepairOutcome};
+
+    type Presets = BTreeMap<String, u32>;
+
+    #[test]
+    fn a_truncated_file_falls_back_to_and_is_repaired_from_its_backup() {
+        let dir = std::env::temp_dir().join(format!("voicekey-persist-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("presets.json");
+        fs::write(backup_path(&path), r#"{ "calm": 1, "loud": 2 }"#).unwrap();
+        fs::write(&path, r#"{ "calm": 1, "lo"#).unwrap();
+        let expected = Presets::from([("calm".to_string(), 1), ("loud".to_string(), 2)]);
+
+        assert_eq!(read_with_backup::<Presets>(&path), expected);
+
+        let report = repair_path::<Presets>(&path, "presets.json", 42).unwrap();
+        assert_eq!(report.outcome, RepairOutcome::RestoredFromBackup);
+        assert_eq!(fs::read_to_string(path.with_extension("json.corrupt-42")).unwrap(), r#"{ "calm": 1, "lo"#);
+        assert_eq!(serde_json::from_str::<Presets>(&fs::read_to_string(&path).unwrap()).unwrap(), expected);
+        assert_eq!(repair_path::<Presets>(&path, "presets.json", 43).unwrap().outcome, RepairOutcome::Valid);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
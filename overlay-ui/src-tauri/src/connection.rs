@@ -0,0 +1,86 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use crate::{config::update_config, OverlayState, SharedOverlayState};
+
+const MAX_CONNECTION_BEHAVIORS: usize = 16;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "snake_case")]
+pub(crate) struct ConnectionBehavior {
+    /// Show (`true`) or hide (`false`) the overlay on entering the state; unset leaves `visible` alone.
+    force_visible: Option<bool>,
+    /// Flash for attention while the state lasts, subject to the attention cooldown.
+    attention: bool,
+    /// Message set on entering the state, e.g. `Reconnecting…`.
+    auto_message: Option<String>,
+}
+
+/// Applies the configured behavior whenever `connection` changes. With no
+/// entries, the default, `connection` only changes the label.
+#[derive(Default)]
+pub(crate) struct ConnectionBehaviors {
+    /// Set while the current connection state flashes.
+    flashing: AtomicBool,
+}
+
+impl ConnectionBehaviors {
+    pub(crate) fn apply(
+        &self,
+        shared: &Arc<SharedOverlayState>,
+        behaviors: &BTreeMap<String, ConnectionBehavior>,
+        attention_cooldown: Duration,
+        previous: &OverlayState,
+        next: &mut OverlayState,
+    ) {
+        if previous.connection != next.connection {
+            let behavior = behaviors.get(&next.connection);
+            let flash = behavior.is_some_and(|behavior| behavior.attention)
+                && shared.message_ack.admit_flash(attention_cooldown, shared.scheduler.clock().now());
+            self.flashing.store(flash, Ordering::Relaxed);
+            if let Some(behavior) = behavior {
+                if let Some(visible) = behavior.force_visible {
+                    next.visible = visible;
+                }
+                // A message awaiting acknowledgment keeps the screen.
+                if behavior.auto_message.is_some() && !shared.message_ack.is_pending() {
+                    next.message = behavior.auto_message.clone();
+                }
+            }
+        }
+        next.attention |= self.flashing.load(Ordering::Relaxed);
+    }
+
+    pub(crate) fn clear(&self) {
+        self.flashing.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Sets the behavior table keyed by `connection` value, e.g. `disconnected`.
+#[tauri::command]
+pub(crate) fn set_connection_behaviors(
+    behaviors: BTreeMap<String, ConnectionBehavior>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if behaviors.len() > MAX_CONNECTION_BEHAVIORS {
+        return Err(format!(
+            "at most {} connection behaviors are allowed",
+            MAX_CONNECTION_BEHAVIORS
+        ));
+    }
+    if behaviors.keys().any(|connection| connection.trim().is_empty()) {
+        return Err("connection states must not be empty".to_string());
+    }
+    update_config(&app, shared.inner(), |config| config.connection_behaviors = behaviors)?;
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use tauri::{AppHandle, State};
+
+use crate::{config::update_config, lock_state, OverlayState, SharedOverlayState};
+
+pub(crate) const DEFAULT_CONTEXT_TEMPLATE: &str = "{target} — {message}";
+const UNKNOWN_TARGET: &str = "unknown";
+
+/// `template` filled with `target` and `message`; when only one of them is
+/// set it stands alone, and with neither there is no context line.
+pub(crate) fn context_line(template: &str, state: &OverlayState) -> Option<String> {
+    let target = Some(state.target.trim()).filter(|target| !target.is_empty() && *target != UNKNOWN_TARGET);
+    let message = state.message.as_deref().map(str::trim).filter(|message| !message.is_empty());
+    match (target, message) {
+        (Some(target), Some(message)) => Some(template.replace("{target}", target).replace("{message}", message)),
+        (Some(only), None) | (None, Some(only)) => Some(only.to_string()),
+        (None, None) => None,
+    }
+}
+
+#[tauri::command]
+pub(crate) fn get_context_line(shared: State<'_, Arc<SharedOverlayState>>) -> Result<Option<String>, String> {
+    Ok(lock_state(shared.inner())?.context.clone())
+}
+
+/// Takes effect with the next state update.
+#[tauri::command]
+pub(crate) fn set_context_template(
+    template: String,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if !template.contains("{target}") && !template.contains("{message}") {
+        return Err("context template must contain {target} or {message}".to_string());
+    }
+    update_config(&app, shared.inner(), |config| config.context_template = template)?;
+    Ok(())
+}
@@ -0,0 +1,218 @@
+use std::{
+    fs,
+    io::{BufRead, BufReader, Write},
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use tauri::AppHandle;
+
+use crate::{
+    capture::capture_window,
+    clock::{Clock, SharedClock, SystemClock},
+    decode_payload, lock_config, lock_state, main_window,
+    mute::fnv1a,
+    position::PositionTrigger,
+    reposition::request_reposition,
+    scheduler::{Scheduler, SCHEDULER_TICK},
+    timeline::{ChangeSource, SourceScope},
+    SharedOverlayState,
+};
+
+/// Loopback port the control socket listens on; unset leaves it off.
+const CONTROL_PORT_VAR: &str = "VOICEKEY_CONTROL_PORT";
+const MAX_ADVANCE_MS: u64 = 600_000;
+
+/// System time plus an offset only `advance_clock` moves, so a test can jump
+/// past decays, TTLs and countdowns instead of waiting for them.
+#[derive(Default)]
+pub(crate) struct ControlClock {
+    offset_ms: AtomicU64,
+}
+
+impl ControlClock {
+    fn offset_ms(&self) -> u64 {
+        self.offset_ms.load(Ordering::Acquire)
+    }
+}
+
+impl Clock for ControlClock {
+    fn now(&self) -> Instant {
+        Instant::now() + Duration::from_millis(self.offset_ms())
+    }
+
+    fn unix_millis(&self) -> u64 {
+        SystemClock.unix_millis() + self.offset_ms()
+    }
+}
+
+/// Drives the scheduler from the control clock; called before the state is shared.
+pub(crate) fn with_control_clock(mut shared: SharedOverlayState) -> SharedOverlayState {
+    let clock: Arc<dyn Clock> = shared.control_clock.clone();
+    shared.scheduler = Scheduler::with_clock(SharedClock::from(clock));
+    shared
+}
+
+#[derive(Debug, Deserialize)]
+struct ControlRequest {
+    #[serde(default)]
+    id: Value,
+    command: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct AdvanceParams {
+    ms: u64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct CaptureParams {
+    /// Where to write the raw RGBA pixels; only the hash is returned when unset.
+    #[serde(default)]
+    path: Option<String>,
+}
+
+fn params<T: for<'de> Deserialize<'de>>(params: Value) -> Result<T, String> {
+    serde_json::from_value(params).map_err(|error| format!("invalid params: {}", error))
+}
+
+fn get_state(shared: &Arc<SharedOverlayState>) -> Result<Value, String> {
+    let state = lock_state(shared)?;
+    serde_json::to_value(&*state).map_err(|error| error.to_string())
+}
+
+/// Applies `params` as one bridge payload, routed like a datagram.
+fn set_state(app: &AppHandle, shared: &Arc<SharedOverlayState>, params: Value) -> Result<Value, String> {
+    let (update, _) = decode_payload(&params.to_string())?;
+    let _source = SourceScope::enter(ChangeSource::Command);
+    update.apply(app, shared)?;
+    get_state(shared)
+}
+
+/// Moves the clock forward a scheduler tick at a time, running due tasks after
+/// each step so repeating ones fire as often as they would in real time. Every
+/// timed policy reads this clock and re-checks from a scheduled task, so holds,
+/// releases and the heartbeat all see the jump.
+fn advance_clock(shared: &Arc<SharedOverlayState>, params: AdvanceParams) -> Result<Value, String> {
+    if params.ms > MAX_ADVANCE_MS {
+        return Err(format!("advance must be at most {} ms", MAX_ADVANCE_MS));
+    }
+    let step_ms = SCHEDULER_TICK.as_millis() as u64;
+    let mut remaining = params.ms;
+    while remaining > 0 {
+        let step = remaining.min(step_ms);
+        shared.control_clock.offset_ms.fetch_add(step, Ordering::AcqRel);
+        shared
+            .scheduler
+            .run_due(shared)
+            .ok_or_else(|| "scheduler lock poisoned".to_string())?;
+        remaining -= step;
+    }
+    Ok(json!({
+        "offset_ms": shared.control_clock.offset_ms(),
+        "unix_millis": shared.scheduler.clock().unix_millis(),
+    }))
+}
+
+/// What the DPI watch does when the overlay crosses onto another monitor.
+fn trigger_monitor_change(app: &AppHandle, shared: &Arc<SharedOverlayState>) -> Result<Value, String> {
+    let relayout = lock_config(shared)?.follow_dpi;
+    request_reposition(&main_window(app)?, PositionTrigger::MonitorChange, relayout);
+    Ok(Value::Null)
+}
+
+fn capture(app: &AppHandle, params: CaptureParams) -> Result<Value, String> {
+    let frame = capture_window(&main_window(app)?)?;
+    if let Some(path) = &params.path {
+        fs::write(path, &frame.rgba).map_err(|error| format!("failed to write {}: {}", path, error))?;
+    }
+    Ok(json!({
+        "width": frame.width,
+        "height": frame.height,
+        "hash": format!("{:016x}", fnv1a(&frame.rgba)),
+    }))
+}
+
+fn handle_line(app: &AppHandle, shared: &Arc<SharedOverlayState>, line: &str) -> Value {
+    let request = match serde_json::from_str::<ControlRequest>(line) {
+        Ok(request) => request,
+        Err(error) => return json!({ "id": Value::Null, "error": format!("invalid request: {}", error) }),
+    };
+    let outcome = match request.command.as_str() {
+        "get_state" => get_state(shared),
+        "set_state" => set_state(app, shared, request.params),
+        "advance_clock" => params(request.params).and_then(|params| advance_clock(shared, params)),
+        "trigger_monitor_change" => trigger_monitor_change(app, shared),
+        "capture" => match request.params {
+            Value::Null => Ok(CaptureParams::default()),
+            params_value => params(params_value),
+        }
+        .and_then(|params| capture(app, params)),
+        other => Err(format!("unknown command {:?}", other)),
+    };
+    let mut response = Map::new();
+    response.insert("id".to_string(), request.id);
+    match outcome {
+        Ok(result) => response.insert("result".to_string(), result),
+        Err(error) => response.insert("error".to_string(), Value::from(error)),
+    };
+    Value::Object(response)
+}
+
+fn serve(app: &AppHandle, shared: &Arc<SharedOverlayState>, stream: TcpStream) -> std::io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut response = handle_line(app, shared, &line).to_string();
+        response.push('\n');
+        writer.write_all(response.as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Debug-only scripting socket for end-to-end tests: newline-delimited
+/// `{ "id", "command", "params" }` requests on loopback, each answered with
+/// `{ "id", "result" }` or `{ "id", "error" }`.
+pub(crate) fn start_control_socket(app: AppHandle, shared: Arc<SharedOverlayState>) {
+    let Ok(port) = std::env::var(CONTROL_PORT_VAR) else {
+        return;
+    };
+    let port = match port.trim().parse::<u16>() {
+        Ok(port) => port,
+        Err(error) => {
+            log::warn!("ignoring {}={:?}: {}", CONTROL_PORT_VAR, port, error);
+            return;
+        }
+    };
+    let listener = match TcpListener::bind((Ipv4Addr::LOCALHOST, port)) {
+        Ok(listener) => listener,
+        Err(error) => {
+            log::warn!("failed to bind control socket on port {}: {}", port, error);
+            return;
+        }
+    };
+    log::info!("control socket listening on 127.0.0.1:{}", port);
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let app = app.clone();
+            let shared = shared.clone();
+            thread::spawn(move || {
+                if let Err(error) = serve(&app, &shared, stream) {
+                    log::warn!("control socket connection closed: {}", error);
+                }
+            });
+        }
+    });
+}
@@ -0,0 +1,58 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use tauri::{AppHandle, State};
+
+use crate::{update_overlay_state, SharedOverlayState};
+
+const MIN_CYCLE_INTERVAL_MS: u64 = 50;
+
+/// Debug aid that rotates `target` through a list, for testing per-target behavior.
+#[derive(Default)]
+pub(crate) struct TargetCycle {
+    generation: AtomicU64,
+}
+
+impl TargetCycle {
+    fn is_current(&self, generation: u64) -> bool {
+        self.generation.load(Ordering::Relaxed) == generation
+    }
+}
+
+/// Replaces any running cycle; runs until `stop_cycle_targets`.
+#[tauri::command]
+pub(crate) fn cycle_targets(
+    list: Vec<String>,
+    interval_ms: u64,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if list.is_empty() {
+        return Err("target list must not be empty".to_string());
+    }
+    if interval_ms < MIN_CYCLE_INTERVAL_MS {
+        return Err(format!("interval must be at least {} ms", MIN_CYCLE_INTERVAL_MS));
+    }
+    let shared = shared.inner().clone();
+    let generation = shared.target_cycle.generation.fetch_add(1, Ordering::Relaxed) + 1;
+    let mut targets = list.into_iter().cycle();
+    shared.scheduler.repeat("target_cycle", Duration::ZERO, move |shared| {
+        if !shared.target_cycle.is_current(generation) {
+            return None;
+        }
+        let target = targets.next()?;
+        update_overlay_state(&app, shared, |state| state.target = target).ok()?;
+        Some(Duration::from_millis(interval_ms))
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn stop_cycle_targets(shared: State<'_, Arc<SharedOverlayState>>) {
+    shared.target_cycle.generation.fetch_add(1, Ordering::Relaxed);
+}
@@ -0,0 +1,87 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use serde_json::{Map, Value};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::{config::update_config, SharedOverlayState};
+
+const MIN_DELTA_KEYFRAME_MS: u64 = 100;
+const MAX_DELTA_KEYFRAME_MS: u64 = 60_000;
+
+#[derive(Default)]
+struct DeltaBase {
+    /// The shape the frontend holds after the last emit.
+    emitted: Option<Map<String, Value>>,
+    keyframe_at: Option<Instant>,
+}
+
+/// Emits `overlay://delta` with only the fields changed since the last emit,
+/// and a full `overlay://state` keyframe every `delta_keyframe_ms`.
+#[derive(Default)]
+pub(crate) struct DeltaEmitter {
+    base: Mutex<DeltaBase>,
+}
+
+impl DeltaEmitter {
+    pub(crate) fn reset(&self) {
+        if let Ok(mut base) = self.base.lock() {
+            *base = DeltaBase::default();
+        }
+    }
+
+    pub(crate) fn emit(&self, app: &AppHandle, payload: Value, keyframe: Duration, now: Instant) {
+        let Value::Object(fields) = payload else {
+            return;
+        };
+        let Ok(mut base) = self.base.lock() else {
+            return;
+        };
+        let keyframe_due = base.keyframe_at.map_or(true, |at| now.duration_since(at) >= keyframe);
+        let emitted = match base.emitted.as_ref() {
+            Some(emitted) if !keyframe_due => emitted,
+            _ => {
+                let _ = app.emit("overlay://state", &fields);
+                base.emitted = Some(fields);
+                base.keyframe_at = Some(now);
+                return;
+            }
+        };
+        let delta: Map<String, Value> = fields
+            .iter()
+            .filter(|(key, value)| emitted.get(*key) != Some(*value))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        if !delta.is_empty() {
+            let _ = app.emit("overlay://delta", &delta);
+        }
+        base.emitted = Some(fields);
+    }
+}
+
+/// Switches state events to deltas with periodic keyframes; `keyframe_ms`
+/// keeps the current interval when omitted.
+#[tauri::command]
+pub(crate) fn set_delta_emit(
+    enabled: bool,
+    keyframe_ms: Option<u64>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if keyframe_ms.is_some_and(|ms| !(MIN_DELTA_KEYFRAME_MS..=MAX_DELTA_KEYFRAME_MS).contains(&ms)) {
+        return Err(format!(
+            "keyframe interval must be between {} and {} ms",
+            MIN_DELTA_KEYFRAME_MS, MAX_DELTA_KEYFRAME_MS
+        ));
+    }
+    update_config(&app, shared.inner(), |config| {
+        config.delta_emit = enabled;
+        if let Some(keyframe_ms) = keyframe_ms {
+            config.delta_keyframe_ms = keyframe_ms;
+        }
+    })?;
+    shared.delta_emitter.reset();
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::{logtap::LogTap, lock_state, SharedOverlayState};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct CollectionSizes {
+    timeline: usize,
+    position_history: usize,
+    logs: usize,
+    muted_messages: usize,
+    pinned_fields: usize,
+    presets: usize,
+    queued_sequence_updates: usize,
+    pending_error_events: usize,
+    steps: usize,
+    words: usize,
+    segments: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct RuntimeDiagnostics {
+    /// Working set in bytes; absent where it can't be read.
+    resident_bytes: Option<u64>,
+    thread_count: Option<u32>,
+    /// Delays and animations waiting on the scheduler thread.
+    scheduled_tasks: usize,
+    collections: CollectionSizes,
+}
+
+#[cfg(windows)]
+fn resident_bytes() -> Option<u64> {
+    use windows_sys::Win32::System::{
+        ProcessStatus::{K32GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS},
+        Threading::GetCurrentProcess,
+    };
+
+    let mut counters = PROCESS_MEMORY_COUNTERS::default();
+    let size = std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32;
+    // SAFETY: `counters` is a writable PROCESS_MEMORY_COUNTERS of `size` bytes
+    // and the pseudo-handle of the current process needs no closing.
+    let ok = unsafe { K32GetProcessMemoryInfo(GetCurrentProcess(), &mut counters, size) };
+    (ok != 0).then_some(counters.WorkingSetSize as u64)
+}
+
+#[cfg(not(windows))]
+fn resident_bytes() -> Option<u64> {
+    None
+}
+
+#[cfg(windows)]
+fn thread_count() -> Option<u32> {
+    use windows_sys::Win32::{
+        Foundation::{CloseHandle, INVALID_HANDLE_VALUE},
+        System::{
+            Diagnostics::ToolHelp::{CreateToolhelp32Snapshot, Thread32First, Thread32Next, TH32CS_SNAPTHREAD, THREADENTRY32},
+            Threading::GetCurrentProcessId,
+        },
+    };
+
+    // SAFETY: the snapshot handle is checked and closed before returning, and
+    // `entry` has `dwSize` set as Thread32First requires.
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0);
+        if snapshot == INVALID_HANDLE_VALUE {
+            return None;
+        }
+        let process = GetCurrentProcessId();
+        let mut entry = THREADENTRY32 {
+            dwSize: std::mem::size_of::<THREADENTRY32>() as u32,
+            ..Default::default()
+        };
+        let mut count = 0;
+        let mut more = Thread32First(snapshot, &mut entry) != 0;
+        while more {
+            if entry.th32OwnerProcessID == process {
+                count += 1;
+            }
+            more = Thread32Next(snapshot, &mut entry) != 0;
+        }
+        CloseHandle(snapshot);
+        Some(count)
+    }
+}
+
+#[cfg(not(windows))]
+fn thread_count() -> Option<u32> {
+    None
+}
+
+/// Process memory, thread counts and the sizes of the backend's buffers, for
+/// spotting leaks over long sessions. Each buffer is locked only to read its length.
+#[tauri::command]
+pub(crate) fn get_runtime_diagnostics(
+    shared: State<'_, Arc<SharedOverlayState>>,
+    tap: State<'_, Arc<LogTap>>,
+) -> Result<RuntimeDiagnostics, String> {
+    let shared = shared.inner();
+    let (steps, words, segments) = {
+        let state = lock_state(shared)?;
+        (state.steps.len(), state.words.len(), state.segments.len())
+    };
+    Ok(RuntimeDiagnostics {
+        resident_bytes: resident_bytes(),
+        thread_count: thread_count(),
+        scheduled_tasks: shared.scheduler.len(),
+        collections: CollectionSizes {
+            timeline: shared.timeline.len(),
+            position_history: shared.position_history.len(),
+            logs: tap.len(),
+            muted_messages: shared.muted_messages.len(),
+            pinned_fields: shared.pinned_fields.len(),
+            presets: shared.presets.len(),
+            queued_sequence_updates: shared.sequencer.queued_len(),
+            pending_error_events: shared.error_events.len(),
+            steps,
+            words,
+            segments,
+        },
+    })
+}
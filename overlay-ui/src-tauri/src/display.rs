@@ -0,0 +1,94 @@
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, Monitor, WebviewWindow};
+
+use crate::{current_or_primary_monitor, main_window, SharedOverlayState};
+
+/// Monitors have no stable id in Tauri; name plus origin tells them apart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MonitorIdentity {
+    name: Option<String>,
+    x: i32,
+    y: i32,
+}
+
+impl MonitorIdentity {
+    fn of(monitor: &Monitor) -> Self {
+        Self {
+            name: monitor.name().cloned(),
+            x: monitor.position().x,
+            y: monitor.position().y,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct DisplayContext {
+    /// Position in the OS monitor list.
+    index: Option<usize>,
+    name: Option<String>,
+    width: u32,
+    height: u32,
+    scale_factor: f64,
+    /// The overlay is on a different monitor than when it was last positioned.
+    changed_since_last_position: bool,
+}
+
+/// The monitor the overlay was on when it was last positioned.
+#[derive(Default)]
+pub(crate) struct DisplayTracker {
+    positioned_on: Mutex<Option<MonitorIdentity>>,
+}
+
+fn display_context(window: &WebviewWindow, monitor: &Monitor, changed: bool) -> DisplayContext {
+    let identity = MonitorIdentity::of(monitor);
+    let index = window
+        .available_monitors()
+        .ok()
+        .and_then(|monitors| monitors.iter().position(|other| MonitorIdentity::of(other) == identity));
+    DisplayContext {
+        index,
+        name: identity.name,
+        width: monitor.size().width,
+        height: monitor.size().height,
+        scale_factor: monitor.scale_factor(),
+        changed_since_last_position: changed,
+    }
+}
+
+/// Called after each move; emits `overlay://display` when the overlay landed on another monitor.
+pub(crate) fn record_positioned_monitor(window: &WebviewWindow, monitor: Option<&Monitor>) {
+    let Some(monitor) = monitor else {
+        return;
+    };
+    let identity = MonitorIdentity::of(monitor);
+    let shared = window.state::<Arc<SharedOverlayState>>();
+    let previous = match shared.display_tracker.positioned_on.lock() {
+        Ok(mut positioned_on) => positioned_on.replace(identity.clone()),
+        Err(_) => return,
+    };
+    if previous.is_some_and(|previous| previous != identity) {
+        log::info!("overlay moved to monitor {}", identity.name.as_deref().unwrap_or("unnamed"));
+        let _ = window.emit("overlay://display", display_context(window, monitor, false));
+    }
+}
+
+/// The overlay's current monitor, and whether it differs from the one at last positioning.
+#[tauri::command]
+pub(crate) fn get_display_context(app: AppHandle) -> Result<Option<DisplayContext>, String> {
+    let window = main_window(&app)?;
+    let Some(monitor) = current_or_primary_monitor(&window).map_err(|error| error.to_string())? else {
+        return Ok(None);
+    };
+    let shared = app.state::<Arc<SharedOverlayState>>();
+    let changed = shared
+        .display_tracker
+        .positioned_on
+        .lock()
+        .map_err(|_| "display tracker lock poisoned".to_string())?
+        .as_ref()
+        .is_some_and(|positioned_on| *positioned_on != MonitorIdentity::of(&monitor));
+    Ok(Some(display_context(&window, &monitor, changed)))
+}
@@ -0,0 +1,230 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, PhysicalPosition, PhysicalSize, State, WebviewWindow};
+
+use crate::{main_window, position::PositionTrigger, position_overlay_window, SharedOverlayState};
+
+const DOCK_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const DOCK_GAP_PX: f64 = 8.0;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum DockSide {
+    #[default]
+    Below,
+    Above,
+}
+
+/// Outer bounds of a top-level window, in physical screen pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct WindowBounds {
+    pub(crate) left: i32,
+    pub(crate) top: i32,
+    pub(crate) right: i32,
+    pub(crate) bottom: i32,
+}
+
+struct DockTarget {
+    side: DockSide,
+    bounds: WindowBounds,
+    generation: u64,
+}
+
+/// The application window the overlay currently follows, if any.
+#[derive(Default)]
+pub(crate) struct Dock {
+    target: Mutex<Option<DockTarget>>,
+    generation: AtomicU64,
+}
+
+impl Dock {
+    fn is_current(&self, generation: u64) -> bool {
+        self.generation.load(Ordering::Acquire) == generation
+    }
+
+    fn update_bounds(&self, generation: u64, bounds: WindowBounds) -> bool {
+        let Ok(mut target) = self.target.lock() else {
+            return false;
+        };
+        match target.as_mut() {
+            Some(target) if target.generation == generation && target.bounds != bounds => {
+                target.bounds = bounds;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn clear(&self) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        if let Ok(mut target) = self.target.lock() {
+            *target = None;
+        }
+    }
+}
+
+/// Bounds of the window the overlay is docked to, if any.
+pub(crate) fn docked_bounds(shared: &SharedOverlayState) -> Option<WindowBounds> {
+    shared.dock.target.lock().ok()?.as_ref().map(|target| target.bounds)
+}
+
+/// Where the overlay should sit while docked, centered against the target window.
+pub(crate) fn docked_position(window: &WebviewWindow, size: PhysicalSize<u32>) -> Option<PhysicalPosition<i32>> {
+    let shared = window.state::<Arc<SharedOverlayState>>();
+    let target = shared.dock.target.lock().ok()?;
+    let target = target.as_ref()?;
+    let gap = (DOCK_GAP_PX * window.scale_factor().unwrap_or(1.0)) as i32;
+    let bounds = target.bounds;
+    let x = bounds.left + (bounds.right - bounds.left - size.width as i32) / 2;
+    let y = match target.side {
+        DockSide::Below => bounds.bottom + gap,
+        DockSide::Above => bounds.top - gap - size.height as i32,
+    };
+    Some(PhysicalPosition::new(x, y))
+}
+
+fn track_dock_target(window: WebviewWindow, shared: Arc<SharedOverlayState>, title: String, generation: u64) {
+    thread::spawn(move || loop {
+        thread::sleep(DOCK_POLL_INTERVAL);
+        if !shared.dock.is_current(generation) {
+            break;
+        }
+        match find_window_bounds(&title) {
+            Some(bounds) => {
+                if shared.dock.update_bounds(generation, bounds) {
+                    let _ = position_overlay_window(&window, PositionTrigger::Dock);
+                }
+            }
+            None => {
+                log::info!(
+                    "dock target {:?} disappeared; falling back to anchored positioning",
+                    title
+                );
+                shared.dock.clear();
+                let _ = position_overlay_window(&window, PositionTrigger::Dock);
+                break;
+            }
+        }
+    });
+}
+
+/// Follows the first visible top-level window whose title contains `title_substring`.
+#[tauri::command]
+pub(crate) fn dock_to_window(
+    title_substring: String,
+    side: Option<DockSide>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    let title = title_substring.trim().to_string();
+    if title.is_empty() {
+        return Err("dock title must not be empty".to_string());
+    }
+    let window = main_window(&app)?;
+    let bounds = find_window_bounds(&title)
+        .ok_or_else(|| format!("no visible window title contains {:?}", title))?;
+    let generation = shared.dock.generation.fetch_add(1, Ordering::AcqRel) + 1;
+    {
+        let mut target = shared
+            .dock
+            .target
+            .lock()
+            .map_err(|_| "dock lock poisoned".to_string())?;
+        *target = Some(DockTarget {
+            side: side.unwrap_or_default(),
+            bounds,
+            generation,
+        });
+    }
+    position_overlay_window(&window, PositionTrigger::Dock).map_err(|error| error.to_string())?;
+    track_dock_target(window, shared.inner().clone(), title, generation);
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn undock_window(app: AppHandle, shared: State<'_, Arc<SharedOverlayState>>) -> Result<(), String> {
+    shared.dock.clear();
+    position_overlay_window(&main_window(&app)?, PositionTrigger::Dock).map_err(|error| error.to_string())
+}
+
+/// Case-insensitive title match over visible, non-minimized top-level windows
+/// of other processes.
+#[cfg(windows)]
+pub(crate) fn find_window_bounds(title_substring: &str) -> Option<WindowBounds> {
+    use windows_sys::{
+        core::BOOL,
+        Win32::{
+            Foundation::{HWND, LPARAM, RECT},
+            System::Threading::GetCurrentProcessId,
+            UI::WindowsAndMessaging::{
+                EnumWindows, GetWindowRect, GetWindowTextLengthW, GetWindowTextW,
+                GetWindowThreadProcessId, IsIconic, IsWindowVisible,
+            },
+        },
+    };
+
+    struct Search {
+        needle: String,
+        own_process: u32,
+        found: Option<WindowBounds>,
+    }
+
+    unsafe extern "system" fn visit(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        // SAFETY: `lparam` is the `&mut Search` passed to EnumWindows below.
+        let search = &mut *(lparam as *mut Search);
+        if IsWindowVisible(hwnd) == 0 || IsIconic(hwnd) != 0 {
+            return 1;
+        }
+        let mut process = 0_u32;
+        GetWindowThreadProcessId(hwnd, &mut process);
+        if process == search.own_process {
+            return 1;
+        }
+        let length = GetWindowTextLengthW(hwnd);
+        if length <= 0 {
+            return 1;
+        }
+        let mut buffer = vec![0_u16; length as usize + 1];
+        let copied = GetWindowTextW(hwnd, buffer.as_mut_ptr(), buffer.len() as i32);
+        let title = String::from_utf16_lossy(&buffer[..copied.max(0) as usize]);
+        if !title.to_lowercase().contains(&search.needle) {
+            return 1;
+        }
+        let mut rect = RECT::default();
+        if GetWindowRect(hwnd, &mut rect) == 0 {
+            return 1;
+        }
+        search.found = Some(WindowBounds {
+            left: rect.left,
+            top: rect.top,
+            right: rect.right,
+            bottom: rect.bottom,
+        });
+        0
+    }
+
+    let mut search = Search {
+        needle: title_substring.to_lowercase(),
+        // SAFETY: no preconditions.
+        own_process: unsafe { GetCurrentProcessId() },
+        found: None,
+    };
+    // SAFETY: `visit` only dereferences `lparam` as the `Search` that outlives this call.
+    unsafe {
+        EnumWindows(Some(visit), &mut search as *mut Search as LPARAM);
+    }
+    search.found
+}
+
+#[cfg(not(windows))]
+pub(crate) fn find_window_bounds(_title_substring: &str) -> Option<WindowBounds> {
+    None
+}
@@ -0,0 +1,126 @@
+#[cfg(debug_assertions)]
+use std::sync::Mutex;
+use std::sync::Arc;
+
+use tauri::{AppHandle, PhysicalPosition, PhysicalSize, State, WebviewWindow, WindowEvent};
+
+use crate::{
+    config::update_config, layout, lock_config, main_window, position::PositionTrigger, reposition,
+    update_overlay_state, SharedOverlayState, TASKBAR_MARGIN_PX,
+};
+
+/// The default anchor on a monitor: centred, the taskbar margin (in logical
+/// pixels, scaled by `scale_factor`) above the bottom edge, pinned inside it.
+pub(crate) fn bottom_center_position(
+    monitor_pos: PhysicalPosition<i32>,
+    monitor_size: PhysicalSize<u32>,
+    window_size: PhysicalSize<u32>,
+    scale_factor: f64,
+) -> PhysicalPosition<i32> {
+    let margin = (f64::from(TASKBAR_MARGIN_PX) * scale_factor) as i32;
+    let x = monitor_pos.x + ((monitor_size.width as i32 - window_size.width as i32) / 2).max(0);
+    let y = monitor_pos.y + (monitor_size.height as i32 - window_size.height as i32 - margin).max(0);
+    PhysicalPosition::new(x, y)
+}
+
+/// Emits the new `scale_factor` when the overlay crosses onto a monitor with a
+/// different DPI and, with `follow_dpi`, re-applies the logical layout there.
+pub(crate) fn start_dpi_watch(window: &WebviewWindow, app: AppHandle, shared: Arc<SharedOverlayState>) {
+    let target = window.clone();
+    window.on_window_event(move |event| {
+        let WindowEvent::ScaleFactorChanged { scale_factor, .. } = event else {
+            return;
+        };
+        let scale_factor = *scale_factor;
+        log::info!("overlay scale factor changed to {}", scale_factor);
+        if lock_config(&shared).is_ok_and(|config| config.follow_dpi) {
+            reposition::request_reposition(&target, PositionTrigger::MonitorChange, true);
+        }
+        let scale_factor = effective_scale_factor(&shared, scale_factor);
+        let _ = update_overlay_state(&app, &shared, |state| state.scale_factor = scale_factor);
+    });
+}
+
+/// With `enabled`, the window is resized to keep its logical size after DPI changes.
+#[tauri::command]
+pub(crate) fn set_follow_dpi(
+    enabled: bool,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    let config = update_config(&app, shared.inner(), |config| config.follow_dpi = enabled)?;
+    if enabled {
+        layout::apply_overlay_layout(&main_window(&app)?, &config, PositionTrigger::Layout).map_err(|error| error.to_string())?;
+    }
+    Ok(())
+}
+
+/// Debug-only stand-in for the monitor scale factor, to exercise the positioning math at other DPIs.
+#[cfg(debug_assertions)]
+#[derive(Default)]
+pub(crate) struct ScaleOverride(Mutex<Option<f64>>);
+
+#[cfg(debug_assertions)]
+pub(crate) fn effective_scale_factor(shared: &SharedOverlayState, real: f64) -> f64 {
+    shared.scale_override.0.lock().ok().and_then(|value| *value).unwrap_or(real)
+}
+
+#[cfg(not(debug_assertions))]
+pub(crate) fn effective_scale_factor(_shared: &SharedOverlayState, real: f64) -> f64 {
+    real
+}
+
+/// Positions and emits as if the monitor had `scale`; `null` returns to the real value.
+#[cfg(debug_assertions)]
+#[tauri::command]
+pub(crate) fn set_scale_override(
+    scale: Option<f64>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if scale.is_some_and(|scale| !scale.is_finite() || scale <= 0.0) {
+        return Err("scale override must be a positive number".to_string());
+    }
+    *shared
+        .scale_override
+        .0
+        .lock()
+        .map_err(|_| "scale override lock poisoned".to_string())? = scale;
+    let window = main_window(&app)?;
+    let real = window.scale_factor().map_err(|error| error.to_string())?;
+    crate::position_overlay_window(&window, PositionTrigger::ScaleOverride).map_err(|error| error.to_string())?;
+    let scale_factor = effective_scale_factor(shared.inner(), real);
+    update_overlay_state(&app, shared.inner(), |state| state.scale_factor = scale_factor)
+}
+
+#[cfg(test)]
+mod tests {
+    use tauri::{LogicalSize, PhysicalPosition, PhysicalSize};
+
+    use super::bottom_center_position;
+
+    /// A 1920x1080 logical monitor at `origin`, and a 360x64 logical overlay on it.
+    fn anchor(origin: PhysicalPosition<i32>, scale_factor: f64) -> PhysicalPosition<i32> {
+        let monitor = LogicalSize::new(1920.0, 1080.0).to_physical::<u32>(scale_factor);
+        let window = LogicalSize::new(360.0, 64.0).to_physical::<u32>(scale_factor);
+        bottom_center_position(origin, monitor, window, scale_factor)
+    }
+
+    #[test]
+    fn keeps_the_logical_anchor_at_100_150_and_200_percent() {
+        assert_eq!(anchor(PhysicalPosition::new(0, 0), 1.0), PhysicalPosition::new(780, 940));
+        assert_eq!(anchor(PhysicalPosition::new(1920, 0), 1.5), PhysicalPosition::new(1920 + 1170, 1410));
+        assert_eq!(anchor(PhysicalPosition::new(-3840, 0), 2.0), PhysicalPosition::new(-3840 + 1560, 1880));
+    }
+
+    #[test]
+    fn pins_an_oversized_window_inside_the_monitor() {
+        let position = bottom_center_position(
+            PhysicalPosition::new(0, 0),
+            PhysicalSize::new(1280, 720),
+            PhysicalSize::new(1400, 700),
+            1.5,
+        );
+        assert_eq!(position, PhysicalPosition::new(0, 0));
+    }
+}
@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::{
+    utils::config::WindowEffectsConfig,
+    window::{Effect, EffectsBuilder},
+    AppHandle, State, WebviewWindow,
+};
+
+use crate::{config::update_config, main_window, SharedOverlayState};
+
+/// Windows 10 1809, the first build with acrylic composition.
+const ACRYLIC_MIN_BUILD: u32 = 17_763;
+/// Windows 11 21H2, the first build with mica.
+const MICA_MIN_BUILD: u32 = 22_000;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum BackgroundEffect {
+    #[default]
+    None,
+    Blur,
+    Acrylic,
+    Mica,
+}
+
+impl BackgroundEffect {
+    fn min_build(self) -> u32 {
+        match self {
+            BackgroundEffect::None | BackgroundEffect::Blur => 0,
+            BackgroundEffect::Acrylic => ACRYLIC_MIN_BUILD,
+            BackgroundEffect::Mica => MICA_MIN_BUILD,
+        }
+    }
+
+    fn effects(self) -> Option<WindowEffectsConfig> {
+        let effect = match self {
+            BackgroundEffect::None => return None,
+            BackgroundEffect::Blur => Effect::Blur,
+            BackgroundEffect::Acrylic => Effect::Acrylic,
+            BackgroundEffect::Mica => Effect::Mica,
+        };
+        Some(EffectsBuilder::new().effect(effect).build())
+    }
+}
+
+#[cfg(windows)]
+fn windows_build() -> Option<u32> {
+    use windows_sys::{
+        Wdk::System::SystemServices::RtlGetVersion, Win32::System::SystemInformation::OSVERSIONINFOW,
+    };
+
+    let mut info = OSVERSIONINFOW {
+        dwOSVersionInfoSize: std::mem::size_of::<OSVERSIONINFOW>() as u32,
+        ..Default::default()
+    };
+    // SAFETY: `info` is a valid OSVERSIONINFOW with its size field set.
+    // RtlGetVersion is used because GetVersionEx reports a shimmed version without a manifest.
+    let status = unsafe { RtlGetVersion(&mut info) };
+    (status == 0).then_some(info.dwBuildNumber)
+}
+
+#[cfg(not(windows))]
+fn windows_build() -> Option<u32> {
+    None
+}
+
+/// The effect `requested` becomes on this OS: itself when supported, otherwise `none`.
+fn supported_effect(requested: BackgroundEffect) -> BackgroundEffect {
+    if requested == BackgroundEffect::None {
+        return requested;
+    }
+    match windows_build() {
+        Some(build) if build >= requested.min_build() => requested,
+        build => {
+            log::warn!(
+                "background effect {:?} is not supported on this OS (build {:?}); using none",
+                requested,
+                build
+            );
+            BackgroundEffect::None
+        }
+    }
+}
+
+/// Applies `requested` (or `none` where unsupported) and returns the effect in use.
+pub(crate) fn apply_background_effect(
+    window: &WebviewWindow,
+    requested: BackgroundEffect,
+) -> tauri::Result<BackgroundEffect> {
+    let effect = supported_effect(requested);
+    window.set_effects(effect.effects())?;
+    Ok(effect)
+}
+
+#[tauri::command]
+pub(crate) fn set_background_effect(
+    effect: BackgroundEffect,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<BackgroundEffect, String> {
+    let applied = apply_background_effect(&main_window(&app)?, effect).map_err(|error| error.to_string())?;
+    update_config(&app, shared.inner(), |config| config.background_effect = effect)?;
+    Ok(applied)
+}
@@ -0,0 +1,93 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use crate::{config::update_config, update_overlay_state, OverlayState, SharedOverlayState};
+
+const ENTRANCE_FRAME: Duration = Duration::from_millis(16);
+const MAX_SHOW_ANIMATION_MS: u64 = 2_000;
+
+/// How the frontend brings the overlay in and out, driven by `entrance`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ShowAnimation {
+    /// Appears and disappears at once.
+    #[default]
+    None,
+    Fade,
+    /// Slides in from the anchored screen edge; the window itself stays at its anchor.
+    Slide,
+}
+
+/// Animates `entrance` between 0 (off-edge or transparent) and 1 (at rest)
+/// on each `visible` change, reversing from wherever a running one got to.
+#[derive(Default)]
+pub(crate) struct EntranceAnimation {
+    generation: AtomicU64,
+}
+
+impl EntranceAnimation {
+    pub(crate) fn follow_visible(
+        &self,
+        app: &AppHandle,
+        shared: &Arc<SharedOverlayState>,
+        animation: ShowAnimation,
+        duration: Duration,
+        previous: &OverlayState,
+        next: &mut OverlayState,
+    ) {
+        next.show_animation = animation;
+        let target = if next.visible { 1.0 } else { 0.0 };
+        if animation == ShowAnimation::None || duration.is_zero() {
+            self.generation.fetch_add(1, Ordering::Relaxed);
+            next.entrance = target;
+            return;
+        }
+        if previous.visible == next.visible {
+            return;
+        }
+        let generation = self.generation.fetch_add(1, Ordering::Relaxed) + 1;
+        let from = next.entrance;
+        let started = shared.scheduler.clock().now();
+        let app = app.clone();
+        shared.scheduler.repeat("entrance", ENTRANCE_FRAME, move |shared| {
+            if shared.entrance_animation.generation.load(Ordering::Relaxed) != generation {
+                return None;
+            }
+            let elapsed = shared.scheduler.clock().now().duration_since(started);
+            let progress = (elapsed.as_secs_f64() / duration.as_secs_f64()).min(1.0);
+            let entrance = from + (target - from) * progress;
+            let _ = update_overlay_state(&app, shared, |state| state.entrance = entrance);
+            (progress < 1.0).then_some(ENTRANCE_FRAME)
+        });
+    }
+}
+
+#[tauri::command]
+pub(crate) fn set_show_animation(
+    animation: ShowAnimation,
+    anim_ms: Option<u64>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if anim_ms.is_some_and(|ms| ms > MAX_SHOW_ANIMATION_MS) {
+        return Err(format!(
+            "show animation must be at most {} ms",
+            MAX_SHOW_ANIMATION_MS
+        ));
+    }
+    update_config(&app, shared.inner(), |config| {
+        config.show_animation = animation;
+        if let Some(ms) = anim_ms {
+            config.show_animation_ms = ms;
+        }
+    })?;
+    update_overlay_state(&app, shared.inner(), |_| {})
+}
@@ -0,0 +1,116 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::{config::update_config, lock_config, SharedOverlayState};
+
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+const MAX_ERROR_DEDUPE_WINDOW_MS: u64 = 60_000;
+
+#[derive(Default)]
+struct ErrorWindow {
+    /// Repeats of each recently emitted event, keyed by its JSON.
+    repeats: HashMap<String, u32>,
+    /// When each event of the last second was emitted, for the rate cap.
+    emitted: VecDeque<Instant>,
+}
+
+/// Coalesces identical `overlay://error` events and caps their rate, so a
+/// misbehaving sender can't flood the webview.
+#[derive(Default)]
+pub(crate) struct ErrorEvents {
+    window: Mutex<ErrorWindow>,
+}
+
+impl ErrorEvents {
+    pub(crate) fn len(&self) -> usize {
+        self.window.lock().map(|window| window.repeats.len()).unwrap_or(0)
+    }
+
+    fn emit_capped(&self, app: &AppHandle, max_per_sec: u32, payload: &Value, now: Instant) {
+        let Ok(mut window) = self.window.lock() else {
+            return;
+        };
+        while window
+            .emitted
+            .front()
+            .is_some_and(|at| now.duration_since(*at) >= RATE_WINDOW)
+        {
+            window.emitted.pop_front();
+        }
+        if max_per_sec > 0 && window.emitted.len() >= max_per_sec as usize {
+            log::debug!("dropped overlay error event over the rate cap: {}", payload);
+            return;
+        }
+        window.emitted.push_back(now);
+        drop(window);
+        let _ = app.emit("overlay://error", payload);
+    }
+}
+
+/// Emits the event now, or counts it when an identical one went out within
+/// the dedupe window; the count follows as one `{ ..., "count" }` event when
+/// the window closes.
+pub(crate) fn emit_error_event(app: &AppHandle, shared: &Arc<SharedOverlayState>, payload: Value) {
+    let (dedupe_ms, max_per_sec) = match lock_config(shared) {
+        Ok(config) => (config.error_dedupe_window_ms, config.max_error_events_per_sec),
+        Err(_) => (0, 0),
+    };
+    if dedupe_ms == 0 {
+        shared.error_events.emit_capped(app, max_per_sec, &payload, shared.scheduler.clock().now());
+        return;
+    }
+    let key = payload.to_string();
+    {
+        let Ok(mut window) = shared.error_events.window.lock() else {
+            return;
+        };
+        if let Some(repeats) = window.repeats.get_mut(&key) {
+            *repeats += 1;
+            return;
+        }
+        window.repeats.insert(key.clone(), 0);
+    }
+    shared.error_events.emit_capped(app, max_per_sec, &payload, shared.scheduler.clock().now());
+
+    let app = app.clone();
+    shared.scheduler.after("error_coalesce", Duration::from_millis(dedupe_ms), move |shared| {
+        let repeats = match shared.error_events.window.lock() {
+            Ok(mut window) => window.repeats.remove(&key).unwrap_or(0),
+            Err(_) => return,
+        };
+        if repeats == 0 {
+            return;
+        }
+        let mut coalesced = payload;
+        if let Value::Object(fields) = &mut coalesced {
+            fields.insert("count".to_string(), Value::from(repeats));
+        }
+        shared.error_events.emit_capped(&app, max_per_sec, &coalesced, shared.scheduler.clock().now());
+    });
+}
+
+#[tauri::command]
+pub(crate) fn set_error_event_limits(
+    dedupe_window_ms: u64,
+    max_per_sec: u32,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if dedupe_window_ms > MAX_ERROR_DEDUPE_WINDOW_MS {
+        return Err(format!(
+            "error dedupe window must be at most {} ms",
+            MAX_ERROR_DEDUPE_WINDOW_MS
+        ));
+    }
+    update_config(&app, shared.inner(), |config| {
+        config.error_dedupe_window_ms = dedupe_window_ms;
+        config.max_error_events_per_sec = max_per_sec;
+    })?;
+    Ok(())
+}
@@ -0,0 +1,152 @@
+use std::sync::{Arc, Mutex};
+
+use tauri::{AppHandle, State};
+use tauri_plugin_log::{fern, Target, TargetKind};
+
+use crate::{
+    config::{update_config, OverlayConfig},
+    logtap::EventLogLevel,
+    SharedOverlayState,
+};
+
+#[cfg(windows)]
+struct EventSource(windows_sys::Win32::Foundation::HANDLE);
+
+// SAFETY: event source handles may be used and closed from any thread.
+#[cfg(windows)]
+unsafe impl Send for EventSource {}
+
+#[cfg(windows)]
+impl EventSource {
+    fn register(name: &str) -> Option<Self> {
+        use windows_sys::Win32::System::EventLog::RegisterEventSourceW;
+
+        let name: Vec<u16> = name.encode_utf16().chain(Some(0)).collect();
+        // SAFETY: `name` is NUL-terminated and outlives the call; a null server means this machine.
+        let handle = unsafe { RegisterEventSourceW(std::ptr::null(), name.as_ptr()) };
+        (!handle.is_null()).then_some(Self(handle))
+    }
+
+    fn report(&self, level: log::Level, message: &str) {
+        use windows_sys::Win32::System::EventLog::{ReportEventW, EVENTLOG_ERROR_TYPE, EVENTLOG_WARNING_TYPE};
+
+        let kind = if level == log::Level::Error {
+            EVENTLOG_ERROR_TYPE
+        } else {
+            EVENTLOG_WARNING_TYPE
+        };
+        let message: Vec<u16> = message.encode_utf16().chain(Some(0)).collect();
+        let strings = [message.as_ptr()];
+        // SAFETY: `self.0` is a registered event source and `strings` holds one
+        // NUL-terminated string that outlives the call.
+        unsafe {
+            ReportEventW(
+                self.0,
+                kind,
+                0,
+                0,
+                std::ptr::null_mut(),
+                1,
+                0,
+                strings.as_ptr(),
+                std::ptr::null(),
+            )
+        };
+    }
+}
+
+#[cfg(windows)]
+impl Drop for EventSource {
+    fn drop(&mut self) {
+        use windows_sys::Win32::System::EventLog::DeregisterEventSource;
+
+        // SAFETY: `self.0` came from RegisterEventSourceW and is released only here.
+        unsafe { DeregisterEventSource(self.0) };
+    }
+}
+
+#[cfg(not(windows))]
+struct EventSource;
+
+#[cfg(not(windows))]
+impl EventSource {
+    fn register(_name: &str) -> Option<Self> {
+        None
+    }
+
+    fn report(&self, _level: log::Level, _message: &str) {}
+}
+
+/// Forwards warnings and errors to the Application event log under a
+/// configurable source name. Unconfigured until `configure` runs.
+#[derive(Default)]
+pub(crate) struct EventLog {
+    sink: Mutex<Option<(EventSource, EventLogLevel)>>,
+}
+
+impl EventLog {
+    pub(crate) fn configure(&self, config: &OverlayConfig) {
+        let source = match config.event_log_level {
+            EventLogLevel::Off => None,
+            level => EventSource::register(&config.event_log_source).map(|source| (source, level)),
+        };
+        let registered = source.is_some();
+        if let Ok(mut sink) = self.sink.lock() {
+            *sink = source;
+        }
+        if config.event_log_level != EventLogLevel::Off && !registered && cfg!(windows) {
+            log::warn!("failed to register event log source {:?}", config.event_log_source);
+        }
+    }
+}
+
+struct EventLogSink(Arc<EventLog>);
+
+impl log::Log for EventLogSink {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Warn
+    }
+
+    fn log(&self, record: &log::Record) {
+        // try_lock: a record logged while the sink is being reconfigured is skipped rather than deadlocking.
+        let Ok(sink) = self.0.sink.try_lock() else {
+            return;
+        };
+        if let Some((source, level)) = sink.as_ref() {
+            if level.admits(record.level()) {
+                source.report(record.level(), &format!("{}: {}", record.target(), record.args()));
+            }
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Log plugin target feeding `event_log`.
+pub(crate) fn log_target(event_log: Arc<EventLog>) -> Target {
+    let sink: Box<dyn log::Log> = Box::new(EventLogSink(event_log));
+    Target::new(TargetKind::Dispatch(fern::Dispatch::new().chain(sink)))
+        .format(|out, message, _record| out.finish(format_args!("{}", message)))
+}
+
+/// Sets the minimum level (`off`, `warn` or `error`) and source name for event log forwarding.
+#[tauri::command]
+pub(crate) fn set_event_log(
+    level: EventLogLevel,
+    source: Option<String>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+    event_log: State<'_, Arc<EventLog>>,
+) -> Result<(), String> {
+    if source.as_deref().is_some_and(|source| source.trim().is_empty()) {
+        return Err("event log source name must not be empty".to_string());
+    }
+    let config = update_config(&app, shared.inner(), |config| {
+        config.event_log_level = level;
+        if let Some(source) = source {
+            config.event_log_source = source.trim().to_string();
+        }
+    })?;
+    event_log.configure(&config);
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use tauri::{AppHandle, State};
+
+use crate::{
+    config::{update_config, OverlayConfig},
+    layout::{apply_overlay_layout, OverlayGeometry},
+    lock_config, main_window,
+    position::PositionTrigger,
+    update_overlay_state, OverlayState, SharedOverlayState,
+};
+
+/// Whether the window currently has the collapsed size; read by the layout,
+/// which can run while the state lock is held.
+#[derive(Default)]
+pub(crate) struct AutoExpand {
+    collapsed: AtomicBool,
+}
+
+impl AutoExpand {
+    pub(crate) fn is_collapsed(&self) -> bool {
+        self.collapsed.load(Ordering::Relaxed)
+    }
+
+    /// Derives `collapsed` from message presence and resizes the window when it flips.
+    pub(crate) fn follow_message(
+        &self,
+        app: &AppHandle,
+        shared: &Arc<SharedOverlayState>,
+        config: &OverlayConfig,
+        next: &mut OverlayState,
+    ) {
+        next.collapsed = config.auto_expand && next.message.is_none();
+        if self.collapsed.swap(next.collapsed, Ordering::Relaxed) == next.collapsed {
+            return;
+        }
+        // Resize off this thread: the caller holds the state lock.
+        let app = app.clone();
+        shared.scheduler.after("auto_expand", Duration::ZERO, move |shared| {
+            let Ok(config) = lock_config(shared).map(|config| config.clone()) else {
+                return;
+            };
+            let result = main_window(&app).and_then(|window| {
+                apply_overlay_layout(&window, &config, PositionTrigger::AutoExpand).map_err(|error| error.to_string())
+            });
+            if let Err(error) = result {
+                log::warn!("failed to resize the overlay for its message: {}", error);
+            }
+        });
+    }
+}
+
+/// Shrinks the overlay to `collapsed` (e.g. a small pill) while there is no
+/// message and grows it back to the configured geometry when one is set.
+#[tauri::command]
+pub(crate) fn set_auto_expand(
+    enabled: bool,
+    collapsed: Option<OverlayGeometry>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if let Some(collapsed) = &collapsed {
+        collapsed.validate()?;
+    }
+    update_config(&app, shared.inner(), |config| {
+        config.auto_expand = enabled;
+        if let Some(collapsed) = collapsed {
+            config.collapsed_geometry = collapsed;
+        }
+    })?;
+    // Re-derive `collapsed`, which resizes the window if it flipped.
+    update_overlay_state(&app, shared.inner(), |_| {})
+}
@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use tauri::{AppHandle, State};
+
+use crate::{config::update_config, SharedOverlayState};
+
+pub(crate) const FAST_LEVEL_REASON: &str = "level-only fast path";
+
+/// The level of a payload shaped exactly `{"level": <number>}`, read without
+/// serde; anything else, including extra keys, returns `None` for the full parser.
+pub(crate) fn parse_level_only(payload: &str) -> Option<f64> {
+    let body = payload.trim().strip_prefix('{')?.strip_suffix('}')?.trim_start();
+    let value = body.strip_prefix("\"level\"")?.trim_start().strip_prefix(':')?.trim();
+    // f64's parser also takes `inf`, `nan`, `+1`, `.5`, `1.` and `01`, which JSON doesn't.
+    if !is_json_number(value) {
+        return None;
+    }
+    value.parse::<f64>().ok().filter(|level| level.is_finite())
+}
+
+/// `-? (0 | [1-9][0-9]*) (. [0-9]+)? ([eE] [+-]? [0-9]+)?`
+fn is_json_number(value: &str) -> bool {
+    fn digits(text: &str) -> (&str, &str) {
+        let end = text.bytes().position(|byte| !byte.is_ascii_digit()).unwrap_or(text.len());
+        text.split_at(end)
+    }
+
+    let rest = value.strip_prefix('-').unwrap_or(value);
+    let (int, mut rest) = digits(rest);
+    if int.is_empty() || (int.len() > 1 && int.starts_with('0')) {
+        return false;
+    }
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let (fraction, after) = digits(fraction);
+        if fraction.is_empty() {
+            return false;
+        }
+        rest = after;
+    }
+    if let Some(exponent) = rest.strip_prefix(['e', 'E']) {
+        let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+        let (exponent, after) = digits(exponent);
+        if exponent.is_empty() {
+            return false;
+        }
+        rest = after;
+    }
+    rest.is_empty()
+}
+
+/// Turns the level-only fast path on or off; on by default.
+#[tauri::command]
+pub(crate) fn set_fast_level_parse(
+    enabled: bool,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    update_config(&app, shared.inner(), |config| config.fast_level_parse = enabled)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+        hint::black_box,
+    };
+
+    use super::parse_level_only;
+    use crate::{decode_payload, sequence::BridgeUpdate, OverlayPatch};
+
+    /// Counts allocations per thread, so parallel tests don't skew each other.
+    struct CountingAlloc;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: CountingAlloc = CountingAlloc;
+
+    fn allocations(run: impl FnOnce()) -> usize {
+        let before = ALLOCATIONS.with(Cell::get);
+        run();
+        ALLOCATIONS.with(Cell::get) - before
+    }
+
+    /// The level full decoding yields when `payload` is a patch carrying only a level.
+    fn decoded_level(payload: &str) -> Option<f64> {
+        match decode_payload(payload) {
+            Ok((BridgeUpdate::Patch(patch), _)) => {
+                let level = patch.level?;
+                let level_only = OverlayPatch {
+                    level: Some(level),
+                    ..Default::default()
+                };
+                (format!("{:?}", patch) == format!("{:?}", level_only)).then_some(level)
+            }
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn matches_full_decoding_for_level_only_payloads() {
+        let payloads = [
+            r#"{"level":0.5}"#,
+            r#" { "level" : 0 } "#,
+            r#"{"level":1}"#,
+            r#"{"level":-0.25}"#,
+            r#"{"level":1e-3}"#,
+            r#"{"level":2.5E+1}"#,
+            r#"{"level":0.123456789}"#,
+            r#"{"level":1e400}"#,
+            r#"{"level":+1}"#,
+            r#"{"level":.5}"#,
+            r#"{"level":1.}"#,
+            r#"{"level":01}"#,
+            r#"{"level":1e}"#,
+            r#"{"level":-}"#,
+            r#"{"level":inf}"#,
+            r#"{"level":NaN}"#,
+            r#"{"level":"0.5"}"#,
+            r#"{"level":null}"#,
+            r#"{"level":0.5,}"#,
+        ];
+        for payload in payloads {
+            assert_eq!(parse_level_only(payload), decoded_level(payload), "{}", payload);
+        }
+    }
+
+    #[test]
+    fn the_fast_path_does_not_allocate() {
+        let payload = r#"{"level":0.5}"#;
+        let fast = allocations(|| {
+            black_box(parse_level_only(black_box(payload)));
+        });
+        let full = allocations(|| {
+            let _ = black_box(decode_payload(black_box(payload)));
+        });
+        assert_eq!(fast, 0);
+        assert!(full > 0, "full decoding allocated {} times", full);
+    }
+
+    #[test]
+    fn leaves_anything_else_to_the_full_parser() {
+        for payload in [r#"{"level":0.5,"listening":"recording"}"#, r#"{"peak_level":0.5}"#, "0.5", ""] {
+            assert_eq!(parse_level_only(payload), None, "{}", payload);
+        }
+    }
+}
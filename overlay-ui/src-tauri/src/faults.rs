@@ -0,0 +1,117 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use serde::Deserialize;
+use tauri::State;
+
+use crate::SharedOverlayState;
+
+const DEFAULT_FAULT_SEED: u64 = 0x5eed_f00d;
+const MAX_FAULT_DELAY_MS: u64 = 10_000;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct FaultSettings {
+    /// Fraction of datagrams dropped, `0..1`.
+    #[serde(default)]
+    drop_rate: f64,
+    /// Delay applied to every datagram that isn't dropped.
+    #[serde(default)]
+    delay_ms: u64,
+    /// Seed for the drop decisions, so runs are reproducible.
+    #[serde(default)]
+    seed: Option<u64>,
+}
+
+struct ActiveFaults {
+    drop_rate: f64,
+    delay: Duration,
+    rng: u64,
+}
+
+impl ActiveFaults {
+    /// xorshift64*, mapped to `0..1`.
+    fn next_unit(&mut self) -> f64 {
+        self.rng ^= self.rng >> 12;
+        self.rng ^= self.rng << 25;
+        self.rng ^= self.rng >> 27;
+        (self.rng.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 11) as f64 / (1_u64 << 53) as f64
+    }
+}
+
+pub(crate) enum FaultAction {
+    Deliver,
+    Drop,
+    Delay(Duration),
+}
+
+/// Debug-only bad-network simulation in the bridge's ingestion path.
+#[derive(Default)]
+pub(crate) struct FaultInjection {
+    active: Mutex<Option<ActiveFaults>>,
+}
+
+impl FaultInjection {
+    pub(crate) fn decide(&self) -> FaultAction {
+        let Ok(mut active) = self.active.lock() else {
+            return FaultAction::Deliver;
+        };
+        let Some(faults) = active.as_mut() else {
+            return FaultAction::Deliver;
+        };
+        if faults.drop_rate > 0.0 && faults.next_unit() < faults.drop_rate {
+            FaultAction::Drop
+        } else if faults.delay.is_zero() {
+            FaultAction::Deliver
+        } else {
+            FaultAction::Delay(faults.delay)
+        }
+    }
+}
+
+#[tauri::command]
+pub(crate) fn set_fault_injection(
+    settings: FaultSettings,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if !(0.0..=1.0).contains(&settings.drop_rate) {
+        return Err("drop_rate must be between 0 and 1".to_string());
+    }
+    if settings.delay_ms > MAX_FAULT_DELAY_MS {
+        return Err(format!("delay_ms must be at most {}", MAX_FAULT_DELAY_MS));
+    }
+    let mut active = shared
+        .fault_injection
+        .active
+        .lock()
+        .map_err(|_| "fault injection lock poisoned".to_string())?;
+    // xorshift must not start at zero.
+    let seed = settings.seed.unwrap_or(DEFAULT_FAULT_SEED).max(1);
+    log::warn!(
+        "bridge fault injection on: drop_rate={} delay_ms={} seed={}",
+        settings.drop_rate,
+        settings.delay_ms,
+        seed
+    );
+    *active = Some(ActiveFaults {
+        drop_rate: settings.drop_rate,
+        delay: Duration::from_millis(settings.delay_ms),
+        rng: seed,
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn clear_fault_injection(shared: State<'_, Arc<SharedOverlayState>>) -> Result<(), String> {
+    let mut active = shared
+        .fault_injection
+        .active
+        .lock()
+        .map_err(|_| "fault injection lock poisoned".to_string())?;
+    if active.take().is_some() {
+        log::info!("bridge fault injection off");
+    }
+    Ok(())
+}
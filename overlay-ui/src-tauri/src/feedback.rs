@@ -0,0 +1,51 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::{update_overlay_state, OverlayState, SharedOverlayState};
+
+/// How long an unmatched phrase stays up before it is cleared.
+const UNMATCHED_FEEDBACK_TTL: Duration = Duration::from_millis(2_000);
+
+/// A recognized voice command, shown apart from dictated `message` text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct CommandFeedback {
+    pub(crate) phrase: String,
+    #[serde(default)]
+    pub(crate) matched: bool,
+    /// What the matched command does, e.g. `"undo"`.
+    #[serde(default)]
+    pub(crate) action: Option<String>,
+}
+
+/// Clears unmatched command feedback after a short while; matched feedback stays until replaced.
+#[derive(Default)]
+pub(crate) struct CommandFeedbackClear {
+    generation: AtomicU64,
+}
+
+impl CommandFeedbackClear {
+    pub(crate) fn apply(&self, app: &AppHandle, shared: &Arc<SharedOverlayState>, previous: &OverlayState, next: &OverlayState) {
+        if previous.command_feedback == next.command_feedback {
+            return;
+        }
+        let generation = self.generation.fetch_add(1, Ordering::Relaxed) + 1;
+        if !next.command_feedback.as_ref().is_some_and(|feedback| !feedback.matched) {
+            return;
+        }
+        let app = app.clone();
+        shared.scheduler.after("command_feedback_clear", UNMATCHED_FEEDBACK_TTL, move |shared| {
+            if shared.command_feedback_clear.generation.load(Ordering::Relaxed) == generation {
+                let _ = update_overlay_state(&app, shared, |state| state.command_feedback = None);
+            }
+        });
+    }
+}
@@ -0,0 +1,96 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::{OverlayState, SharedOverlayState};
+
+const FIELD_RATE_WINDOW: Duration = Duration::from_secs(10);
+/// Most change instants kept per field; enough for 400 Hz over the window.
+const MAX_CHANGES_PER_FIELD: usize = 4_000;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct FieldRate {
+    /// Changes per second over the rolling window.
+    per_sec: f64,
+    /// Changes since startup.
+    total: u64,
+}
+
+#[derive(Default)]
+struct FieldChanges {
+    recent: VecDeque<Instant>,
+    total: u64,
+}
+
+impl FieldChanges {
+    fn prune(&mut self, now: Instant) {
+        while self.recent.front().is_some_and(|at| now.duration_since(*at) > FIELD_RATE_WINDOW) {
+            self.recent.pop_front();
+        }
+    }
+}
+
+/// How often each state field actually changes, for picking throttle values.
+#[derive(Default)]
+pub(crate) struct FieldRates {
+    fields: Mutex<BTreeMap<&'static str, FieldChanges>>,
+}
+
+macro_rules! changed_fields {
+    ($previous:expr, $next:expr, $($field:ident),+ $(,)?) => {
+        [$((stringify!($field), $previous.$field != $next.$field)),+]
+    };
+}
+
+impl FieldRates {
+    pub(crate) fn record(&self, previous: &OverlayState, next: &OverlayState) {
+        let changed = changed_fields!(
+            previous, next, connection, listening, processing, target, level, meters, peak_level, activity,
+            clipping, visible, message, message_id, confidence, steps, words, command_feedback, entrance,
+            streaming, collapsed, interactive, context, rendered_text,
+        );
+        let Ok(mut fields) = self.fields.lock() else {
+            return;
+        };
+        let now = Instant::now();
+        for (name, _) in changed.iter().filter(|(_, changed)| *changed) {
+            let changes = fields.entry(name).or_default();
+            changes.prune(now);
+            if changes.recent.len() == MAX_CHANGES_PER_FIELD {
+                changes.recent.pop_front();
+            }
+            changes.recent.push_back(now);
+            changes.total += 1;
+        }
+    }
+}
+
+/// Per-field change rates over the last 10 s; fields that never changed are absent.
+#[tauri::command]
+pub(crate) fn get_field_rates(
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<BTreeMap<&'static str, FieldRate>, String> {
+    let mut fields = shared
+        .field_rates
+        .fields
+        .lock()
+        .map_err(|_| "field rates lock poisoned".to_string())?;
+    let now = Instant::now();
+    Ok(fields
+        .iter_mut()
+        .map(|(name, changes)| {
+            changes.prune(now);
+            let rate = FieldRate {
+                per_sec: changes.recent.len() as f64 / FIELD_RATE_WINDOW.as_secs_f64(),
+                total: changes.total,
+            };
+            (*name, rate)
+        })
+        .collect())
+}
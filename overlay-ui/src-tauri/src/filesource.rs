@@ -0,0 +1,98 @@
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::PathBuf,
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+use tauri::AppHandle;
+
+use crate::{handle_datagram, ingest::Transport, lock_config, SharedOverlayState};
+
+const FILE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const MAX_LINE_BYTES: usize = 8192;
+
+/// Follows a JSONL file from its current end.
+struct Tail {
+    path: PathBuf,
+    offset: u64,
+    partial: Vec<u8>,
+}
+
+impl Tail {
+    fn new(path: PathBuf) -> Self {
+        let offset = std::fs::metadata(&path).map(|metadata| metadata.len()).unwrap_or(0);
+        Self {
+            path,
+            offset,
+            partial: Vec::new(),
+        }
+    }
+
+    /// Complete lines appended since the last poll. A file shorter than what
+    /// was already read has been truncated or rotated, so it is read from the start.
+    fn poll(&mut self) -> Vec<Vec<u8>> {
+        let Ok(mut file) = File::open(&self.path) else {
+            return Vec::new();
+        };
+        let len = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+        if len < self.offset {
+            log::info!("{} was truncated or rotated; reading from the start", self.path.display());
+            self.offset = 0;
+            self.partial.clear();
+        }
+        if len == self.offset || file.seek(SeekFrom::Start(self.offset)).is_err() {
+            return Vec::new();
+        }
+        let mut appended = Vec::new();
+        let Ok(read) = file.read_to_end(&mut appended) else {
+            return Vec::new();
+        };
+        self.offset += read as u64;
+        self.partial.extend_from_slice(&appended);
+        let Some(end) = self.partial.iter().rposition(|byte| *byte == b'\n') else {
+            return Vec::new();
+        };
+        let rest = self.partial.split_off(end + 1);
+        let complete = std::mem::replace(&mut self.partial, rest);
+        complete
+            .split(|byte| *byte == b'\n')
+            .map(|line| line.strip_suffix(b"\r").unwrap_or(line).to_vec())
+            .filter(|line| !line.is_empty())
+            .collect()
+    }
+}
+
+/// Applies each line appended to `source_file` as a bridge payload.
+pub(crate) fn start_file_source(app: AppHandle, shared: Arc<SharedOverlayState>) {
+    let path = match lock_config(&shared) {
+        Ok(config) => config.source_file.clone(),
+        Err(_) => return,
+    };
+    let Some(path) = path.map(PathBuf::from) else {
+        return;
+    };
+    log::info!("overlay tailing {}", path.display());
+    thread::spawn(move || {
+        let mut tail = Tail::new(path);
+        loop {
+            thread::sleep(FILE_POLL_INTERVAL);
+            for line in tail.poll() {
+                if line.len() > MAX_LINE_BYTES {
+                    shared.metrics.record_rejected();
+                    crate::logsample::sampled_warn!(
+                        &shared,
+                        "oversized_line",
+                        "dropped {}-byte line from {}",
+                        line.len(),
+                        tail.path.display()
+                    );
+                    continue;
+                }
+                let _ = handle_datagram(&app, &shared, Transport::File, &line);
+            }
+        }
+    });
+}
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+use tauri::{AppHandle, State, WebviewWindow};
+
+use crate::{config::update_config, lock_config, main_window, SharedOverlayState};
+
+/// Window attributes that decide whether showing the overlay can take focus.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct FocusFlags {
+    /// `set_no_activate` setting.
+    enforced: bool,
+    /// `WS_EX_NOACTIVATE` is set on the window.
+    no_activate: bool,
+    tool_window: bool,
+    /// Click-through (`WS_EX_TRANSPARENT`).
+    transparent: bool,
+    topmost: bool,
+    /// The overlay is the foreground window, i.e. it has taken focus.
+    foreground: bool,
+    focused: bool,
+}
+
+#[cfg(windows)]
+fn read_focus_flags(window: &WebviewWindow, flags: &mut FocusFlags) -> Result<(), String> {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        GetForegroundWindow, GetWindowLongPtrW, GWL_EXSTYLE, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW, WS_EX_TOPMOST,
+        WS_EX_TRANSPARENT,
+    };
+
+    let hwnd = window.hwnd().map_err(|error| error.to_string())?.0;
+    // SAFETY: `hwnd` is this process's live overlay window; both calls only read.
+    let (ex_style, foreground) = unsafe { (GetWindowLongPtrW(hwnd, GWL_EXSTYLE) as u32, GetForegroundWindow()) };
+    flags.no_activate = ex_style & WS_EX_NOACTIVATE != 0;
+    flags.tool_window = ex_style & WS_EX_TOOLWINDOW != 0;
+    flags.transparent = ex_style & WS_EX_TRANSPARENT != 0;
+    flags.topmost = ex_style & WS_EX_TOPMOST != 0;
+    flags.foreground = foreground == hwnd;
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn read_focus_flags(_window: &WebviewWindow, _flags: &mut FocusFlags) -> Result<(), String> {
+    Ok(())
+}
+
+/// Sets or clears `WS_EX_NOACTIVATE`, so a shown or clicked overlay never takes focus from the active app.
+#[cfg(windows)]
+pub(crate) fn apply_no_activate(window: &WebviewWindow, enabled: bool) -> Result<(), String> {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        GetWindowLongPtrW, SetWindowLongPtrW, SetWindowPos, GWL_EXSTYLE, SWP_FRAMECHANGED, SWP_NOACTIVATE,
+        SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, WS_EX_NOACTIVATE,
+    };
+
+    let hwnd = window.hwnd().map_err(|error| error.to_string())?.0;
+    // SAFETY: `hwnd` is this process's live overlay window; only the one style bit changes.
+    unsafe {
+        let current = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+        let next = if enabled {
+            current | WS_EX_NOACTIVATE as isize
+        } else {
+            current & !(WS_EX_NOACTIVATE as isize)
+        };
+        if next == current {
+            return Ok(());
+        }
+        SetWindowLongPtrW(hwnd, GWL_EXSTYLE, next);
+        let flags = SWP_FRAMECHANGED | SWP_NOMOVE | SWP_NOSIZE | SWP_NOZORDER | SWP_NOACTIVATE;
+        if SetWindowPos(hwnd, std::ptr::null_mut(), 0, 0, 0, 0, flags) == 0 {
+            return Err("failed to apply the window style".to_string());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(windows))]
+pub(crate) fn apply_no_activate(_window: &WebviewWindow, _enabled: bool) -> Result<(), String> {
+    Ok(())
+}
+
+/// The overlay window's focus-related styles and whether it currently holds focus.
+#[tauri::command]
+pub(crate) fn get_focus_flags(app: AppHandle, shared: State<'_, Arc<SharedOverlayState>>) -> Result<FocusFlags, String> {
+    let window = main_window(&app)?;
+    let mut flags = FocusFlags {
+        enforced: lock_config(shared.inner())?.no_activate,
+        focused: window.is_focused().map_err(|error| error.to_string())?,
+        ..FocusFlags::default()
+    };
+    read_focus_flags(&window, &mut flags)?;
+    Ok(flags)
+}
+
+#[tauri::command]
+pub(crate) fn set_no_activate(
+    enabled: bool,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    apply_no_activate(&main_window(&app)?, enabled)?;
+    update_config(&app, shared.inner(), |config| config.no_activate = enabled)?;
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use std::sync::{Arc, Mutex};
+
+use tauri::{AppHandle, State, WebviewWindow};
+
+use crate::{config::update_config, focus::apply_no_activate, lock_config, main_window, SharedOverlayState};
+
+/// The extended style the window had before game overlay mode, restored when it is turned off.
+#[derive(Default)]
+pub(crate) struct GameOverlayMode {
+    original_ex_style: Mutex<Option<isize>>,
+}
+
+/// Layered, click-through, tool-window (no taskbar or Alt-Tab entry) and
+/// never activated, which fullscreen games and anti-cheat tolerate better.
+#[cfg(windows)]
+fn set_game_overlay_styles(window: &WebviewWindow, mode: &GameOverlayMode, enabled: bool) -> Result<(), String> {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        GetWindowLongPtrW, SetLayeredWindowAttributes, SetWindowLongPtrW, SetWindowPos, GWL_EXSTYLE, LWA_ALPHA,
+        SWP_FRAMECHANGED, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, WS_EX_LAYERED, WS_EX_NOACTIVATE,
+        WS_EX_TOOLWINDOW, WS_EX_TRANSPARENT,
+    };
+
+    const GAME_OVERLAY_EX_STYLE: u32 = WS_EX_LAYERED | WS_EX_TRANSPARENT | WS_EX_TOOLWINDOW | WS_EX_NOACTIVATE;
+
+    let hwnd = window.hwnd().map_err(|error| error.to_string())?.0;
+    let mut original = mode
+        .original_ex_style
+        .lock()
+        .map_err(|_| "game overlay lock poisoned".to_string())?;
+    // SAFETY: `hwnd` is this process's live overlay window; only its extended
+    // style and layered alpha are changed.
+    unsafe {
+        let current = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+        let next = if enabled {
+            original.get_or_insert(current);
+            current | GAME_OVERLAY_EX_STYLE as isize
+        } else {
+            match original.take() {
+                Some(style) => style,
+                None => return Ok(()),
+            }
+        };
+        SetWindowLongPtrW(hwnd, GWL_EXSTYLE, next);
+        if enabled {
+            // A layered window stays invisible until its attributes are set.
+            SetLayeredWindowAttributes(hwnd, 0, 255, LWA_ALPHA);
+        }
+        let flags = SWP_FRAMECHANGED | SWP_NOMOVE | SWP_NOSIZE | SWP_NOZORDER | SWP_NOACTIVATE;
+        if SetWindowPos(hwnd, std::ptr::null_mut(), 0, 0, 0, 0, flags) == 0 {
+            return Err("failed to apply the window style".to_string());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn set_game_overlay_styles(_window: &WebviewWindow, _mode: &GameOverlayMode, enabled: bool) -> Result<(), String> {
+    if enabled {
+        return Err("game overlay mode is only available on Windows".to_string());
+    }
+    Ok(())
+}
+
+/// Applies the saved setting at startup.
+pub(crate) fn restore_game_overlay_mode(window: &WebviewWindow, shared: &SharedOverlayState, enabled: bool) {
+    if enabled {
+        if let Err(error) = set_game_overlay_styles(window, &shared.game_overlay_mode, true) {
+            log::warn!("failed to restore game overlay mode: {}", error);
+        }
+    }
+}
+
+/// Switches the overlay window to game-friendly extended styles, or back to the originals.
+#[tauri::command]
+pub(crate) fn game_overlay_mode(
+    enabled: bool,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    let window = main_window(&app)?;
+    set_game_overlay_styles(&window, &shared.game_overlay_mode, enabled)?;
+    if !enabled {
+        // The restored styles predate any later `set_no_activate`.
+        apply_no_activate(&window, lock_config(shared.inner())?.no_activate)?;
+    }
+    update_config(&app, shared.inner(), |config| config.game_overlay_mode = enabled)?;
+    Ok(())
+}
@@ -0,0 +1,276 @@
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State, WebviewWindow};
+
+use crate::{
+    config::update_config, current_or_primary_monitor, emit_overlay_state, lock_state,
+    position::PositionTrigger, reposition::request_reposition, SharedOverlayState,
+};
+
+const ADAPTER_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Which GPU Windows should run the overlay on; applies from the next launch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum RenderAdapter {
+    /// No preference; Windows decides.
+    #[default]
+    Auto,
+    /// The power-saving, usually integrated, GPU.
+    Integrated,
+    /// The high-performance, usually discrete, GPU.
+    Discrete,
+}
+
+impl RenderAdapter {
+    /// The `GpuPreference` value Windows graphics settings store for an app.
+    #[cfg_attr(not(windows), allow(dead_code))]
+    fn gpu_preference(self) -> Option<u32> {
+        match self {
+            RenderAdapter::Auto => None,
+            RenderAdapter::Integrated => Some(1),
+            RenderAdapter::Discrete => Some(2),
+        }
+    }
+}
+
+/// The `GpuPreference` in a stored `Key=Value;` list; `0` (let Windows decide) reads as none.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn parse_gpu_preference(data: &str) -> Option<u32> {
+    data.split(';')
+        .find_map(|entry| entry.trim().strip_prefix("GpuPreference="))
+        .and_then(|value| value.trim().parse().ok())
+        .filter(|&value| value != 0)
+}
+
+/// This executable's path, NUL-terminated, as graphics settings name its value.
+#[cfg(windows)]
+fn exe_value_name() -> Result<Vec<u16>, String> {
+    let exe = std::env::current_exe().map_err(|error| error.to_string())?;
+    Ok(exe.to_string_lossy().encode_utf16().chain(Some(0)).collect())
+}
+
+#[cfg(windows)]
+fn read_gpu_preference() -> Result<Option<u32>, String> {
+    use windows_sys::{
+        core::w,
+        Win32::{
+            Foundation::ERROR_FILE_NOT_FOUND,
+            System::Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_SZ},
+        },
+    };
+
+    let name = exe_value_name()?;
+    let mut data = [0u16; 256];
+    let mut size = std::mem::size_of_val(&data) as u32;
+    // SAFETY: the key is a static wide literal, `name` is NUL-terminated and
+    // `size` is the byte length of `data`.
+    let status = unsafe {
+        RegGetValueW(
+            HKEY_CURRENT_USER,
+            w!("Software\\Microsoft\\DirectX\\UserGpuPreferences"),
+            name.as_ptr(),
+            RRF_RT_REG_SZ,
+            std::ptr::null_mut(),
+            data.as_mut_ptr().cast(),
+            &mut size,
+        )
+    };
+    match status {
+        0 => {}
+        ERROR_FILE_NOT_FOUND => return Ok(None),
+        status => return Err(format!("GPU preference read failed with error {}", status)),
+    }
+    let len = data.iter().position(|&unit| unit == 0).unwrap_or(data.len());
+    Ok(parse_gpu_preference(&String::from_utf16_lossy(&data[..len])))
+}
+
+#[cfg(not(windows))]
+fn read_gpu_preference() -> Result<Option<u32>, String> {
+    Err("GPU preference is only supported on Windows".to_string())
+}
+
+#[cfg(windows)]
+fn write_gpu_preference(preference: RenderAdapter) -> Result<(), String> {
+    use windows_sys::{
+        core::w,
+        Win32::{
+            Foundation::ERROR_FILE_NOT_FOUND,
+            System::Registry::{RegDeleteKeyValueW, RegSetKeyValueW, HKEY_CURRENT_USER, REG_SZ},
+        },
+    };
+
+    let name = exe_value_name()?;
+    let key = w!("Software\\Microsoft\\DirectX\\UserGpuPreferences");
+    let status = match preference.gpu_preference() {
+        // SAFETY: `key` is a static wide literal and `name` is NUL-terminated.
+        None => match unsafe { RegDeleteKeyValueW(HKEY_CURRENT_USER, key, name.as_ptr()) } {
+            ERROR_FILE_NOT_FOUND => 0,
+            status => status,
+        },
+        Some(value) => {
+            let data: Vec<u16> = format!("GpuPreference={};", value).encode_utf16().chain(Some(0)).collect();
+            // SAFETY: as above; `data` is a NUL-terminated REG_SZ of the given byte length.
+            unsafe {
+                RegSetKeyValueW(
+                    HKEY_CURRENT_USER,
+                    key,
+                    name.as_ptr(),
+                    REG_SZ,
+                    data.as_ptr().cast(),
+                    (data.len() * std::mem::size_of::<u16>()) as u32,
+                )
+            }
+        }
+    };
+    if status != 0 {
+        return Err(format!("GPU preference write failed with error {}", status));
+    }
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn write_gpu_preference(_preference: RenderAdapter) -> Result<(), String> {
+    Err("GPU preference is only supported on Windows".to_string())
+}
+
+/// Writes `preference` only when the stored one differs, or can't be read.
+fn sync_gpu_preference(preference: RenderAdapter) -> Result<(), String> {
+    match read_gpu_preference() {
+        Ok(stored) if stored == preference.gpu_preference() => Ok(()),
+        _ => write_gpu_preference(preference),
+    }
+}
+
+/// The adapter driving the display named like `\\.\DISPLAY1`.
+#[cfg(windows)]
+fn display_adapter(display: &str) -> Option<String> {
+    use windows_sys::Win32::Graphics::Gdi::{EnumDisplayDevicesW, DISPLAY_DEVICEW};
+
+    fn wide(buffer: &[u16]) -> String {
+        let len = buffer.iter().position(|&unit| unit == 0).unwrap_or(buffer.len());
+        String::from_utf16_lossy(&buffer[..len])
+    }
+
+    (0..)
+        .map_while(|index| {
+            let mut device = DISPLAY_DEVICEW {
+                cb: std::mem::size_of::<DISPLAY_DEVICEW>() as u32,
+                ..Default::default()
+            };
+            // SAFETY: a null device name enumerates adapters; `device.cb` is set.
+            let found = unsafe { EnumDisplayDevicesW(std::ptr::null(), index, &mut device, 0) };
+            (found != 0).then_some(device)
+        })
+        .find(|device| wide(&device.DeviceName) == display)
+        .map(|device| wide(&device.DeviceString))
+}
+
+#[cfg(not(windows))]
+fn display_adapter(_display: &str) -> Option<String> {
+    None
+}
+
+fn current_adapter(window: &WebviewWindow) -> Option<String> {
+    let monitor = current_or_primary_monitor(window).ok()??;
+    display_adapter(monitor.name()?)
+}
+
+/// The adapter last seen driving the overlay's monitor, and the preference it launched with.
+#[derive(Default)]
+pub(crate) struct AdapterWatch {
+    adapter: Mutex<Option<String>>,
+    launched_with: Mutex<RenderAdapter>,
+}
+
+/// Re-applies the saved preference when the stored one drifted, so it holds
+/// for the next launch, then re-lays out and repaints the overlay whenever its
+/// display changes adapter, e.g. when a fullscreen game flips a hybrid laptop's MUX.
+pub(crate) fn start_adapter_watch(window: WebviewWindow, app: AppHandle, shared: Arc<SharedOverlayState>, preference: RenderAdapter) {
+    if let Ok(mut launched_with) = shared.adapter_watch.launched_with.lock() {
+        *launched_with = preference;
+    }
+    if let Err(error) = sync_gpu_preference(preference) {
+        log::warn!("failed to apply render adapter preference: {}", error);
+    }
+    thread::spawn(move || {
+        let mut first = true;
+        loop {
+            let adapter = current_adapter(&window);
+            let previous = match shared.adapter_watch.adapter.lock() {
+                Ok(mut seen) => std::mem::replace(&mut *seen, adapter.clone()),
+                Err(_) => return,
+            };
+            let name = adapter.as_deref().unwrap_or("an unknown adapter");
+            if first {
+                log::info!("overlay is on a display driven by {} (preference {:?})", name, preference);
+                first = false;
+            } else if previous != adapter {
+                log::info!(
+                    "overlay display moved from {} to {}; repainting",
+                    previous.as_deref().unwrap_or("an unknown adapter"),
+                    name
+                );
+                request_reposition(&window, PositionTrigger::AdapterChange, true);
+                if let Ok(state) = lock_state(&shared) {
+                    emit_overlay_state(&app, &state);
+                }
+            }
+            thread::sleep(ADAPTER_CHECK_INTERVAL);
+        }
+    });
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct RenderAdapterStatus {
+    preference: RenderAdapter,
+    /// The adapter driving the overlay's display right now.
+    adapter: Option<String>,
+    /// The preference differs from the one this process launched with.
+    restart_required: bool,
+}
+
+#[tauri::command]
+pub(crate) fn set_render_adapter(
+    preference: RenderAdapter,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<RenderAdapterStatus, String> {
+    write_gpu_preference(preference)?;
+    update_config(&app, shared.inner(), |config| config.render_adapter = preference)?;
+    let launched_with = *shared
+        .adapter_watch
+        .launched_with
+        .lock()
+        .map_err(|_| "adapter watch lock poisoned".to_string())?;
+    let adapter = shared
+        .adapter_watch
+        .adapter
+        .lock()
+        .map_err(|_| "adapter watch lock poisoned".to_string())?
+        .clone();
+    Ok(RenderAdapterStatus {
+        preference,
+        adapter,
+        restart_required: preference != launched_with,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_gpu_preference;
+
+    #[test]
+    fn reads_the_preference_among_other_settings() {
+        assert_eq!(parse_gpu_preference("GpuPreference=2;"), Some(2));
+        assert_eq!(parse_gpu_preference("SwapEffectUpgradeEnable=1;GpuPreference=1;"), Some(1));
+        assert_eq!(parse_gpu_preference("GpuPreference=0;"), None);
+        assert_eq!(parse_gpu_preference("SwapEffectUpgradeEnable=1;"), None);
+    }
+}
@@ -0,0 +1,141 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tauri::{AppHandle, State};
+
+use crate::{config::update_config, lock_config, lock_state, update_overlay_state, SharedOverlayState};
+
+const HEARTBEAT_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+const MAX_CONNECTION_WINDOW_MS: u64 = 600_000;
+
+/// Whether the `heartbeat` task is scheduled; it runs only while a timeout is set.
+#[derive(Default)]
+pub(crate) struct ConnectionWatch {
+    armed: Mutex<bool>,
+}
+
+/// What the watch forced `connection` to, and what to put back when the sender returns.
+struct Lapse {
+    previous: String,
+    forced: Option<&'static str>,
+}
+
+fn force_connection(app: &AppHandle, shared: &Arc<SharedOverlayState>, lapse: &mut Lapse, connection: &'static str) {
+    log::info!("no bridge datagram within the heartbeat window; connection is {}", connection);
+    lapse.forced = Some(connection);
+    let _ = update_overlay_state(app, shared, |state| state.connection = connection.to_string());
+}
+
+/// Puts back the pre-lapse connection, unless a sender has set one since.
+fn restore_connection(app: &AppHandle, shared: &Arc<SharedOverlayState>, lapse: Lapse) {
+    let Some(forced) = lapse.forced else {
+        return;
+    };
+    log::info!("bridge datagram received; connection restored");
+    let _ = update_overlay_state(app, shared, |state| {
+        if state.connection == forced {
+            state.connection = lapse.previous;
+        }
+    });
+}
+
+fn check_heartbeat(app: &AppHandle, shared: &Arc<SharedOverlayState>, current: &mut Option<Lapse>) {
+    let (timeout_ms, grace_ms, show_reconnecting) = match lock_config(shared) {
+        Ok(config) => (config.heartbeat_timeout_ms, config.connection_grace_ms, config.show_reconnecting),
+        Err(_) => return,
+    };
+    let last_datagram_at = shared.metrics.last_datagram_at();
+    let silent_ms = shared.scheduler.clock().unix_millis().saturating_sub(last_datagram_at);
+    if timeout_ms == 0 || last_datagram_at == 0 || silent_ms < timeout_ms {
+        if let Some(lapse) = current.take() {
+            restore_connection(app, shared, lapse);
+        }
+        return;
+    }
+    if current.is_none() {
+        let previous = match lock_state(shared) {
+            Ok(state) => state.connection.clone(),
+            Err(_) => return,
+        };
+        *current = Some(Lapse { previous, forced: None });
+    }
+    let Some(lapse) = current.as_mut() else {
+        return;
+    };
+    if lapse.forced == Some("offline") {
+        return;
+    }
+    if silent_ms >= timeout_ms.saturating_add(grace_ms) {
+        force_connection(app, shared, lapse, "offline");
+    } else if show_reconnecting && lapse.forced.is_none() {
+        force_connection(app, shared, lapse, "reconnecting");
+    }
+}
+
+fn heartbeat_enabled(shared: &Arc<SharedOverlayState>) -> bool {
+    lock_config(shared).is_ok_and(|config| config.heartbeat_timeout_ms > 0)
+}
+
+/// Flips `connection` when the bridge goes quiet: to `reconnecting` once the
+/// heartbeat timeout lapses, then to `offline` after the grace period. Any
+/// datagram puts the previous value back. Runs on the scheduler, so a moved
+/// clock is seen on the next check, and stops once the timeout is 0; call
+/// again after the timeout changes.
+pub(crate) fn start_connection_watch(app: AppHandle, shared: Arc<SharedOverlayState>) {
+    let Ok(mut armed) = shared.connection_watch.armed.lock() else {
+        return;
+    };
+    if *armed || !heartbeat_enabled(&shared) {
+        return;
+    }
+    *armed = true;
+    let mut lapse = None;
+    shared.scheduler.repeat("heartbeat", HEARTBEAT_CHECK_INTERVAL, move |shared| {
+        check_heartbeat(&app, shared, &mut lapse);
+        let mut armed = shared.connection_watch.armed.lock().ok()?;
+        if heartbeat_enabled(shared) {
+            return Some(HEARTBEAT_CHECK_INTERVAL);
+        }
+        *armed = false;
+        None
+    });
+}
+
+fn check_window(name: &str, window_ms: u64) -> Result<(), String> {
+    if window_ms > MAX_CONNECTION_WINDOW_MS {
+        return Err(format!("{} must be at most {} ms", name, MAX_CONNECTION_WINDOW_MS));
+    }
+    Ok(())
+}
+
+/// Sets how long the bridge may stay quiet before the grace period starts; 0 disables the watch.
+#[tauri::command]
+pub(crate) fn set_heartbeat_timeout(
+    timeout_ms: u64,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    check_window("heartbeat timeout", timeout_ms)?;
+    update_config(&app, shared.inner(), |config| config.heartbeat_timeout_ms = timeout_ms)?;
+    start_connection_watch(app, shared.inner().clone());
+    Ok(())
+}
+
+/// Sets the grace period after a heartbeat lapse before `offline` is committed,
+/// and whether `reconnecting` is shown meanwhile.
+#[tauri::command]
+pub(crate) fn set_connection_grace(
+    grace_ms: u64,
+    show_reconnecting: bool,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    check_window("connection grace", grace_ms)?;
+    update_config(&app, shared.inner(), |config| {
+        config.connection_grace_ms = grace_ms;
+        config.show_reconnecting = show_reconnecting;
+    })?;
+    Ok(())
+}
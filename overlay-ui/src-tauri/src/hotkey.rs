@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+use crate::{apply_overlay_patch, config::update_config, lock_config, lock_state, OverlayPatch, SharedOverlayState};
+
+fn parse_accelerator(accelerator: &str) -> Result<Shortcut, String> {
+    accelerator
+        .parse::<Shortcut>()
+        .map_err(|error| format!("invalid accelerator {:?}: {}", accelerator, error))
+}
+
+fn toggle_visibility(app: &AppHandle) {
+    let shared = app.state::<Arc<SharedOverlayState>>();
+    let visible = match lock_state(shared.inner()) {
+        Ok(state) => state.visible,
+        Err(error) => {
+            log::error!("toggle hotkey ignored: {}", error);
+            return;
+        }
+    };
+    let patch = OverlayPatch {
+        visible: Some(!visible),
+        ..Default::default()
+    };
+    if let Err(error) = apply_overlay_patch(app, shared.inner(), patch) {
+        log::error!("toggle hotkey failed: {}", error);
+    }
+}
+
+fn bind_toggle_hotkey(app: &AppHandle, shortcut: Shortcut, accelerator: &str) -> Result<(), String> {
+    if app.global_shortcut().is_registered(shortcut) {
+        return Err(format!("accelerator {:?} is already registered", accelerator));
+    }
+    app.global_shortcut()
+        .on_shortcut(shortcut, |app, _shortcut, event| {
+            if event.state == ShortcutState::Pressed {
+                toggle_visibility(app);
+            }
+        })
+        .map_err(|error| format!("failed to register accelerator {:?}: {}", accelerator, error))
+}
+
+/// Moves the toggle binding from `from` to `to`; the old one is kept when `to` fails to bind.
+pub(crate) fn rebind_toggle_hotkey(app: &AppHandle, from: Option<&str>, to: Option<&str>) -> Result<(), String> {
+    let previous = from.and_then(|previous| parse_accelerator(previous).ok());
+    let next = to.map(parse_accelerator).transpose()?;
+    if previous == next {
+        return Ok(());
+    }
+    if let (Some(shortcut), Some(accelerator)) = (next, to) {
+        bind_toggle_hotkey(app, shortcut, accelerator)?;
+    }
+    if let Some(previous) = previous {
+        let _ = app.global_shortcut().unregister(previous);
+    }
+    Ok(())
+}
+
+/// Re-binds the accelerator saved in config, if any.
+pub(crate) fn restore_toggle_hotkey(app: &AppHandle, shared: &Arc<SharedOverlayState>) {
+    let accelerator = match lock_config(shared) {
+        Ok(config) => config.toggle_hotkey.clone(),
+        Err(_) => None,
+    };
+    if let Some(accelerator) = accelerator {
+        let result = parse_accelerator(&accelerator)
+            .and_then(|shortcut| bind_toggle_hotkey(app, shortcut, &accelerator));
+        if let Err(error) = result {
+            log::warn!("failed to restore toggle hotkey: {}", error);
+        }
+    }
+}
+
+#[tauri::command]
+pub(crate) fn register_toggle_hotkey(
+    accelerator: String,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    let previous = lock_config(shared.inner())?.toggle_hotkey.clone();
+    rebind_toggle_hotkey(&app, previous.as_deref(), Some(&accelerator))?;
+    update_config(&app, shared.inner(), |config| config.toggle_hotkey = Some(accelerator))?;
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn unregister_toggle_hotkey(
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    let previous = lock_config(shared.inner())?.toggle_hotkey.clone();
+    if let Some(shortcut) = previous.as_deref().and_then(|previous| parse_accelerator(previous).ok()) {
+        app.global_shortcut()
+            .unregister(shortcut)
+            .map_err(|error| format!("failed to unregister toggle hotkey: {}", error))?;
+    }
+    update_config(&app, shared.inner(), |config| config.toggle_hotkey = None)?;
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use std::{
+    fmt::Write,
+    io::Write as _,
+    sync::{mpsc::RecvTimeoutError, Arc},
+    thread,
+    time::Duration,
+};
+
+use serde::Serialize;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::{lock_config, projection::public_state_of, SharedOverlayState};
+
+const SSE_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+fn json_response<T: Serialize>(value: &T) -> Response<std::io::Cursor<Vec<u8>>> {
+    match serde_json::to_vec(value) {
+        Ok(body) => Response::from_data(body).with_header(content_type("application/json")),
+        Err(error) => Response::from_string(error.to_string()).with_status_code(500),
+    }
+}
+
+fn content_type(value: &str) -> Header {
+    Header::from_bytes("Content-Type", value).expect("static header is valid")
+}
+
+fn write_metric(out: &mut String, name: &str, kind: &str, help: &str, value: f64) {
+    let _ = writeln!(out, "# HELP voicekey_overlay_{} {}", name, help);
+    let _ = writeln!(out, "# TYPE voicekey_overlay_{} {}", name, kind);
+    let _ = writeln!(out, "voicekey_overlay_{} {}", name, value);
+}
+
+/// Bridge and render metrics in the Prometheus text exposition format.
+fn prometheus_metrics(shared: &Arc<SharedOverlayState>) -> String {
+    let metrics = shared.metrics.snapshot();
+    let render = shared.render_stats.snapshot();
+    let mut out = String::new();
+    let counters = [
+        ("datagrams_total", "UDP datagrams received by the bridge.", metrics.datagrams),
+        ("state_updates_total", "Datagrams applied as full states.", metrics.state_updates),
+        ("patch_updates_total", "Datagrams applied as patches.", metrics.patch_updates),
+        ("parse_errors_total", "Datagrams rejected as invalid.", metrics.rejected),
+        ("throttled_total", "Level-only patches skipped by max_level_hz.", metrics.throttled),
+    ];
+    for (name, help, value) in counters {
+        write_metric(&mut out, name, "counter", help, value as f64);
+    }
+    let gauges = [
+        ("bridge_bound", "1 while the UDP bridge socket is bound.", f64::from(u8::from(metrics.bound))),
+        ("uptime_seconds", "Seconds since the overlay started.", metrics.uptime_ms as f64 / 1000.0),
+        (
+            "last_datagram_seconds",
+            "Unix time of the last datagram, 0 if none arrived.",
+            metrics.last_datagram_at.unwrap_or(0) as f64 / 1000.0,
+        ),
+        ("emit_rate", "State emits per second over the last 5 s.", render.emit_rate),
+        ("render_fps", "Frames per second reported by the webview over the last 5 s.", render.fps),
+    ];
+    for (name, help, value) in gauges {
+        write_metric(&mut out, name, "gauge", help, value);
+    }
+    out
+}
+
+fn route(shared: &Arc<SharedOverlayState>, path: &str) -> Response<std::io::Cursor<Vec<u8>>> {
+    let result = match path {
+        "/state" => public_state_of(shared).map(|state| json_response(&state)),
+        "/config" => lock_config(shared).map(|config| json_response(&*config)),
+        "/metrics" => Ok(Response::from_string(prometheus_metrics(shared))
+            .with_header(content_type("text/plain; version=0.0.4; charset=utf-8"))),
+        "/metrics.json" => Ok(json_response(&shared.metrics.snapshot())),
+        "/healthz" => {
+            let health = shared.metrics.health(shared.scheduler.clock().unix_millis());
+            let status = if health.bridge_bound { 200 } else { 503 };
+            Ok(json_response(&health).with_status_code(status))
+        }
+        _ => return Response::from_string("not found").with_status_code(404),
+    };
+    result.unwrap_or_else(|error| Response::from_string(error).with_status_code(500))
+}
+
+/// Streams each emitted state as an SSE `data:` line until the client goes
+/// away; tiny_http's chunked responses buffer, so the stream is written raw.
+fn stream_events(shared: &Arc<SharedOverlayState>, request: Request) {
+    let Some(events) = shared.state_subscribers.subscribe() else {
+        let _ = request.respond(Response::from_string("too many state subscribers").with_status_code(503));
+        return;
+    };
+    let initial = public_state_of(shared).ok().map(|state| state.to_string());
+    thread::spawn(move || {
+        let mut writer = request.into_writer();
+        let head = "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n";
+        if writer.write_all(head.as_bytes()).is_err() {
+            return;
+        }
+        let mut next = initial.ok_or(RecvTimeoutError::Timeout);
+        loop {
+            let chunk = match next {
+                Ok(data) => format!("data: {}\n\n", data),
+                Err(RecvTimeoutError::Timeout) => ": keepalive\n\n".to_string(),
+                Err(RecvTimeoutError::Disconnected) => return,
+            };
+            if writer.write_all(chunk.as_bytes()).and_then(|_| writer.flush()).is_err() {
+                log::debug!("SSE subscriber disconnected");
+                return;
+            }
+            next = events.recv_timeout(SSE_KEEPALIVE_INTERVAL);
+        }
+    });
+}
+
+/// Read-only loopback mirror of the overlay state, config and bridge metrics.
+pub(crate) fn start_http_mirror(shared: Arc<SharedOverlayState>) {
+    let port = match lock_config(&shared) {
+        Ok(config) => config.http_port,
+        Err(_) => return,
+    };
+    if port == 0 {
+        return;
+    }
+    thread::spawn(move || {
+        let addr = format!("127.0.0.1:{}", port);
+        let server = match Server::http(&addr) {
+            Ok(server) => server,
+            Err(error) => {
+                log::error!("failed to bind HTTP mirror at {}: {}", addr, error);
+                return;
+            }
+        };
+        log::info!("overlay HTTP mirror listening on {}", addr);
+        for request in server.incoming_requests() {
+            let response = match request.method() {
+                Method::Get => {
+                    let path = request.url().split('?').next().unwrap_or_default().to_string();
+                    if path == "/events" {
+                        stream_events(&shared, request);
+                        continue;
+                    }
+                    route(&shared, &path)
+                }
+                _ => Response::from_string("method not allowed").with_status_code(405),
+            };
+            let _ = request.respond(response);
+        }
+    });
+}
@@ -0,0 +1,193 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use tauri::{AppHandle, State};
+
+use crate::{
+    config::update_config, update_overlay_state, OverlayState, SharedOverlayState,
+};
+
+const READY: &str = "ready";
+const MAX_LISTENING_DEBOUNCE_MS: u64 = 5_000;
+
+struct PendingRelease {
+    since: Instant,
+    generation: u64,
+}
+
+/// Debounces `listening` falling back to `ready`, so a flapping VAD doesn't blink the overlay.
+#[derive(Default)]
+pub(crate) struct ListeningHysteresis {
+    pending: Mutex<Option<PendingRelease>>,
+    generation: AtomicU64,
+}
+
+impl ListeningHysteresis {
+    /// Holds `next.listening` at its previous value until `ready` has been requested for `debounce`.
+    /// Only a `listening_requested` update to another value withdraws a pending release.
+    pub(crate) fn filter(
+        &self,
+        app: &AppHandle,
+        shared: &Arc<SharedOverlayState>,
+        previous: &OverlayState,
+        next: &mut OverlayState,
+        debounce: Duration,
+        listening_requested: bool,
+    ) {
+        let now = shared.scheduler.clock().now();
+        if let Some(generation) = self.hold(previous, next, debounce, listening_requested, now) {
+            let app = app.clone();
+            schedule_release(shared, generation, debounce, move |shared| {
+                let _ = update_overlay_state(&app, shared, |state| state.listening = READY.to_string());
+            });
+        }
+    }
+
+    /// Applies the hold; returns the release to schedule when one newly starts.
+    fn hold(
+        &self,
+        previous: &OverlayState,
+        next: &mut OverlayState,
+        debounce: Duration,
+        listening_requested: bool,
+        now: Instant,
+    ) -> Option<u64> {
+        let mut pending = self.pending.lock().ok()?;
+        if debounce.is_zero() || previous.listening == READY {
+            *pending = None;
+            return None;
+        }
+        if next.listening != READY {
+            if listening_requested {
+                *pending = None;
+            }
+            return None;
+        }
+        match pending.as_ref() {
+            Some(release) if now.duration_since(release.since) >= debounce => *pending = None,
+            Some(_) => next.listening = previous.listening.clone(),
+            None => {
+                let generation = self.generation.fetch_add(1, Ordering::Relaxed) + 1;
+                *pending = Some(PendingRelease {
+                    since: now,
+                    generation,
+                });
+                next.listening = previous.listening.clone();
+                return Some(generation);
+            }
+        }
+        None
+    }
+
+    fn is_pending(&self, generation: u64) -> bool {
+        self.pending
+            .lock()
+            .map(|pending| pending.as_ref().map(|release| release.generation) == Some(generation))
+            .unwrap_or(false)
+    }
+}
+
+fn schedule_release(
+    shared: &Arc<SharedOverlayState>,
+    generation: u64,
+    debounce: Duration,
+    release: impl FnOnce(&Arc<SharedOverlayState>) + Send + 'static,
+) {
+    shared.scheduler.after("listening_release", debounce, move |shared| {
+        if shared.listening_hysteresis.is_pending(generation) {
+            release(shared);
+        }
+    });
+}
+
+#[tauri::command]
+pub(crate) fn set_listening_debounce(
+    ms: u64,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if ms > MAX_LISTENING_DEBOUNCE_MS {
+        return Err(format!(
+            "listening debounce must be at most {} ms",
+            MAX_LISTENING_DEBOUNCE_MS
+        ));
+    }
+    update_config(&app, shared.inner(), |config| config.listening_debounce_ms = ms)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use super::{schedule_release, READY};
+    use crate::{
+        clock::{Clock, MockClock, SharedClock},
+        lock_state,
+        scheduler::Scheduler,
+        OverlayState, SharedOverlayState,
+    };
+
+    const DEBOUNCE: Duration = Duration::from_millis(200);
+
+    /// `update_overlay_state` reduced to the hysteresis step.
+    fn update(shared: &Arc<SharedOverlayState>, edit: impl FnOnce(&mut OverlayState), listening_requested: bool) {
+        let mut state = lock_state(shared).unwrap();
+        let previous = state.clone();
+        edit(&mut state);
+        let now = shared.scheduler.clock().now();
+        if let Some(generation) = shared
+            .listening_hysteresis
+            .hold(&previous, &mut state, DEBOUNCE, listening_requested, now)
+        {
+            schedule_release(shared, generation, DEBOUNCE, |shared| {
+                update(shared, |state| state.listening = READY.to_string(), false);
+            });
+        }
+    }
+
+    fn recording() -> (Arc<MockClock>, Arc<SharedOverlayState>) {
+        let clock = MockClock::new();
+        let shared = Arc::new(SharedOverlayState {
+            scheduler: Scheduler::with_clock(SharedClock::from(clock.clone() as Arc<dyn Clock>)),
+            ..Default::default()
+        });
+        lock_state(&shared).unwrap().listening = "recording".to_string();
+        (clock, shared)
+    }
+
+    fn listening(shared: &Arc<SharedOverlayState>) -> String {
+        lock_state(shared).unwrap().listening.clone()
+    }
+
+    #[test]
+    fn a_level_patch_keeps_the_pending_release() {
+        let (clock, shared) = recording();
+        update(&shared, |state| state.listening = READY.to_string(), true);
+        assert_eq!(listening(&shared), "recording");
+
+        clock.advance(Duration::from_millis(50));
+        update(&shared, |state| state.level = 0.4, false);
+        assert_eq!(listening(&shared), "recording");
+
+        clock.advance(DEBOUNCE);
+        shared.scheduler.run_due(&shared);
+        assert_eq!(listening(&shared), READY);
+    }
+
+    #[test]
+    fn an_explicit_value_withdraws_the_release() {
+        let (clock, shared) = recording();
+        update(&shared, |state| state.listening = READY.to_string(), true);
+        update(&shared, |state| state.listening = "recording".to_string(), true);
+
+        clock.advance(DEBOUNCE);
+        shared.scheduler.run_due(&shared);
+        assert_eq!(listening(&shared), "recording");
+    }
+}
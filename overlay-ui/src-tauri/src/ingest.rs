@@ -0,0 +1,229 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicU32, AtomicU64, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, State};
+
+use crate::{
+    config::update_config, context, decode_payload, lock_config, lock_state, projection::state_field_names,
+    replace_state, sequence::BridgeUpdate, OverlayState, SharedOverlayState,
+};
+
+const MAX_LEVEL_HZ: u32 = 1_000;
+const MAX_SOURCE_OWNERSHIP_MS: u64 = 60_000;
+
+/// Ingestion path a bridge payload arrived on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Transport {
+    Udp,
+    UnixSocket,
+    File,
+    Osc,
+}
+
+pub(crate) fn default_source_priority() -> Vec<Transport> {
+    vec![Transport::Udp, Transport::UnixSocket, Transport::File, Transport::Osc]
+}
+
+/// Lets a higher-priority transport own the overlay for `source_ownership_ms`
+/// after each of its updates; lower ones are ignored meanwhile.
+#[derive(Default)]
+pub(crate) struct SourceArbiter {
+    last_update: Mutex<BTreeMap<Transport, Instant>>,
+    last_source: Mutex<Option<Transport>>,
+}
+
+impl SourceArbiter {
+    /// Returns false when a transport ranked above `transport` updated within `ownership`.
+    /// Transports missing from `priority` rank lowest.
+    pub(crate) fn admit(&self, transport: Transport, priority: &[Transport], ownership: Duration, now: Instant) -> bool {
+        let rank = |candidate: Transport| {
+            priority
+                .iter()
+                .position(|entry| *entry == candidate)
+                .unwrap_or(priority.len())
+        };
+        let Ok(mut last_update) = self.last_update.lock() else {
+            return true;
+        };
+        let owned_above = last_update
+            .iter()
+            .any(|(other, at)| rank(*other) < rank(transport) && now.duration_since(*at) < ownership);
+        if owned_above {
+            return false;
+        }
+        last_update.insert(transport, now);
+        if let Ok(mut last_source) = self.last_source.lock() {
+            *last_source = Some(transport);
+        }
+        true
+    }
+}
+
+/// Keys a payload may carry that the emitted state doesn't: the tag,
+/// incremental patches, and one-shot inputs the backend consumes.
+const PATCH_ONLY_FIELDS: &[&str] = &["kind", "requires_ack", "sound", "step_patch", "word_patch", "segment_patch"];
+
+/// Every top-level key a bridge payload may carry, as a patch or a full state
+/// (including backend fields echoed back from `get_overlay_state`).
+fn known_payload_fields() -> &'static [String] {
+    static FIELDS: OnceLock<Vec<String>> = OnceLock::new();
+    FIELDS.get_or_init(|| {
+        let mut fields = state_field_names();
+        fields.extend(PATCH_ONLY_FIELDS.iter().map(|field| field.to_string()));
+        fields
+    })
+}
+
+/// First key of a JSON object payload that no state or patch field matches.
+pub(crate) fn unknown_payload_field(payload: &str) -> Option<String> {
+    let Ok(Value::Object(fields)) = serde_json::from_str::<Value>(payload) else {
+        return None;
+    };
+    fields
+        .keys()
+        .find(|key| !known_payload_fields().contains(key))
+        .cloned()
+}
+
+/// Drops level-only patches that arrive faster than `max_level_hz`, before they reach the state lock.
+#[derive(Default)]
+pub(crate) struct LevelIngestLimit {
+    /// The scheduler-clock instant of the first patch checked.
+    origin: OnceLock<Instant>,
+    max_hz: AtomicU32,
+    /// Nanoseconds since `origin` of the last accepted level-only patch, plus one; 0 means none yet.
+    last_accepted: AtomicU64,
+}
+
+impl LevelIngestLimit {
+    pub(crate) fn set_max_hz(&self, max_hz: u32) {
+        self.max_hz.store(max_hz, Ordering::Relaxed);
+    }
+
+    /// Returns true when this level-only patch should be skipped.
+    pub(crate) fn skip(&self, now: Instant) -> bool {
+        let max_hz = self.max_hz.load(Ordering::Relaxed);
+        if max_hz == 0 {
+            return false;
+        }
+        let interval = 1_000_000_000 / u64::from(max_hz);
+        let origin = *self.origin.get_or_init(|| now);
+        let now = now.duration_since(origin).as_nanos() as u64 + 1;
+        let last = self.last_accepted.load(Ordering::Relaxed);
+        if last != 0 && now.saturating_sub(last) < interval {
+            return true;
+        }
+        // Losing the race to another accept is fine; either one stands for this interval.
+        self.last_accepted
+            .compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed)
+            .is_err()
+    }
+}
+
+/// Caps how many level-only UDP patches per second are applied; 0 removes the cap.
+#[tauri::command]
+pub(crate) fn set_max_level_hz(
+    hz: u32,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if hz > MAX_LEVEL_HZ {
+        return Err(format!("max level rate must be at most {} Hz", MAX_LEVEL_HZ));
+    }
+    update_config(&app, shared.inner(), |config| config.max_level_hz = hz)?;
+    shared.level_ingest.set_max_hz(hz);
+    Ok(())
+}
+
+/// Rejects bridge payloads with unknown keys (e.g. a `levle` typo) instead of ignoring them.
+#[tauri::command]
+pub(crate) fn set_strict_fields(
+    enabled: bool,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    update_config(&app, shared.inner(), |config| config.strict_fields = enabled)?;
+    Ok(())
+}
+
+/// Highest-priority transport first; `ownership_ms` is how long each of its
+/// updates locks out the ones below it (0 disables arbitration).
+#[tauri::command]
+pub(crate) fn set_source_priority(
+    order: Vec<Transport>,
+    ownership_ms: u64,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if ownership_ms > MAX_SOURCE_OWNERSHIP_MS {
+        return Err(format!("source ownership must be at most {} ms", MAX_SOURCE_OWNERSHIP_MS));
+    }
+    update_config(&app, shared.inner(), |config| {
+        config.source_priority = order;
+        config.source_ownership_ms = ownership_ms;
+    })?;
+    Ok(())
+}
+
+/// The transport whose update was applied last, if any.
+#[tauri::command]
+pub(crate) fn get_last_update_source(shared: State<'_, Arc<SharedOverlayState>>) -> Result<Option<Transport>, String> {
+    shared
+        .source_arbiter
+        .last_source
+        .lock()
+        .map(|last_source| *last_source)
+        .map_err(|_| "source arbiter lock poisoned".to_string())
+}
+
+/// The state a bridge payload would leave behind, applied on top of the
+/// current state without storing it. Pins, sticky fields and the context line
+/// are included; time-based policies (debounce, visibility floor and cap,
+/// auto-clear) and muting are not.
+#[tauri::command]
+pub(crate) fn preview_apply(payload: String, shared: State<'_, Arc<SharedOverlayState>>) -> Result<OverlayState, String> {
+    let mut state = lock_state(shared.inner())?.clone();
+    let config = lock_config(shared.inner())?.clone();
+    if config.strict_fields {
+        if let Some(key) = unknown_payload_field(&payload) {
+            return Err(format!("unknown field {:?}", key));
+        }
+    }
+    match decode_payload(&payload)?.0 {
+        BridgeUpdate::State(next) => replace_state(&mut state, next, &config.sticky_fields),
+        BridgeUpdate::Patch(patch) => patch.apply(&mut state),
+    }
+    shared.pinned_fields.apply(&mut state);
+    state.context = context::context_line(&config.context_template, &state);
+    state.sound = None;
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::LevelIngestLimit;
+    use crate::clock::{Clock, MockClock};
+
+    #[test]
+    fn skips_levels_within_the_interval_of_the_clock() {
+        let clock = MockClock::new();
+        let limit = LevelIngestLimit::default();
+        limit.set_max_hz(10);
+        assert!(!limit.skip(clock.now()));
+        clock.advance(Duration::from_millis(50));
+        assert!(limit.skip(clock.now()));
+        clock.advance(Duration::from_millis(50));
+        assert!(!limit.skip(clock.now()));
+    }
+}
@@ -0,0 +1,90 @@
+use std::net::UdpSocket;
+
+use serde_json::{json, Map, Value};
+
+use crate::{cli::StartupArgs, UDP_ADDR};
+
+// This runs before the log plugin is installed, so failures go to stderr.
+
+/// Held for the lifetime of the first overlay process.
+pub(crate) struct InstanceGuard {
+    #[cfg(windows)]
+    handle: windows_sys::Win32::Foundation::HANDLE,
+}
+
+#[cfg(windows)]
+impl Drop for InstanceGuard {
+    fn drop(&mut self) {
+        use windows_sys::Win32::Foundation::CloseHandle;
+
+        if !self.handle.is_null() {
+            // SAFETY: `handle` came from CreateMutexW and is closed only here.
+            unsafe { CloseHandle(self.handle) };
+        }
+    }
+}
+
+/// Takes the per-session named mutex, or returns `None` when another
+/// overlay already holds it.
+#[cfg(windows)]
+pub(crate) fn acquire_instance_guard() -> Option<InstanceGuard> {
+    use windows_sys::{
+        core::w,
+        Win32::{
+            Foundation::{CloseHandle, GetLastError, ERROR_ALREADY_EXISTS},
+            System::Threading::CreateMutexW,
+        },
+    };
+
+    // SAFETY: the name is a static wide literal and no security attributes are passed.
+    let handle = unsafe { CreateMutexW(std::ptr::null(), 0, w!("Local\\VoiceKeyOverlaySingleInstance")) };
+    if handle.is_null() {
+        eprintln!("failed to create the single-instance mutex; continuing without it");
+        return Some(InstanceGuard { handle });
+    }
+    // SAFETY: called right after CreateMutexW on the same thread.
+    if unsafe { GetLastError() } == ERROR_ALREADY_EXISTS {
+        // SAFETY: `handle` is a valid mutex handle that this process no longer needs.
+        unsafe { CloseHandle(handle) };
+        return None;
+    }
+    Some(InstanceGuard { handle })
+}
+
+#[cfg(not(windows))]
+pub(crate) fn acquire_instance_guard() -> Option<InstanceGuard> {
+    Some(InstanceGuard {})
+}
+
+/// The startup overrides as a bridge patch, or `None` when there are none.
+fn forward_payload(startup: &StartupArgs) -> Option<Value> {
+    let patch = &startup.patch;
+    let mut payload = Map::new();
+    if let Some(target) = &patch.target {
+        payload.insert("target".to_string(), json!(target));
+    }
+    if let Some(connection) = &patch.connection {
+        payload.insert("connection".to_string(), json!(connection));
+    }
+    if let Some(visible) = patch.visible {
+        payload.insert("visible".to_string(), json!(visible));
+    }
+    if payload.is_empty() {
+        return None;
+    }
+    payload.insert("kind".to_string(), json!("patch"));
+    Some(Value::Object(payload))
+}
+
+/// Sends this launch's overrides to the running overlay's UDP bridge instead
+/// of starting a second overlay that would fail to bind it.
+pub(crate) fn forward_to_running_instance(startup: &StartupArgs) {
+    eprintln!("another overlay instance is already running; forwarding startup arguments and exiting");
+    let Some(payload) = forward_payload(startup) else {
+        return;
+    };
+    let sent = UdpSocket::bind("127.0.0.1:0").and_then(|socket| socket.send_to(payload.to_string().as_bytes(), UDP_ADDR));
+    if let Err(error) = sent {
+        eprintln!("failed to forward startup arguments to the running overlay: {}", error);
+    }
+}
@@ -0,0 +1,80 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use tauri::{AppHandle, State};
+
+use crate::{
+    main_window,
+    timeline::{ChangeSource, SourceScope},
+    update_overlay_state, SharedOverlayState,
+};
+
+const MAX_INTERACTIVE_TIMEOUT_MS: u64 = 60_000;
+
+/// Bounded window during which the overlay accepts clicks, e.g. to dismiss an error.
+#[derive(Default)]
+pub(crate) struct InteractiveMode {
+    generation: AtomicU64,
+}
+
+fn set_interactive(app: &AppHandle, shared: &Arc<SharedOverlayState>, interactive: bool) -> Result<(), String> {
+    main_window(app)?
+        .set_ignore_cursor_events(!interactive)
+        .map_err(|error| error.to_string())?;
+    update_overlay_state(app, shared, |state| state.interactive = interactive)
+}
+
+/// Makes the overlay clickable and reverts to click-through after `timeout_ms`.
+#[tauri::command]
+pub(crate) fn enter_interactive_mode(
+    timeout_ms: u64,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if timeout_ms == 0 || timeout_ms > MAX_INTERACTIVE_TIMEOUT_MS {
+        return Err(format!(
+            "interactive timeout must be between 1 and {} ms",
+            MAX_INTERACTIVE_TIMEOUT_MS
+        ));
+    }
+    let shared = shared.inner().clone();
+    let generation = shared.interactive_mode.generation.fetch_add(1, Ordering::Relaxed) + 1;
+    set_interactive(&app, &shared, true)?;
+    shared.scheduler.after("interactive_timeout", Duration::from_millis(timeout_ms), move |shared| {
+        if shared.interactive_mode.generation.load(Ordering::Relaxed) == generation {
+            if let Err(error) = set_interactive(&app, shared, false) {
+                log::warn!("failed to leave interactive mode: {}", error);
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Clears the current message and error, then returns to click-through.
+#[tauri::command]
+pub(crate) fn dismiss_overlay(app: AppHandle, shared: State<'_, Arc<SharedOverlayState>>) -> Result<(), String> {
+    shared.interactive_mode.generation.fetch_add(1, Ordering::Relaxed);
+    shared.last_error.clear();
+    shared.message_ack.clear();
+    shared.connection_behaviors.clear();
+    main_window(&app)?
+        .set_ignore_cursor_events(true)
+        .map_err(|error| error.to_string())?;
+    let _source = SourceScope::enter(ChangeSource::Command);
+    update_overlay_state(&app, shared.inner(), |state| {
+        state.interactive = false;
+        state.message = None;
+        state.message_id = None;
+        if state.processing == "error" {
+            state.processing = "idle".to_string();
+        }
+        if state.listening == "error" {
+            state.listening = "ready".to_string();
+        }
+    })
+}
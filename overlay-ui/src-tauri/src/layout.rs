@@ -0,0 +1,261 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, LogicalSize, Manager, Size, State, WebviewWindow};
+
+use crate::{
+    config::{update_config, OverlayConfig},
+    current_or_primary_monitor, lock_config, main_window,
+    position::PositionTrigger,
+    position_overlay_window,
+    statusdot::{dot_size, DOT_SIZE},
+    update_overlay_state, SharedOverlayState, TASKBAR_MARGIN_PX,
+};
+
+pub(crate) const DEFAULT_WINDOW_WIDTH: f64 = 194.0;
+pub(crate) const DEFAULT_WINDOW_HEIGHT: f64 = 126.0;
+pub(crate) const DEFAULT_CORNER_RADIUS: f64 = 7.0;
+const MIN_WINDOW_SIDE: f64 = 48.0;
+const BANNER_HEIGHT: f64 = 40.0;
+const COLLAPSED_WINDOW_WIDTH: f64 = 96.0;
+const COLLAPSED_WINDOW_HEIGHT: f64 = 48.0;
+const MIN_TEXT_SCALE: f64 = 0.5;
+const MAX_TEXT_SCALE: f64 = 3.0;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum DisplayForm {
+    /// Compact overlay at the configured anchor.
+    #[default]
+    Overlay,
+    /// Full-width strip along the top edge of the monitor.
+    Banner,
+}
+
+/// Logical window size and corner rounding, before text scaling.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct OverlayGeometry {
+    pub(crate) width: f64,
+    pub(crate) height: f64,
+    pub(crate) corner_radius: f64,
+}
+
+impl Default for OverlayGeometry {
+    fn default() -> Self {
+        Self {
+            width: DEFAULT_WINDOW_WIDTH,
+            height: DEFAULT_WINDOW_HEIGHT,
+            corner_radius: DEFAULT_CORNER_RADIUS,
+        }
+    }
+}
+
+impl OverlayGeometry {
+    /// A small pill, the default `collapsed_geometry`.
+    pub(crate) fn collapsed() -> Self {
+        Self {
+            width: COLLAPSED_WINDOW_WIDTH,
+            height: COLLAPSED_WINDOW_HEIGHT,
+            corner_radius: COLLAPSED_WINDOW_HEIGHT / 2.0,
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if ![self.width, self.height, self.corner_radius].iter().all(|value| value.is_finite()) {
+            return Err("overlay geometry values must be finite numbers".to_string());
+        }
+        if self.width < MIN_WINDOW_SIDE || self.height < MIN_WINDOW_SIDE {
+            return Err(format!("overlay width and height must be at least {}", MIN_WINDOW_SIDE));
+        }
+        if self.corner_radius < 0.0 || self.corner_radius > self.width.min(self.height) / 2.0 {
+            return Err("corner radius must be between 0 and half the shorter side".to_string());
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn clamp_text_scale(scale: f64) -> f64 {
+    if scale.is_finite() {
+        scale.clamp(MIN_TEXT_SCALE, MAX_TEXT_SCALE)
+    } else {
+        1.0
+    }
+}
+
+fn scaled_size(geometry: &OverlayGeometry, text_scale: f64) -> LogicalSize<f64> {
+    let scale = clamp_text_scale(text_scale);
+    LogicalSize::new(geometry.width * scale, geometry.height * scale)
+}
+
+fn overlay_size(config: &OverlayConfig) -> LogicalSize<f64> {
+    scaled_size(&config.geometry, config.text_scale)
+}
+
+/// Full monitor width at the top edge, or the compact size when there is no monitor.
+fn banner_size(window: &WebviewWindow, config: &OverlayConfig) -> tauri::Result<LogicalSize<f64>> {
+    let height = BANNER_HEIGHT * clamp_text_scale(config.text_scale);
+    Ok(match current_or_primary_monitor(window)? {
+        Some(monitor) => LogicalSize::new(f64::from(monitor.size().width) / monitor.scale_factor(), height),
+        None => overlay_size(config),
+    })
+}
+
+#[cfg(windows)]
+fn apply_window_region(window: &WebviewWindow, size: LogicalSize<f64>, corner_radius: f64) -> tauri::Result<()> {
+    use std::ptr::null_mut;
+    use windows_sys::Win32::Graphics::Gdi::{CreateRoundRectRgn, DeleteObject, SetWindowRgn};
+
+    let scale_factor = window.scale_factor()?;
+    let size = size.to_physical::<i32>(scale_factor);
+    let diameter = (corner_radius * scale_factor * 2.0) as i32;
+    let hwnd = window.hwnd()?.0;
+    // SAFETY: `hwnd` is this process's live overlay window. On success the
+    // system owns the region; on failure it is still ours to delete.
+    unsafe {
+        if diameter == 0 {
+            SetWindowRgn(hwnd, null_mut(), 1);
+            return Ok(());
+        }
+        let region = CreateRoundRectRgn(0, 0, size.width + 1, size.height + 1, diameter, diameter);
+        if !region.is_null() && SetWindowRgn(hwnd, region, 1) == 0 {
+            DeleteObject(region);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn apply_window_region(_window: &WebviewWindow, _size: LogicalSize<f64>, _corner_radius: f64) -> tauri::Result<()> {
+    Ok(())
+}
+
+/// Resizes the window to the size implied by `config` and re-anchors it.
+pub(crate) fn apply_overlay_layout(
+    window: &WebviewWindow,
+    config: &OverlayConfig,
+    trigger: PositionTrigger,
+) -> tauri::Result<()> {
+    let shared = window.state::<Arc<SharedOverlayState>>();
+    let split = shared.split_view.is_active();
+    let geometry = if shared.auto_expand.is_collapsed() {
+        &config.collapsed_geometry
+    } else {
+        &config.geometry
+    };
+    let (logical_size, corner_radius) = match config.display_form {
+        _ if shared.status_dot.is_active() => (dot_size(), DOT_SIZE / 2.0),
+        DisplayForm::Overlay => {
+            let size = scaled_size(geometry, config.text_scale);
+            let width = if split { size.width * 2.0 } else { size.width };
+            (
+                LogicalSize::new(width, size.height),
+                geometry.corner_radius * clamp_text_scale(config.text_scale),
+            )
+        }
+        DisplayForm::Banner => (banner_size(window, config)?, 0.0),
+    };
+    let size = Size::Logical(logical_size);
+    // Drop the fixed min/max first so growing and shrinking are both allowed.
+    window.set_min_size(None::<Size>)?;
+    window.set_max_size(None::<Size>)?;
+    window.set_size(size)?;
+    window.set_min_size(Some(size))?;
+    window.set_max_size(Some(size))?;
+    apply_window_region(window, logical_size, corner_radius)?;
+    position_overlay_window(window, trigger)
+}
+
+/// Dry-runs the layout `config` implies against the connected monitors and
+/// returns human-readable warnings; nothing is applied.
+#[tauri::command]
+pub(crate) fn validate_positioning_config(config: OverlayConfig, app: AppHandle) -> Result<Vec<String>, String> {
+    let mut warnings = Vec::new();
+    if !config.text_scale.is_finite() || clamp_text_scale(config.text_scale) != config.text_scale {
+        warnings.push(format!(
+            "text scale {} is outside {}..{}, will be clamped to {}",
+            config.text_scale,
+            MIN_TEXT_SCALE,
+            MAX_TEXT_SCALE,
+            clamp_text_scale(config.text_scale)
+        ));
+    }
+    let window = main_window(&app)?;
+    let monitor = current_or_primary_monitor(&window).map_err(|error| error.to_string())?;
+    let Some(monitor) = monitor else {
+        warnings.push("no monitor detected, the overlay position can't be checked".to_string());
+        return Ok(warnings);
+    };
+    let scale_factor = monitor.scale_factor();
+    let size = overlay_size(&config).to_physical::<f64>(scale_factor);
+    let margin = f64::from(TASKBAR_MARGIN_PX) * scale_factor;
+    let extent = monitor.size();
+    if size.width > f64::from(extent.width) {
+        warnings.push(format!(
+            "overlay width {:.0}px exceeds monitor width {}px",
+            size.width, extent.width
+        ));
+    }
+    if size.height + margin > f64::from(extent.height) {
+        warnings.push(format!(
+            "overlay height {:.0}px plus the {:.0}px taskbar margin exceeds monitor height {}px, it will be pinned to the top",
+            size.height, margin, extent.height
+        ));
+    }
+    Ok(warnings)
+}
+
+/// Resizes, re-rounds and re-anchors the window; rejects sizes larger than its monitor.
+#[tauri::command]
+pub(crate) fn set_overlay_geometry(
+    geometry: OverlayGeometry,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<OverlayGeometry, String> {
+    geometry.validate()?;
+    let window = main_window(&app)?;
+    let mut candidate = lock_config(shared.inner())?.clone();
+    candidate.geometry = geometry;
+    let monitor = current_or_primary_monitor(&window).map_err(|error| error.to_string())?;
+    if let Some(monitor) = monitor {
+        let size = overlay_size(&candidate).to_physical::<f64>(monitor.scale_factor());
+        let extent = monitor.size();
+        if size.width > f64::from(extent.width) || size.height > f64::from(extent.height) {
+            return Err(format!(
+                "overlay size {:.0}x{:.0}px exceeds the monitor's {}x{}px",
+                size.width, size.height, extent.width, extent.height
+            ));
+        }
+    }
+    let config = update_config(&app, shared.inner(), |config| config.geometry = geometry)?;
+    apply_overlay_layout(&window, &config, PositionTrigger::Layout).map_err(|error| error.to_string())?;
+    update_overlay_state(&app, shared.inner(), |state| state.corner_radius = geometry.corner_radius)?;
+    Ok(geometry)
+}
+
+#[tauri::command]
+pub(crate) fn set_display_form(
+    form: DisplayForm,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    let config = update_config(&app, shared.inner(), |config| config.display_form = form)?;
+    apply_overlay_layout(&main_window(&app)?, &config, PositionTrigger::Layout).map_err(|error| error.to_string())?;
+    update_overlay_state(&app, shared.inner(), |state| state.display_form = form)
+}
+
+#[tauri::command]
+pub(crate) fn set_text_scale(
+    scale: f64,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<f64, String> {
+    if !scale.is_finite() {
+        return Err("text scale must be a finite number".to_string());
+    }
+    let scale = clamp_text_scale(scale);
+    let config = update_config(&app, shared.inner(), |config| config.text_scale = scale)?;
+    apply_overlay_layout(&main_window(&app)?, &config, PositionTrigger::Layout).map_err(|error| error.to_string())?;
+    update_overlay_state(&app, shared.inner(), |state| state.text_scale = scale)?;
+    Ok(scale)
+}
@@ -0,0 +1,112 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, PhysicalPosition, PhysicalSize, State, WebviewWindow};
+
+use crate::{
+    config::update_config, dock, lock_config, main_window, position::PositionTrigger, position_overlay_window,
+    OverlayState, SharedOverlayState,
+};
+
+/// The picture area of a playing video, in physical screen pixels, as reported by a sender.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct VideoRegion {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+impl VideoRegion {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    fn bottom(&self) -> i32 {
+        self.y + self.height as i32
+    }
+}
+
+/// The last reported video region, kept apart from the state so positioning
+/// never needs the state lock.
+#[derive(Default)]
+pub(crate) struct Letterbox {
+    region: Mutex<Option<VideoRegion>>,
+}
+
+impl Letterbox {
+    /// Tracks `video_region` and, in letterbox-aware mode, repositions when it changes.
+    pub(crate) fn follow(
+        &self,
+        app: &AppHandle,
+        shared: &Arc<SharedOverlayState>,
+        enabled: bool,
+        previous: &OverlayState,
+        next: &OverlayState,
+    ) {
+        if previous.video_region == next.video_region {
+            return;
+        }
+        if let Ok(mut region) = self.region.lock() {
+            *region = next.video_region;
+        }
+        if !enabled {
+            return;
+        }
+        let app = app.clone();
+        shared.scheduler.after("letterbox", Duration::ZERO, move |_| {
+            if let Ok(window) = main_window(&app) {
+                if let Err(error) = position_overlay_window(&window, PositionTrigger::Letterbox) {
+                    log::warn!("failed to reposition overlay into the letterbox: {}", error);
+                }
+            }
+        });
+    }
+}
+
+/// Centered under the video in the bottom bar, or else above it in the top bar,
+/// of the docked window or, undocked, the video's monitor. `None` when letterbox
+/// mode is off, no region is known or neither bar fits the overlay.
+pub(crate) fn letterbox_position(window: &WebviewWindow, size: PhysicalSize<u32>) -> Option<PhysicalPosition<i32>> {
+    let shared = window.state::<Arc<SharedOverlayState>>();
+    if !lock_config(shared.inner()).ok()?.letterbox_aware {
+        return None;
+    }
+    let region = (*shared.letterbox.region.lock().ok()?)?;
+    let (frame_top, frame_bottom) = match dock::docked_bounds(shared.inner()) {
+        Some(bounds) => (bounds.top, bounds.bottom),
+        None => {
+            let center_x = region.x + region.width as i32 / 2;
+            let center_y = region.y + region.height as i32 / 2;
+            let monitor = window.monitor_from_point(f64::from(center_x), f64::from(center_y)).ok()??;
+            (monitor.position().y, monitor.position().y + monitor.size().height as i32)
+        }
+    };
+    let height = size.height as i32;
+    let x = region.x + (region.width as i32 - size.width as i32) / 2;
+    let below = frame_bottom - region.bottom();
+    if below >= height {
+        return Some(PhysicalPosition::new(x, region.bottom() + (below - height) / 2));
+    }
+    let above = region.y - frame_top;
+    if above >= height {
+        return Some(PhysicalPosition::new(x, frame_top + (above - height) / 2));
+    }
+    None
+}
+
+/// Places the overlay in the letterbox bars around a sender-reported `video_region`
+/// instead of at the anchor; falls back to the anchor without one.
+#[tauri::command]
+pub(crate) fn set_letterbox_aware(
+    enabled: bool,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    update_config(&app, shared.inner(), |config| config.letterbox_aware = enabled)?;
+    position_overlay_window(&main_window(&app)?, PositionTrigger::Letterbox).map_err(|error| error.to_string())
+}
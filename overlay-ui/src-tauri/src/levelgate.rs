@@ -0,0 +1,100 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use crate::{config::update_config, update_overlay_state, OverlayState, SharedOverlayState};
+
+const MAX_LEVEL_GATE_HOLD_MS: u64 = 10_000;
+
+/// Show the overlay once `level` has stayed above `threshold` for `hold_ms`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct VisibilityLevelGate {
+    pub(crate) threshold: f64,
+    #[serde(default)]
+    pub(crate) hold_ms: u64,
+}
+
+/// Forces `visible` on while the level gate is open; the sender's own
+/// `visible` is kept and restored once the level drops.
+#[derive(Default)]
+pub(crate) struct LevelGate {
+    above_since: Mutex<Option<Instant>>,
+    open: AtomicBool,
+    /// The `visible` senders asked for while the gate was open.
+    wanted_visible: AtomicBool,
+}
+
+impl LevelGate {
+    pub(crate) fn filter(
+        &self,
+        app: &AppHandle,
+        shared: &Arc<SharedOverlayState>,
+        gate: Option<VisibilityLevelGate>,
+        previous: &OverlayState,
+        next: &mut OverlayState,
+    ) {
+        let open = self.open.load(Ordering::Relaxed);
+        // While open `visible` reads true, so only a change is a sender's request.
+        if !open || next.visible != previous.visible {
+            self.wanted_visible.store(next.visible, Ordering::Relaxed);
+        }
+        let Ok(mut above_since) = self.above_since.lock() else {
+            return;
+        };
+        let Some(gate) = gate.filter(|gate| next.level > gate.threshold) else {
+            *above_since = None;
+            if open {
+                next.visible = self.wanted_visible.load(Ordering::Relaxed);
+                self.open.store(false, Ordering::Relaxed);
+            }
+            return;
+        };
+        let hold = Duration::from_millis(gate.hold_ms);
+        let now = shared.scheduler.clock().now();
+        let since = match *above_since {
+            Some(since) => since,
+            None => {
+                *above_since = Some(now);
+                if !hold.is_zero() {
+                    // Re-check at the end of the hold in case no further level arrives.
+                    let app = app.clone();
+                    shared.scheduler.after("level_gate", hold, move |shared| {
+                        let _ = update_overlay_state(&app, shared, |_| {});
+                    });
+                }
+                now
+            }
+        };
+        if now.duration_since(since) >= hold {
+            next.visible = true;
+            self.open.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Shows the overlay while `level` stays above `threshold` for `hold_ms`; `None` turns the gate off.
+#[tauri::command]
+pub(crate) fn set_visibility_level_gate(
+    gate: Option<VisibilityLevelGate>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if let Some(gate) = gate {
+        if !(0.0..1.0).contains(&gate.threshold) {
+            return Err("level gate threshold must be in 0..1".to_string());
+        }
+        if gate.hold_ms > MAX_LEVEL_GATE_HOLD_MS {
+            return Err(format!("level gate hold must be at most {} ms", MAX_LEVEL_GATE_HOLD_MS));
+        }
+    }
+    update_config(&app, shared.inner(), |config| config.visibility_level_gate = gate)?;
+    update_overlay_state(&app, shared.inner(), |_| {})
+}
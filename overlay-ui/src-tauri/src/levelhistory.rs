@@ -0,0 +1,105 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, State};
+
+use crate::{config::update_config, lock_config, lock_state, SharedOverlayState};
+
+const MAX_LEVEL_HISTORY_LEN: usize = 1_024;
+/// Faster than the scheduler tick would only repeat samples.
+const MIN_LEVEL_SAMPLE_MS: u64 = 16;
+const MAX_LEVEL_SAMPLE_MS: u64 = 1_000;
+
+/// Ring buffer of `level` sampled on the scheduler at a fixed rate, so a
+/// rolling waveform stays time-uniform however the datagrams arrive.
+#[derive(Default)]
+pub(crate) struct LevelHistory {
+    samples: Mutex<VecDeque<f64>>,
+    /// Bumped on each restart; a sampler from an older one stops.
+    generation: AtomicU64,
+}
+
+impl LevelHistory {
+    /// (Re)starts sampling with the configured length and rate; a length of 0 stops it.
+    pub(crate) fn start(&self, shared: &Arc<SharedOverlayState>) {
+        let generation = self.generation.fetch_add(1, Ordering::Relaxed) + 1;
+        let (length, sample_ms) = match lock_config(shared) {
+            Ok(config) => (config.level_history_len, config.level_history_sample_ms),
+            Err(_) => return,
+        };
+        if let Ok(mut samples) = self.samples.lock() {
+            samples.clear();
+        }
+        if length == 0 {
+            return;
+        }
+        let interval = Duration::from_millis(sample_ms);
+        shared.scheduler.repeat("level_history", interval, move |shared| {
+            let history = &shared.level_history;
+            if history.generation.load(Ordering::Relaxed) != generation {
+                return None;
+            }
+            let level = lock_state(shared).map(|state| state.level).ok()?;
+            let mut samples = history.samples.lock().ok()?;
+            if samples.len() >= length {
+                samples.pop_front();
+            }
+            samples.push_back(level);
+            Some(interval)
+        });
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct LevelHistorySnapshot {
+    sample_ms: u64,
+    /// Oldest first.
+    levels: Vec<f64>,
+}
+
+/// Keeps the last `length` levels, one per `sample_ms`; a length of 0 turns the history off.
+#[tauri::command]
+pub(crate) fn set_level_history(
+    length: usize,
+    sample_ms: u64,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if length > MAX_LEVEL_HISTORY_LEN {
+        return Err(format!("level history must be at most {} samples", MAX_LEVEL_HISTORY_LEN));
+    }
+    if !(MIN_LEVEL_SAMPLE_MS..=MAX_LEVEL_SAMPLE_MS).contains(&sample_ms) {
+        return Err(format!(
+            "level sample interval must be {}-{} ms",
+            MIN_LEVEL_SAMPLE_MS, MAX_LEVEL_SAMPLE_MS
+        ));
+    }
+    update_config(&app, shared.inner(), |config| {
+        config.level_history_len = length;
+        config.level_history_sample_ms = sample_ms;
+    })?;
+    shared.level_history.start(shared.inner());
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn get_level_history(shared: State<'_, Arc<SharedOverlayState>>) -> Result<LevelHistorySnapshot, String> {
+    let sample_ms = lock_config(shared.inner())?.level_history_sample_ms;
+    let samples = shared
+        .level_history
+        .samples
+        .lock()
+        .map_err(|_| "level history lock poisoned".to_string())?;
+    Ok(LevelHistorySnapshot {
+        sample_ms,
+        levels: samples.iter().copied().collect(),
+    })
+}
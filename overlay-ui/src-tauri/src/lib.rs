@@ -1,17 +1,166 @@
+mod ack;
+mod activity;
+mod announce;
+mod batch;
+mod bootstrap;
+mod callback;
+#[cfg(any(feature = "recording", debug_assertions))]
+mod capture;
+mod cli;
+mod clock;
+mod config;
+mod connection;
+mod context;
+#[cfg(debug_assertions)]
+mod controlsock;
+#[cfg(debug_assertions)]
+mod cycle;
+mod delta;
+mod diagnostics;
+mod display;
+mod dock;
+mod dpi;
+mod effects;
+mod entrance;
+mod errors;
+#[cfg(feature = "eventlog")]
+mod eventlog;
+mod expand;
+#[cfg(debug_assertions)]
+mod faults;
+mod fastlevel;
+mod feedback;
+mod fieldrates;
+#[cfg(feature = "file_source")]
+mod filesource;
+mod focus;
+mod gamemode;
+mod gpu;
+mod heartbeat;
+mod hotkey;
+#[cfg(feature = "http")]
+mod http;
+mod hysteresis;
+mod ingest;
+mod instance;
+mod interactive;
+mod layout;
+mod levelgate;
+mod letterbox;
+mod levelhistory;
+mod lockstats;
+mod logsample;
+mod logtap;
+mod meters;
+mod metrics;
+mod mute;
+#[cfg(feature = "obs")]
+mod obs;
+#[cfg(feature = "osc")]
+mod osc;
+mod peak;
+mod pins;
+mod placement;
+mod position;
+mod power;
+mod presets;
+mod quarantine;
+mod projection;
+mod ready;
+mod render;
+mod reposition;
+#[cfg(feature = "unixsocket")]
+mod rpc;
+mod rules;
+mod safety;
+mod saferect;
+mod scheduler;
+mod segments;
+mod sequence;
+mod session;
+mod severity;
+mod snapshot;
+mod sound;
+mod splash;
+mod split;
+mod stateline;
+mod statusdot;
+mod startup;
+mod steps;
+mod style;
+mod sticky;
+mod stream;
+#[cfg(any(feature = "http", feature = "unixsocket"))]
+mod subscribers;
+mod targets;
+mod template;
+mod theme;
+mod themepkg;
+mod timeline;
+mod undo;
+#[cfg(feature = "unixsocket")]
+mod unixsock;
+mod vectors;
+mod words;
+#[cfg(feature = "recording")]
+mod recording;
+
 use std::{
+    cell::Cell,
+    collections::BTreeMap,
     net::UdpSocket,
     sync::{Arc, Mutex},
     thread,
     time::Duration,
 };
 
-use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Emitter, Manager, PhysicalPosition, Position, State, WebviewWindow};
+use serde::{Deserialize, Deserializer, Serialize};
+use tauri::{AppHandle, Emitter, Manager, Monitor, PhysicalPosition, State, WebviewWindow};
+
+use config::OverlayConfig;
+
+/// Fields the update being applied set itself, rather than carrying over the current value.
+#[derive(Debug, Clone, Copy, Default)]
+struct RequestedFields {
+    visible: bool,
+    listening: bool,
+}
+
+impl RequestedFields {
+    const ALL: Self = Self {
+        visible: true,
+        listening: true,
+    };
+}
+
+thread_local! {
+    static REQUESTED: Cell<RequestedFields> = const {
+        Cell::new(RequestedFields {
+            visible: false,
+            listening: false,
+        })
+    };
+}
+
+/// Marks the update being applied as an explicit `visible` request.
+fn mark_visible_requested() {
+    REQUESTED.with(|requested| requested.set(RequestedFields { visible: true, ..requested.get() }));
+}
+
+/// Marks the update being applied as an explicit `listening` request.
+fn mark_listening_requested() {
+    REQUESTED.with(|requested| requested.set(RequestedFields { listening: true, ..requested.get() }));
+}
 
 const UDP_ADDR: &str = "127.0.0.1:38485";
+/// Plain-text datagram answered with a `pong` health reply instead of being parsed.
+const UDP_PING: &[u8] = b"ping";
 const TASKBAR_MARGIN_PX: i32 = 76;
+const MIN_BRIDGE_POLL_MS: u64 = 10;
+const MAX_BRIDGE_POLL_MS: u64 = 5_000;
+const MONITOR_CHECK_INTERVAL: Duration = Duration::from_secs(10);
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 struct OverlayState {
     connection: String,
@@ -19,9 +168,107 @@ struct OverlayState {
     processing: String,
     target: String,
     level: f64,
+    /// Named levels (input, output, ...); when non-empty they determine `level`.
+    #[serde(default)]
+    meters: BTreeMap<String, f64>,
+    /// Peak-hold marker; derived by the backend from `level`.
+    #[serde(default)]
+    peak_level: f64,
+    /// Smoothed 0..1 blend of `listening`, `processing` and `level`; derived by the backend.
+    #[serde(default)]
+    activity: f64,
+    /// Set for `clip_hold_ms` after `level` last reached full scale; derived by the backend.
+    #[serde(default)]
+    clipping: bool,
     visible: bool,
     #[serde(default)]
     message: Option<String>,
+    /// Sender id of `message`, matched by `acknowledge_message`.
+    #[serde(default)]
+    message_id: Option<String>,
+    /// Holds `message` until it is acknowledged; consumed by the backend and never emitted.
+    #[serde(default, skip_serializing)]
+    requires_ack: bool,
+    /// Set while a `requires_ack` message is held; derived by the backend.
+    #[serde(default)]
+    awaiting_ack: bool,
+    /// Set while the held message flashes for attention; derived by the backend.
+    #[serde(default)]
+    attention: bool,
+    #[serde(default)]
+    confidence: Option<f64>,
+    /// Pipeline checklist; when non-empty it determines `processing`.
+    #[serde(default)]
+    steps: Vec<steps::Step>,
+    /// Live transcription, one entry per word; an alternative to `message`.
+    #[serde(default)]
+    words: Vec<words::Word>,
+    /// Finalized, timestamped transcript; the oldest are evicted past `max_segments`.
+    #[serde(default)]
+    segments: Vec<segments::Segment>,
+    /// Command-mode recognition result, shown apart from `message`.
+    #[serde(default)]
+    command_feedback: Option<feedback::CommandFeedback>,
+    /// Picture area of a playing video, for letterbox-aware positioning.
+    #[serde(default)]
+    video_region: Option<letterbox::VideoRegion>,
+    /// Seconds left in a "get ready" countdown; set by the backend.
+    #[serde(default)]
+    ready_countdown: Option<u32>,
+    #[serde(default = "default_text_scale")]
+    text_scale: f64,
+    #[serde(default = "default_corner_radius")]
+    corner_radius: f64,
+    #[serde(default)]
+    display_form: layout::DisplayForm,
+    /// DPI scale of the monitor the overlay is on.
+    #[serde(default = "default_scale_factor")]
+    scale_factor: f64,
+    #[serde(default)]
+    power_mode: power::PowerMode,
+    #[serde(default)]
+    system_theme: theme::SystemTheme,
+    /// Set while the overlay accepts clicks; the frontend shows a dismiss button.
+    #[serde(default)]
+    interactive: bool,
+    /// 0 hidden to 1 at rest, animated on `visible` changes; derived by the backend.
+    #[serde(default)]
+    entrance: f64,
+    #[serde(default)]
+    show_animation: entrance::ShowAnimation,
+    /// Set while `stream_message` is still revealing `message`.
+    #[serde(default)]
+    streaming: bool,
+    /// Set while `auto_expand` has shrunk the window because there is no message.
+    #[serde(default)]
+    collapsed: bool,
+    /// Set while the hidden overlay shows only its status dot.
+    #[serde(default)]
+    status_dot: bool,
+    /// Themes of the two halves while the A/B split view is on.
+    #[serde(default)]
+    split_view: Option<split::SplitThemes>,
+    /// `target` and `message` combined through `context_template`; derived by the backend.
+    #[serde(default)]
+    context: Option<String>,
+    /// `display_template` filled from this state, shown verbatim; derived by the backend.
+    #[serde(default)]
+    rendered_text: Option<String>,
+    /// One-shot cue name; consumed by the backend and never emitted.
+    #[serde(default, skip_serializing)]
+    sound: Option<String>,
+}
+
+fn default_text_scale() -> f64 {
+    1.0
+}
+
+fn default_corner_radius() -> f64 {
+    layout::DEFAULT_CORNER_RADIUS
+}
+
+fn default_scale_factor() -> f64 {
+    1.0
 }
 
 impl Default for OverlayState {
@@ -32,12 +279,92 @@ impl Default for OverlayState {
             processing: "idle".to_string(),
             target: "unknown".to_string(),
             level: 0.0,
+            meters: BTreeMap::new(),
+            peak_level: 0.0,
+            activity: 0.0,
+            clipping: false,
             visible: false,
             message: None,
+            message_id: None,
+            requires_ack: false,
+            awaiting_ack: false,
+            attention: false,
+            confidence: None,
+            steps: Vec::new(),
+            words: Vec::new(),
+            segments: Vec::new(),
+            command_feedback: None,
+            video_region: None,
+            ready_countdown: None,
+            text_scale: default_text_scale(),
+            corner_radius: default_corner_radius(),
+            display_form: layout::DisplayForm::default(),
+            scale_factor: default_scale_factor(),
+            power_mode: power::PowerMode::default(),
+            system_theme: theme::SystemTheme::default(),
+            interactive: false,
+            entrance: 0.0,
+            show_animation: entrance::ShowAnimation::default(),
+            streaming: false,
+            collapsed: false,
+            status_dot: false,
+            split_view: None,
+            context: None,
+            rendered_text: None,
+            sound: None,
         }
     }
 }
 
+impl OverlayState {
+    fn sanitize(mut self) -> Self {
+        self.level = self.level.clamp(0.0, 1.0);
+        meters::sanitize_meters(&mut self.meters);
+        self.confidence = sanitize_confidence(self.confidence);
+        self.video_region = self.video_region.filter(|region| !region.is_empty());
+        self.sync_processing_with_steps();
+        words::cap_words(&mut self.words);
+        segments::sanitize_segments(&mut self.segments, segments::MAX_SEGMENTS);
+        self
+    }
+
+    fn sync_processing_with_steps(&mut self) {
+        if let Some(processing) = steps::processing_for(&self.steps) {
+            self.processing = processing.to_string();
+        }
+    }
+
+    /// Carries over fields the backend owns (config-derived or mode flags), which senders don't set.
+    fn keep_backend_fields(&mut self, previous: &OverlayState) {
+        self.text_scale = previous.text_scale;
+        self.corner_radius = previous.corner_radius;
+        self.display_form = previous.display_form;
+        self.scale_factor = previous.scale_factor;
+        self.power_mode = previous.power_mode;
+        self.system_theme = previous.system_theme;
+        self.interactive = previous.interactive;
+        self.entrance = previous.entrance;
+        self.streaming = previous.streaming;
+        self.collapsed = previous.collapsed;
+        self.status_dot = previous.status_dot;
+        self.split_view = previous.split_view;
+        self.ready_countdown = previous.ready_countdown;
+    }
+}
+
+fn sanitize_confidence(value: Option<f64>) -> Option<f64> {
+    value.filter(|value| value.is_finite()).map(|value| value.clamp(0.0, 1.0))
+}
+
+/// Distinguishes an explicit `null` (`Some(None)`) from an omitted field (`None`).
+fn deserialize_nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
 #[derive(Debug, Clone, Deserialize, Default)]
 #[serde(default, rename_all = "snake_case")]
 struct OverlayPatch {
@@ -46,8 +373,25 @@ struct OverlayPatch {
     processing: Option<String>,
     target: Option<String>,
     level: Option<f64>,
+    meters: Option<BTreeMap<String, f64>>,
     visible: Option<bool>,
     message: Option<String>,
+    #[serde(deserialize_with = "deserialize_nullable")]
+    message_id: Option<Option<String>>,
+    requires_ack: Option<bool>,
+    #[serde(deserialize_with = "deserialize_nullable")]
+    confidence: Option<Option<f64>>,
+    steps: Option<Vec<steps::Step>>,
+    step_patch: Option<steps::StepPatch>,
+    words: Option<Vec<words::Word>>,
+    word_patch: Option<words::WordPatch>,
+    segments: Option<Vec<segments::Segment>>,
+    segment_patch: Option<segments::SegmentPatch>,
+    #[serde(deserialize_with = "deserialize_nullable")]
+    command_feedback: Option<Option<feedback::CommandFeedback>>,
+    #[serde(deserialize_with = "deserialize_nullable")]
+    video_region: Option<Option<letterbox::VideoRegion>>,
+    sound: Option<String>,
 }
 
 impl OverlayPatch {
@@ -57,8 +401,30 @@ impl OverlayPatch {
             || self.processing.is_some()
             || self.target.is_some()
             || self.level.is_some()
+            || self.meters.is_some()
             || self.visible.is_some()
             || self.message.is_some()
+            || self.message_id.is_some()
+            || self.requires_ack.is_some()
+            || self.confidence.is_some()
+            || self.steps.is_some()
+            || self.step_patch.is_some()
+            || self.words.is_some()
+            || self.word_patch.is_some()
+            || self.segments.is_some()
+            || self.segment_patch.is_some()
+            || self.command_feedback.is_some()
+            || self.video_region.is_some()
+            || self.sound.is_some()
+    }
+
+    fn is_level_only(&self) -> bool {
+        self.level.is_some()
+            && !OverlayPatch {
+                level: None,
+                ..self.clone()
+            }
+            .has_updates()
     }
 
     fn apply(self, state: &mut OverlayState) {
@@ -67,6 +433,7 @@ impl OverlayPatch {
         }
         if let Some(value) = self.listening {
             state.listening = value;
+            mark_listening_requested();
         }
         if let Some(value) = self.processing {
             state.processing = value;
@@ -77,8 +444,13 @@ impl OverlayPatch {
         if let Some(value) = self.level {
             state.level = value.clamp(0.0, 1.0);
         }
+        if let Some(mut value) = self.meters {
+            meters::sanitize_meters(&mut value);
+            state.meters = value;
+        }
         if let Some(value) = self.visible {
             state.visible = value;
+            mark_visible_requested();
         }
         if let Some(value) = self.message {
             state.message = if value.trim().is_empty() {
@@ -87,23 +459,361 @@ impl OverlayPatch {
                 Some(value)
             };
         }
+        if let Some(value) = self.message_id {
+            state.message_id = value;
+        }
+        if let Some(value) = self.requires_ack {
+            state.requires_ack = value;
+        }
+        if let Some(value) = self.confidence {
+            state.confidence = sanitize_confidence(value);
+        }
+        if let Some(value) = self.steps {
+            state.steps = value;
+        }
+        if let Some(value) = self.step_patch {
+            value.apply(&mut state.steps);
+        }
+        state.sync_processing_with_steps();
+        if let Some(mut value) = self.words {
+            words::cap_words(&mut value);
+            state.words = value;
+        }
+        if let Some(value) = self.word_patch {
+            value.apply(&mut state.words);
+        }
+        if let Some(mut value) = self.segments {
+            segments::sanitize_segments(&mut value, segments::MAX_SEGMENTS);
+            state.segments = value;
+        }
+        if let Some(value) = self.segment_patch {
+            value.apply(&mut state.segments);
+        }
+        if let Some(value) = self.command_feedback {
+            state.command_feedback = value;
+        }
+        if let Some(value) = self.video_region {
+            state.video_region = value.filter(|region| !region.is_empty());
+        }
+        if let Some(value) = self.sound {
+            state.sound = Some(value);
+        }
     }
 }
 
 #[derive(Default)]
 struct SharedOverlayState {
     current: Mutex<OverlayState>,
+    config: Mutex<OverlayConfig>,
+    listening_hysteresis: hysteresis::ListeningHysteresis,
+    presets: presets::Presets,
+    level_throttle: power::LevelEmitThrottle,
+    dock: dock::Dock,
+    sound_cues: sound::SoundCues,
+    metrics: metrics::BridgeMetrics,
+    connection_watch: heartbeat::ConnectionWatch,
+    muted_messages: mute::MutedMessages,
+    visibility_callback: callback::VisibilityCallback,
+    error_events: errors::ErrorEvents,
+    visible_cap: safety::VisibleCap,
+    visible_floor: safety::VisibleFloor,
+    interactive_mode: interactive::InteractiveMode,
+    scheduler: scheduler::Scheduler,
+    delta_emitter: delta::DeltaEmitter,
+    sequencer: sequence::Sequencer,
+    render_stats: render::RenderStats,
+    level_ingest: ingest::LevelIngestLimit,
+    source_arbiter: ingest::SourceArbiter,
+    source_quarantine: quarantine::SourceQuarantine,
+    pinned_fields: pins::PinnedFields,
+    message_stream: stream::MessageStream,
+    message_auto_clear: severity::MessageAutoClear,
+    last_error: severity::LastErrorReplay,
+    peak_meter: peak::PeakMeter,
+    activity_meter: activity::ActivityMeter,
+    timeline: timeline::Timeline,
+    undo_stack: undo::UndoStack,
+    position_history: position::PositionHistory,
+    display_tracker: display::DisplayTracker,
+    adapter_watch: gpu::AdapterWatch,
+    reposition_throttle: reposition::RepositionThrottle,
+    split_view: split::SplitView,
+    session_lock: session::SessionLock,
+    target_gate: targets::TargetGate,
+    level_gate: levelgate::LevelGate,
+    level_history: levelhistory::LevelHistory,
+    letterbox: letterbox::Letterbox,
+    ready_countdown: ready::ReadyCountdown,
+    lock_stats: lockstats::LockStats,
+    status_dot: statusdot::StatusDot,
+    auto_expand: expand::AutoExpand,
+    command_feedback_clear: feedback::CommandFeedbackClear,
+    entrance_animation: entrance::EntranceAnimation,
+    game_overlay_mode: gamemode::GameOverlayMode,
+    display_template: template::DisplayTemplate,
+    field_rates: fieldrates::FieldRates,
+    splash: splash::Splash,
+    log_sampler: logsample::LogSampler,
+    message_ack: ack::MessageAck,
+    connection_behaviors: connection::ConnectionBehaviors,
+    startup_timings: startup::StartupTimings,
+    #[cfg(feature = "obs")]
+    obs_mirror: obs::ObsMirror,
+    #[cfg(any(feature = "http", feature = "unixsocket"))]
+    state_subscribers: subscribers::StateSubscribers,
+    #[cfg(debug_assertions)]
+    target_cycle: cycle::TargetCycle,
+    #[cfg(debug_assertions)]
+    fault_injection: faults::FaultInjection,
+    #[cfg(debug_assertions)]
+    scale_override: dpi::ScaleOverride,
+    #[cfg(debug_assertions)]
+    control_clock: Arc<controlsock::ControlClock>,
+}
+
+fn unix_millis() -> u64 {
+    clock::Clock::unix_millis(&clock::SystemClock)
 }
 
 fn emit_overlay_state(app: &AppHandle, state: &OverlayState) {
-    let _ = app.emit("overlay://state", state);
+    let shared = app.state::<Arc<SharedOverlayState>>();
+    shared.startup_timings.record(startup::StartupMilestone::FirstEmit);
+    let pushes = match lock_config(shared.inner()) {
+        Ok(config) => {
+            #[cfg(any(feature = "http", feature = "unixsocket"))]
+            shared.state_subscribers.broadcast(state, &config.private_fields);
+            if config.state_line_logging {
+                log::info!("{}", stateline::format_state_line(state, &config.state_line_fields));
+            }
+            config.delivery_mode.pushes()
+        }
+        Err(_) => true,
+    };
+    if pushes {
+        push_overlay_state(app, state);
+    }
+}
+
+/// Emits regardless of the delivery mode.
+fn push_overlay_state(app: &AppHandle, state: &OverlayState) {
+    let shared = app.state::<Arc<SharedOverlayState>>();
+    let (projection, delta_keyframe) = match lock_config(shared.inner()) {
+        Ok(config) => (
+            config.emit_projection.clone(),
+            config.delta_emit.then(|| Duration::from_millis(config.delta_keyframe_ms)),
+        ),
+        Err(_) => (Vec::new(), None),
+    };
+    if projection.is_empty() && delta_keyframe.is_none() {
+        let _ = app.emit("overlay://state", state);
+    } else {
+        let payload = if projection.is_empty() {
+            serde_json::to_value(state).unwrap_or_default()
+        } else {
+            projection::project_state(state, &projection)
+        };
+        match delta_keyframe {
+            Some(keyframe) => shared
+                .delta_emitter
+                .emit(app, payload, keyframe, shared.scheduler.clock().now()),
+            None => {
+                let _ = app.emit("overlay://state", payload);
+            }
+        }
+    }
+    shared.render_stats.record_emit();
 }
 
+/// Every path takes the state lock here, so the wait is measured here too.
 fn lock_state(shared: &Arc<SharedOverlayState>) -> Result<std::sync::MutexGuard<'_, OverlayState>, String> {
-    shared
+    match shared.current.try_lock() {
+        Ok(state) => {
+            shared.lock_stats.record_uncontended();
+            return Ok(state);
+        }
+        Err(std::sync::TryLockError::Poisoned(_)) => return Err("overlay state lock poisoned".to_string()),
+        Err(std::sync::TryLockError::WouldBlock) => {}
+    }
+    let started = std::time::Instant::now();
+    let state = shared
         .current
         .lock()
-        .map_err(|_| "overlay state lock poisoned".to_string())
+        .map_err(|_| "overlay state lock poisoned".to_string())?;
+    shared.lock_stats.record_wait(started.elapsed());
+    Ok(state)
+}
+
+fn lock_config(shared: &Arc<SharedOverlayState>) -> Result<std::sync::MutexGuard<'_, OverlayConfig>, String> {
+    shared
+        .config
+        .lock()
+        .map_err(|_| "overlay config lock poisoned".to_string())
+}
+
+fn replace_overlay_state(
+    app: &AppHandle,
+    shared: &Arc<SharedOverlayState>,
+    next: OverlayState,
+) -> Result<(), String> {
+    update_overlay_state(app, shared, |state| {
+        let sticky_fields = lock_config(shared)
+            .map(|config| config.sticky_fields.clone())
+            .unwrap_or_default();
+        replace_state(state, next, &sticky_fields);
+    })
+}
+
+/// Replaces `state` with a sanitized full-state update, keeping backend-owned and sticky fields.
+fn replace_state(state: &mut OverlayState, next: OverlayState, sticky_fields: &[sticky::StateField]) {
+    let mut next = next.sanitize();
+    next.keep_backend_fields(state);
+    sticky::retain_sticky_fields(sticky_fields, state, &mut next);
+    next.sync_processing_with_steps();
+    *state = next;
+    REQUESTED.with(|requested| requested.set(RequestedFields::ALL));
+}
+
+/// Policies applied to every state change, whatever its source, before it is emitted.
+/// `requested` names the fields the update set itself. Returns true when a
+/// muted message was dropped.
+fn enforce_state_policies(
+    app: &AppHandle,
+    shared: &Arc<SharedOverlayState>,
+    previous: &OverlayState,
+    next: &mut OverlayState,
+    requested: RequestedFields,
+) -> bool {
+    let config = match lock_config(shared) {
+        Ok(config) => config.clone(),
+        Err(_) => OverlayConfig::default(),
+    };
+    // Before the message policies, so a muted message is never tracked, held or timed.
+    let muted = shared.muted_messages.filter(previous, next);
+    meters::derive_master_level(config.master_meter, next);
+    segments::sanitize_segments(&mut next.segments, config.max_segments);
+    shared.pinned_fields.apply(next);
+    // `saver` drops the peak marker, activity and entrance straight to their targets.
+    let snap = next.power_mode.snaps_animations();
+    let (peak_hold, peak_fall) = if snap {
+        (Duration::ZERO, f64::INFINITY)
+    } else {
+        (Duration::from_millis(config.peak_hold_ms), config.peak_fall_per_sec)
+    };
+    shared.peak_meter.filter(
+        app,
+        shared,
+        next,
+        peak_hold,
+        peak_fall,
+        Duration::from_millis(config.clip_hold_ms),
+    );
+    shared.activity_meter.filter(
+        app,
+        shared,
+        next,
+        &config.activity_weights,
+        Duration::from_millis(if snap { 0 } else { config.activity_decay_ms }),
+    );
+    shared.listening_hysteresis.filter(
+        app,
+        shared,
+        previous,
+        next,
+        Duration::from_millis(config.listening_debounce_ms),
+        requested.listening,
+    );
+    shared.last_error.track(previous, next);
+    shared
+        .message_ack
+        .filter(next, Duration::from_millis(config.attention_cooldown_ms), shared.scheduler.clock().now());
+    shared.connection_behaviors.apply(
+        shared,
+        &config.connection_behaviors,
+        Duration::from_millis(config.attention_cooldown_ms),
+        previous,
+        next,
+    );
+    shared.command_feedback_clear.apply(app, shared, previous, next);
+    shared
+        .message_auto_clear
+        .apply(
+            app,
+            shared,
+            &config.severity_policy,
+            Duration::from_millis(config.attention_cooldown_ms),
+            previous,
+            next,
+        );
+    shared
+        .level_gate
+        .filter(app, shared, config.visibility_level_gate, previous, next);
+    shared
+        .visible_floor
+        .filter(app, shared, previous, next, Duration::from_millis(config.min_visible_ms), requested.visible);
+    shared
+        .visible_cap
+        .filter(app, shared, next, Duration::from_millis(config.max_visible_ms), requested.visible);
+    shared.target_gate.filter(&config.visible_only_for_targets, previous, next);
+    shared.session_lock.filter(config.lock_behavior, next);
+    shared.entrance_animation.follow_visible(
+        app,
+        shared,
+        config.show_animation,
+        Duration::from_millis(if snap { 0 } else { config.show_animation_ms }),
+        previous,
+        next,
+    );
+    shared
+        .sound_cues
+        .trigger(&config, previous, next, shared.scheduler.clock().now());
+    shared
+        .visibility_callback
+        .notify(config.visibility_callback.as_deref(), previous, next);
+    announce::announce_message(app, config.accessibility_announcements, previous, next);
+    next.context = context::context_line(&config.context_template, next);
+    shared.display_template.render(config.display_template.as_deref(), next);
+    shared.auto_expand.follow_message(app, shared, &config, next);
+    shared.status_dot.follow_visible(app, shared, &config, next);
+    shared.letterbox.follow(app, shared, config.letterbox_aware, previous, next);
+    rules::fire_transition_rules(&shared.visibility_callback, &config.transition_rules, previous, next);
+    muted
+}
+
+fn update_overlay_state(
+    app: &AppHandle,
+    shared: &Arc<SharedOverlayState>,
+    update: impl FnOnce(&mut OverlayState),
+) -> Result<(), String> {
+    let mut state = lock_state(shared)?;
+    // The policy chain, timeline and undo stack all diff against the prior state, so
+    // even a level-only update snapshots it; the level check below compares in place.
+    let previous = state.clone();
+    REQUESTED.with(|requested| requested.set(RequestedFields::default()));
+    update(&mut state);
+    let requested = REQUESTED.with(|requested| requested.take());
+    if enforce_state_policies(app, shared, &previous, &mut state, requested) && *state == previous {
+        return Ok(());
+    }
+    shared.timeline.record(&previous, &state);
+    shared.field_rates.record(&previous, &state);
+    shared.undo_stack.record(&previous, &state);
+    let levels = (state.level, state.peak_level, state.activity);
+    (state.level, state.peak_level, state.activity) = (previous.level, previous.peak_level, previous.activity);
+    let level_only = levels != (previous.level, previous.peak_level, previous.activity) && *state == previous;
+    (state.level, state.peak_level, state.activity) = levels;
+    if !(level_only && shared.level_throttle.defer(app, shared, state.power_mode)) {
+        emit_overlay_state(app, &state);
+        shared.level_throttle.record_emit(shared.scheduler.clock().now());
+    }
+    Ok(())
+}
+
+fn apply_overlay_patch(
+    app: &AppHandle,
+    shared: &Arc<SharedOverlayState>,
+    patch: OverlayPatch,
+) -> Result<(), String> {
+    update_overlay_state(app, shared, |state| patch.apply(state))
 }
 
 #[tauri::command]
@@ -111,21 +821,166 @@ fn get_overlay_state(shared: State<'_, Arc<SharedOverlayState>>) -> Result<Overl
     Ok(lock_state(shared.inner())?.clone())
 }
 
+/// One-shot push of the current state, also honored in `pull` delivery mode.
+#[tauri::command]
+fn resync_overlay(app: AppHandle, shared: State<'_, Arc<SharedOverlayState>>) -> Result<(), String> {
+    let state = lock_state(shared.inner())?;
+    push_overlay_state(&app, &state);
+    Ok(())
+}
+
 #[tauri::command]
 fn set_overlay_state(
     next: OverlayState,
     app: AppHandle,
     shared: State<'_, Arc<SharedOverlayState>>,
 ) -> Result<(), String> {
+    let _source = timeline::SourceScope::enter(timeline::ChangeSource::Command);
+    replace_overlay_state(&app, shared.inner(), next)
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum PayloadKind {
+    State,
+    Patch,
+}
+
+/// Optional `"kind"` discriminator; the rest of the payload is ignored here.
+#[derive(Debug, Deserialize)]
+struct PayloadTag {
+    #[serde(default)]
+    kind: Option<PayloadKind>,
+}
+
+fn decode_patch(payload: &str) -> Result<OverlayPatch, String> {
+    match serde_json::from_str::<OverlayPatch>(payload) {
+        Ok(patch) if patch.has_updates() => Ok(patch),
+        Ok(_) => Err("no known fields to update".to_string()),
+        Err(error) => Err(format!("invalid JSON shape: {}", error)),
+    }
+}
+
+/// Routes on `"kind"` when present; otherwise tries a full `OverlayState` first,
+/// then an `OverlayPatch`. Returns the update and why it was chosen.
+fn decode_payload(payload: &str) -> Result<(sequence::BridgeUpdate, String), String> {
+    let tag = serde_json::from_str::<PayloadTag>(payload).map_err(|error| format!("invalid JSON shape: {}", error))?;
+    match tag.kind {
+        Some(PayloadKind::State) => {
+            return serde_json::from_str::<OverlayState>(payload)
+                .map(|next| (sequence::BridgeUpdate::State(next), "tagged kind: state".to_string()))
+                .map_err(|error| format!("tagged as state but invalid: {}", error));
+        }
+        Some(PayloadKind::Patch) => {
+            return decode_patch(payload)
+                .map(|patch| (sequence::BridgeUpdate::Patch(patch), "tagged kind: patch".to_string()));
+        }
+        None => {}
+    }
+    let state_error = match serde_json::from_str::<OverlayState>(payload) {
+        Ok(next) => {
+            let reason = "all required state fields present; omitted optional fields reset to defaults";
+            return Ok((sequence::BridgeUpdate::State(next), reason.to_string()));
+        }
+        Err(error) => error,
+    };
+    decode_patch(payload).map(|patch| {
+        (
+            sequence::BridgeUpdate::Patch(patch),
+            format!("not a full state ({}); applied as a patch", state_error),
+        )
+    })
+}
+
+/// Decodes and applies one bridge datagram, recording metrics; false when it was rejected as malformed.
+fn handle_datagram(app: &AppHandle, shared: &Arc<SharedOverlayState>, transport: ingest::Transport, datagram: &[u8]) -> bool {
+    let _source = timeline::SourceScope::enter(timeline::ChangeSource::Udp);
+    shared.metrics.record_datagram(shared.scheduler.clock().unix_millis());
+    let payload = match std::str::from_utf8(datagram) {
+        Ok(text) => text,
+        Err(error) => {
+            shared.metrics.record_rejected();
+            shared
+                .metrics
+                .record_parse(transport, datagram, metrics::ParseShape::Rejected, format!("invalid UTF-8: {}", error));
+            logsample::sampled_warn!(shared, "invalid_utf8", "invalid UTF-8 UDP payload: {}", error);
+            return false;
+        }
+    };
+    if lock_config(shared).is_ok_and(|config| shared.session_lock.pauses_bridge(config.lock_behavior)) {
+        log::debug!("dropped bridge payload while the session is locked");
+        return true;
+    }
+    if lock_config(shared).is_ok_and(|config| config.strict_fields) {
+        if let Some(key) = ingest::unknown_payload_field(payload) {
+            let reason = format!("unknown field {:?}", key);
+            shared.metrics.record_rejected();
+            logsample::sampled_warn!(shared, "unknown_field", "ignored UDP payload ({}): {}", reason, payload);
+            shared.metrics.record_parse(transport, datagram, metrics::ParseShape::Rejected, reason);
+            errors::emit_error_event(app, shared, serde_json::json!({ "error": "unknown_field", "key": key }));
+            return false;
+        }
+    }
+    let fast_level = lock_config(shared)
+        .is_ok_and(|config| config.fast_level_parse)
+        .then(|| fastlevel::parse_level_only(payload))
+        .flatten();
+    let decoded = match fast_level {
+        Some(level) => Ok((
+            sequence::BridgeUpdate::Patch(OverlayPatch {
+                level: Some(level),
+                ..OverlayPatch::default()
+            }),
+            fastlevel::FAST_LEVEL_REASON.to_string(),
+        )),
+        None => decode_payload(payload),
+    };
+    let update = match decoded {
+        Ok((update, reason)) => {
+            let shape = match &update {
+                sequence::BridgeUpdate::State(_) => metrics::ParseShape::State,
+                sequence::BridgeUpdate::Patch(_) => metrics::ParseShape::Patch,
+            };
+            shared.metrics.record_parse(transport, datagram, shape, reason);
+            update
+        }
+        Err(reason) => {
+            shared.metrics.record_rejected();
+            logsample::sampled_warn!(shared, "invalid_payload", "ignored UDP payload ({}): {}", reason, payload);
+            shared.metrics.record_parse(transport, datagram, metrics::ParseShape::Rejected, reason);
+            return false;
+        }
+    };
+    let (priority, ownership) = match lock_config(shared) {
+        Ok(config) => (
+            config.source_priority.clone(),
+            Duration::from_millis(config.source_ownership_ms),
+        ),
+        Err(_) => (Vec::new(), Duration::ZERO),
+    };
+    if !shared
+        .source_arbiter
+        .admit(transport, &priority, ownership, shared.scheduler.clock().now())
     {
-        let mut state = lock_state(shared.inner())?;
-        *state = OverlayState {
-            level: next.level.clamp(0.0, 1.0),
-            ..next
-        };
-        emit_overlay_state(&app, &state);
+        log::debug!("ignored {:?} update while a higher-priority source owns the overlay", transport);
+        return true;
     }
-    Ok(())
+    match &update {
+        sequence::BridgeUpdate::State(_) => shared.metrics.record_state(),
+        sequence::BridgeUpdate::Patch(patch) => {
+            if patch.is_level_only() && shared.level_ingest.skip(shared.scheduler.clock().now()) {
+                shared.metrics.record_throttled();
+                return true;
+            }
+            shared.metrics.record_patch();
+        }
+    }
+    shared.splash.end_for_update(shared);
+    let conflict = sequence::sequence_conflict(shared);
+    if let Some(update) = shared.sequencer.intercept(conflict, update) {
+        let _ = update.apply(app, shared);
+    }
+    true
 }
 
 fn start_udp_bridge(app: AppHandle, shared: Arc<SharedOverlayState>) {
@@ -137,40 +992,52 @@ fn start_udp_bridge(app: AppHandle, shared: Arc<SharedOverlayState>) {
                 return;
             }
         };
-        let _ = socket.set_read_timeout(Some(Duration::from_millis(250)));
+        let poll_ms = lock_config(&shared)
+            .map(|config| config.bridge_poll_ms)
+            .unwrap_or(250)
+            .clamp(MIN_BRIDGE_POLL_MS, MAX_BRIDGE_POLL_MS);
+        let _ = socket.set_read_timeout(Some(Duration::from_millis(poll_ms)));
+        shared.metrics.set_bound(true);
+        shared.startup_timings.record(startup::StartupMilestone::BridgeBound);
+        shared.visibility_callback.attach(&socket);
         log::info!("overlay UDP bridge listening on {}", UDP_ADDR);
 
         let mut buffer = [0_u8; 8192];
         loop {
             match socket.recv_from(&mut buffer) {
-                Ok((count, _)) => {
-                    let payload = match std::str::from_utf8(&buffer[..count]) {
-                        Ok(text) => text,
-                        Err(error) => {
-                            log::warn!("invalid UTF-8 UDP payload: {}", error);
+                Ok((count, sender)) if &buffer[..count] == UDP_PING => {
+                    let health = shared.metrics.health(shared.scheduler.clock().unix_millis());
+                    let pong = serde_json::json!({
+                        "event": "pong",
+                        "uptime_ms": health.uptime_ms,
+                        "bridge_bound": health.bridge_bound,
+                        "last_datagram_ago_ms": health.last_datagram_ago_ms,
+                    });
+                    let _ = socket.send_to(pong.to_string().as_bytes(), sender);
+                }
+                Ok((_, sender)) if shared.source_quarantine.is_quarantined(sender, shared.scheduler.clock().now()) => continue,
+                Ok((count, sender)) => {
+                    shared.visibility_callback.record_sender(sender);
+                    #[cfg(debug_assertions)]
+                    match shared.fault_injection.decide() {
+                        faults::FaultAction::Deliver => {}
+                        faults::FaultAction::Drop => continue,
+                        faults::FaultAction::Delay(delay) => {
+                            let (app, shared, datagram) = (app.clone(), shared.clone(), buffer[..count].to_vec());
+                            thread::spawn(move || {
+                                thread::sleep(delay);
+                                let _ = handle_datagram(&app, &shared, ingest::Transport::Udp, &datagram);
+                            });
                             continue;
                         }
-                    };
-                    if let Ok(next) = serde_json::from_str::<OverlayState>(payload) {
-                        if let Ok(mut state) = lock_state(&shared) {
-                            *state = OverlayState {
-                                level: next.level.clamp(0.0, 1.0),
-                                ..next
-                            };
-                            emit_overlay_state(&app, &state);
-                        }
-                        continue;
                     }
-                    if let Ok(patch) = serde_json::from_str::<OverlayPatch>(payload) {
-                        if patch.has_updates() {
-                            if let Ok(mut state) = lock_state(&shared) {
-                                patch.apply(&mut state);
-                                emit_overlay_state(&app, &state);
-                            }
-                            continue;
+                    if !handle_datagram(&app, &shared, ingest::Transport::Udp, &buffer[..count]) {
+                        if let Ok(policy) = lock_config(&shared).map(|config| config.quarantine_policy) {
+                            shared
+                                .source_quarantine
+                                .record_failure(sender, &policy, shared.scheduler.clock().now());
                         }
                     }
-                    log::warn!("ignored UDP payload (invalid JSON shape): {}", payload);
                 }
                 Err(error)
                     if error.kind() == std::io::ErrorKind::WouldBlock
@@ -179,6 +1046,7 @@ fn start_udp_bridge(app: AppHandle, shared: Arc<SharedOverlayState>) {
                     continue;
                 }
                 Err(error) => {
+                    shared.metrics.set_bound(false);
                     log::error!("overlay UDP bridge stopped: {}", error);
                     break;
                 }
@@ -187,51 +1055,413 @@ fn start_udp_bridge(app: AppHandle, shared: Arc<SharedOverlayState>) {
     });
 }
 
-fn position_overlay_window(window: &WebviewWindow) -> tauri::Result<()> {
-    let monitor = match window.current_monitor()? {
-        Some(current) => Some(current),
-        None => window.primary_monitor()?,
-    };
-    if let Some(monitor) = monitor {
-        let monitor_size = monitor.size();
-        let monitor_pos = monitor.position();
-        let window_size = window.outer_size()?;
-        let margin = (TASKBAR_MARGIN_PX as f64 * monitor.scale_factor()) as i32;
-        let x = monitor_pos.x + ((monitor_size.width as i32 - window_size.width as i32) / 2).max(0);
-        let y = monitor_pos.y + (monitor_size.height as i32 - window_size.height as i32 - margin).max(0);
-        window.set_position(Position::Physical(PhysicalPosition::new(x, y)))?;
+/// Changes how long each bridge read blocks, i.e. how often the loop wakes without traffic.
+#[tauri::command]
+fn set_bridge_poll_interval(
+    ms: u64,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if ms < MIN_BRIDGE_POLL_MS {
+        return Err(format!(
+            "bridge poll interval must be at least {} ms; shorter polls wake the bridge thread more often and cost CPU",
+            MIN_BRIDGE_POLL_MS
+        ));
     }
+    if ms > MAX_BRIDGE_POLL_MS {
+        return Err(format!(
+            "bridge poll interval must be at most {} ms; longer polls delay everything the loop checks between datagrams",
+            MAX_BRIDGE_POLL_MS
+        ));
+    }
+    shared.visibility_callback.set_read_timeout(Duration::from_millis(ms))?;
+    config::update_config(&app, shared.inner(), |config| config.bridge_poll_ms = ms)?;
     Ok(())
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+struct MonitorInfo {
+    name: Option<String>,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    scale_factor: f64,
+}
+
+impl From<&tauri::Monitor> for MonitorInfo {
+    fn from(monitor: &tauri::Monitor) -> Self {
+        Self {
+            name: monitor.name().cloned(),
+            x: monitor.position().x,
+            y: monitor.position().y,
+            width: monitor.size().width,
+            height: monitor.size().height,
+            scale_factor: monitor.scale_factor(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+struct OverlayRect {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    scale_factor: f64,
+    monitor: Option<MonitorInfo>,
+}
+
+fn main_window(app: &AppHandle) -> Result<WebviewWindow, String> {
+    app.get_webview_window("main")
+        .ok_or_else(|| "overlay window not found".to_string())
+}
+
+/// Physical on-screen bounds of the overlay, for companion tools aligning to it.
+#[tauri::command]
+fn get_overlay_rect(app: AppHandle) -> Result<OverlayRect, String> {
+    let window = main_window(&app)?;
+    let position = window.outer_position().map_err(|error| error.to_string())?;
+    let size = window.outer_size().map_err(|error| error.to_string())?;
+    let monitor = window.current_monitor().map_err(|error| error.to_string())?;
+    Ok(OverlayRect {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+        scale_factor: window.scale_factor().map_err(|error| error.to_string())?,
+        monitor: monitor.as_ref().map(MonitorInfo::from),
+    })
+}
+
+fn current_or_primary_monitor(window: &WebviewWindow) -> tauri::Result<Option<Monitor>> {
+    match window.current_monitor()? {
+        Some(current) => Ok(Some(current)),
+        None => window.primary_monitor(),
+    }
+}
+
+/// Where the form, dock or default anchor puts the window, before the safe rect clamp.
+fn anchored_position(window: &WebviewWindow, shared: &Arc<SharedOverlayState>) -> tauri::Result<Option<PhysicalPosition<i32>>> {
+    let form = lock_config(shared)
+        .map(|config| config.display_form)
+        .unwrap_or_default();
+    if let Some(position) = statusdot::dot_position(window, shared, window.outer_size()?)? {
+        return Ok(Some(position));
+    }
+    if form == layout::DisplayForm::Banner {
+        return Ok(current_or_primary_monitor(window)?.map(|monitor| *monitor.position()));
+    }
+    if let Some(position) = letterbox::letterbox_position(window, window.outer_size()?) {
+        return Ok(Some(position));
+    }
+    if let Some(position) = dock::docked_position(window, window.outer_size()?) {
+        return Ok(Some(position));
+    }
+    let Some(monitor) = current_or_primary_monitor(window)? else {
+        return Ok(None);
+    };
+    let scale_factor = dpi::effective_scale_factor(shared, monitor.scale_factor());
+    Ok(Some(dpi::bottom_center_position(
+        *monitor.position(),
+        *monitor.size(),
+        window.outer_size()?,
+        scale_factor,
+    )))
+}
+
+fn position_overlay_window(window: &WebviewWindow, trigger: position::PositionTrigger) -> tauri::Result<()> {
+    let shared = window.state::<Arc<SharedOverlayState>>();
+    let Some(mut position) = anchored_position(window, shared.inner())? else {
+        return Ok(());
+    };
+    if let Some(rect) = lock_config(shared.inner()).ok().and_then(|config| config.safe_rect) {
+        position = rect.clamp(position, window.outer_size()?);
+    }
+    position::set_overlay_position(window, position, trigger)
+}
+
+fn is_on_any_monitor(window: &WebviewWindow) -> tauri::Result<bool> {
+    let position = window.outer_position()?;
+    let size = window.outer_size()?;
+    let center_x = position.x + size.width as i32 / 2;
+    let center_y = position.y + size.height as i32 / 2;
+    Ok(window.available_monitors()?.iter().any(|monitor| {
+        let origin = monitor.position();
+        let extent = monitor.size();
+        center_x >= origin.x
+            && center_x < origin.x + extent.width as i32
+            && center_y >= origin.y
+            && center_y < origin.y + extent.height as i32
+    }))
+}
+
+/// Re-anchors the overlay when monitor hot-plugging leaves it off every screen.
+fn start_monitor_self_check(window: WebviewWindow) {
+    thread::spawn(move || loop {
+        thread::sleep(MONITOR_CHECK_INTERVAL);
+        match is_on_any_monitor(&window) {
+            Ok(true) => {}
+            Ok(false) => {
+                log::info!("overlay window is off-screen; repositioning");
+                reposition::request_reposition(&window, position::PositionTrigger::SelfCheck, false);
+            }
+            Err(error) => log::warn!("monitor self-check failed: {}", error),
+        }
+    });
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    let shared = Arc::new(SharedOverlayState::default());
+    let startup = cli::parse_startup_args(std::env::args().skip(1));
+    let Some(_instance) = instance::acquire_instance_guard() else {
+        instance::forward_to_running_instance(&startup);
+        return;
+    };
+    #[cfg(debug_assertions)]
+    let shared = controlsock::with_control_clock(SharedOverlayState::default());
+    #[cfg(not(debug_assertions))]
+    let shared = SharedOverlayState::default();
+    let shared = Arc::new(shared);
+    if let Ok(mut state) = lock_state(&shared) {
+        startup.patch.apply(&mut state);
+    }
     let state_for_setup = shared.clone();
+    shared.startup_timings.record(startup::StartupMilestone::BuilderStart);
 
     tauri::Builder::default()
         .manage(shared)
-        .invoke_handler(tauri::generate_handler![get_overlay_state, set_overlay_state])
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
+        .invoke_handler(tauri::generate_handler![
+            get_overlay_state,
+            set_overlay_state,
+            set_bridge_poll_interval,
+            resync_overlay,
+            activity::set_activity_weights,
+            announce::set_accessibility_announcements,
+            bootstrap::apply_bootstrap,
+            bootstrap::diff_config,
+            callback::set_visibility_callback,
+            config::set_delivery_mode,
+            config::repair_persisted_state,
+            delta::set_delta_emit,
+            context::get_context_line,
+            context::set_context_template,
+            template::set_display_template,
+            #[cfg(debug_assertions)]
+            cycle::cycle_targets,
+            #[cfg(debug_assertions)]
+            cycle::stop_cycle_targets,
+            diagnostics::get_runtime_diagnostics,
+            startup::get_startup_timings,
+            scheduler::get_scheduler_stats,
+            dock::dock_to_window,
+            dock::undock_window,
+            letterbox::set_letterbox_aware,
+            ready::start_ready_countdown,
+            ready::cancel_ready_countdown,
+            segments::apply_segment_patch,
+            segments::set_max_segments,
+            lockstats::get_lock_stats,
+            dpi::set_follow_dpi,
+            reposition::set_reposition_throttle,
+            fastlevel::set_fast_level_parse,
+            saferect::set_safe_rect,
+            gamemode::game_overlay_mode,
+            focus::get_focus_flags,
+            focus::set_no_activate,
+            display::get_display_context,
+            projection::set_emit_projection,
+            projection::set_private_fields,
+            projection::get_fields,
+            #[cfg(debug_assertions)]
+            dpi::set_scale_override,
+            effects::set_background_effect,
+            errors::set_error_event_limits,
+            #[cfg(feature = "eventlog")]
+            eventlog::set_event_log,
+            expand::set_auto_expand,
+            statusdot::set_status_dot,
+            #[cfg(debug_assertions)]
+            faults::set_fault_injection,
+            #[cfg(debug_assertions)]
+            faults::clear_fault_injection,
+            hotkey::register_toggle_hotkey,
+            hotkey::unregister_toggle_hotkey,
+            get_overlay_rect,
+            hysteresis::set_listening_debounce,
+            ingest::set_max_level_hz,
+            ingest::set_strict_fields,
+            ingest::set_source_priority,
+            ingest::get_last_update_source,
+            ingest::preview_apply,
+            quarantine::set_quarantine_policy,
+            quarantine::list_quarantined_sources,
+            quarantine::release_source,
+            #[cfg(feature = "unixsocket")]
+            rpc::get_rpc_contract,
+            interactive::enter_interactive_mode,
+            interactive::dismiss_overlay,
+            batch::apply_commands,
+            ack::acknowledge_message,
+            ack::set_attention_cooldown,
+            ack::get_attention_cooldown,
+            connection::set_connection_behaviors,
+            layout::set_display_form,
+            entrance::set_show_animation,
+            layout::set_overlay_geometry,
+            layout::set_text_scale,
+            layout::validate_positioning_config,
+            logtap::get_recent_logs,
+            logtap::set_log_streaming,
+            metrics::get_bridge_metrics,
+            metrics::get_last_parse,
+            metrics::get_last_payloads,
+            fieldrates::get_field_rates,
+            logsample::set_log_sampling,
+            mute::mute_message_hash,
+            mute::unmute_message_hash,
+            mute::list_muted_messages,
+            #[cfg(feature = "obs")]
+            obs::start_obs_mirror,
+            #[cfg(feature = "obs")]
+            obs::stop_obs_mirror,
+            peak::set_peak_hold,
+            peak::set_clip_hold,
+            pins::pin_field,
+            placement::save_placement,
+            position::get_position_history,
+            placement::restore_placement,
+            pins::unpin_field,
+            pins::list_pinned_fields,
+            power::set_power_mode,
+            gpu::set_render_adapter,
+            presets::save_preset,
+            presets::apply_preset,
+            presets::list_presets,
+            presets::delete_preset,
+            render::report_frames,
+            render::get_render_stats,
+            safety::set_max_visible_duration,
+            safety::set_min_visible_duration,
+            safety::get_visible_time_remaining,
+            rules::set_transition_rules,
+            severity::set_severity_policy,
+            severity::get_last_error,
+            severity::show_last_error,
+            sequence::play_sequence,
+            sequence::cancel_sequence,
+            sequence::set_sequence_conflict,
+            session::set_lock_behavior,
+            targets::set_visible_only_for_targets,
+            levelgate::set_visibility_level_gate,
+            levelhistory::set_level_history,
+            levelhistory::get_level_history,
+            meters::set_master_meter_mode,
+            heartbeat::set_heartbeat_timeout,
+            heartbeat::set_connection_grace,
+            snapshot::get_full_snapshot,
+            sound::set_sound_enabled,
+            sound::list_sound_cues,
+            stateline::get_state_line,
+            stateline::set_state_line_logging,
+            sticky::set_sticky_fields,
+            stream::stream_message,
+            split::set_split_view,
+            splash::set_splash_state,
+            style::get_style_contract,
+            themepkg::validate_theme,
+            themepkg::install_theme,
+            vectors::generate_test_vectors,
+            theme::get_system_theme,
+            timeline::get_transition_timeline,
+            undo::undo_overlay,
+            undo::redo_overlay,
+            #[cfg(feature = "recording")]
+            recording::record_overlay
+        ])
         .setup(move |app| {
-            if cfg!(debug_assertions) {
-                app.handle().plugin(
-                    tauri_plugin_log::Builder::default()
-                        .level(log::LevelFilter::Info)
-                        .build(),
-                )?;
+            state_for_setup.startup_timings.record(startup::StartupMilestone::SetupStart);
+            let log_tap = logtap::LogTap::new(app.handle().clone());
+            let mut logger = tauri_plugin_log::Builder::default().level(log::LevelFilter::Info);
+            if !cfg!(debug_assertions) {
+                // Release builds keep only the in-memory tap, no stdout/file output.
+                logger = logger.clear_targets();
+            }
+            #[cfg(feature = "eventlog")]
+            let event_log = Arc::new(eventlog::EventLog::default());
+            #[cfg(feature = "eventlog")]
+            {
+                logger = logger.target(eventlog::log_target(event_log.clone()));
+                app.manage(event_log.clone());
             }
+            app.handle()
+                .plugin(logger.target(logtap::log_target(log_tap.clone())).build())?;
+            app.manage(log_tap);
+            for arg in &startup.ignored {
+                log::warn!("ignored unknown launch argument: {}", arg);
+            }
+
+            let config = config::load_config(app.handle());
+            if let Ok(mut state) = lock_state(&state_for_setup) {
+                state.text_scale = layout::clamp_text_scale(config.text_scale);
+                state.corner_radius = config.geometry.corner_radius;
+                state.display_form = config.display_form;
+                state.power_mode = config.power_mode.resolve();
+                state.system_theme = theme::read_system_theme();
+            }
+            state_for_setup.level_ingest.set_max_hz(config.max_level_hz);
+            #[cfg(feature = "eventlog")]
+            event_log.configure(&config);
+            if let Ok(mut current) = lock_config(&state_for_setup) {
+                *current = config.clone();
+            }
+            hotkey::restore_toggle_hotkey(app.handle(), &state_for_setup);
+            state_for_setup.presets.load(app.handle());
 
             if let Some(window) = app.get_webview_window("main") {
+                state_for_setup.startup_timings.record(startup::StartupMilestone::WindowAcquired);
                 let _ = window.set_ignore_cursor_events(true);
-                let _ = position_overlay_window(&window);
+                let _ = layout::apply_overlay_layout(&window, &config, position::PositionTrigger::Initial);
+                state_for_setup.startup_timings.record(startup::StartupMilestone::Positioned);
+                let _ = effects::apply_background_effect(&window, config.background_effect);
+                if let Err(error) = focus::apply_no_activate(&window, config.no_activate) {
+                    log::warn!("failed to apply no-activate: {}", error);
+                }
+                gamemode::restore_game_overlay_mode(&window, &state_for_setup, config.game_overlay_mode);
+                if let (Ok(scale_factor), Ok(mut state)) = (window.scale_factor(), lock_state(&state_for_setup)) {
+                    state.scale_factor = dpi::effective_scale_factor(&state_for_setup, scale_factor);
+                }
+                dpi::start_dpi_watch(&window, app.handle().clone(), state_for_setup.clone());
+                theme::start_theme_watch(&window, app.handle().clone(), state_for_setup.clone());
+                gpu::start_adapter_watch(window.clone(), app.handle().clone(), state_for_setup.clone(), config.render_adapter);
+                start_monitor_self_check(window);
             }
 
+            state_for_setup.splash.show(app.handle(), &state_for_setup, &config);
             if let Ok(initial) = lock_state(&state_for_setup) {
                 let handle = app.handle().clone();
                 emit_overlay_state(&handle, &initial);
             }
 
+            scheduler::start_scheduler(state_for_setup.clone());
+            state_for_setup.level_history.start(&state_for_setup);
+            #[cfg(debug_assertions)]
+            controlsock::start_control_socket(app.handle().clone(), state_for_setup.clone());
+            power::start_power_source_watch(app.handle().clone(), state_for_setup.clone());
+            session::start_session_lock_watch(app.handle().clone(), state_for_setup.clone());
+            heartbeat::start_connection_watch(app.handle().clone(), state_for_setup.clone());
             start_udp_bridge(app.handle().clone(), state_for_setup.clone());
+            #[cfg(feature = "http")]
+            http::start_http_mirror(state_for_setup.clone());
+            #[cfg(feature = "unixsocket")]
+            unixsock::start_unix_socket_bridge(app.handle().clone(), state_for_setup.clone());
+            #[cfg(feature = "file_source")]
+            filesource::start_file_source(app.handle().clone(), state_for_setup.clone());
+            #[cfg(feature = "osc")]
+            osc::start_osc_bridge(app.handle().clone(), state_for_setup.clone());
             Ok(())
         })
         .run(tauri::generate_context!())
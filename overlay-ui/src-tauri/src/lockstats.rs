@@ -0,0 +1,98 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::SharedOverlayState;
+
+/// Bucket 0 counts uncontended acquisitions; bucket `i > 0` waits under `2^(i-1)` µs
+/// that missed the bucket before it.
+const WAIT_BUCKETS: usize = 24;
+
+/// Histogram of how long `lock_state` callers waited for the state lock,
+/// kept in atomics so recording takes no lock of its own.
+pub(crate) struct LockStats {
+    buckets: [AtomicU64; WAIT_BUCKETS],
+    max_wait_us: AtomicU64,
+}
+
+impl Default for LockStats {
+    fn default() -> Self {
+        Self {
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            max_wait_us: AtomicU64::new(0),
+        }
+    }
+}
+
+impl LockStats {
+    pub(crate) fn record_uncontended(&self) {
+        self.buckets[0].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_wait(&self, wait: Duration) {
+        let micros = wait.as_micros().min(u128::from(u64::MAX)) as u64;
+        let bucket = (u64::BITS - micros.leading_zeros()) as usize + 1;
+        self.buckets[bucket.min(WAIT_BUCKETS - 1)].fetch_add(1, Ordering::Relaxed);
+        self.max_wait_us.fetch_max(micros, Ordering::Relaxed);
+    }
+
+    fn reset(&self) {
+        for bucket in &self.buckets {
+            bucket.store(0, Ordering::Relaxed);
+        }
+        self.max_wait_us.store(0, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> LockStatsSnapshot {
+        let counts: Vec<u64> = self.buckets.iter().map(|bucket| bucket.load(Ordering::Relaxed)).collect();
+        let acquisitions: u64 = counts.iter().sum();
+        // Upper bound, in µs, of the bucket holding the given fraction of acquisitions.
+        let percentile = |fraction: f64| -> u64 {
+            let rank = (acquisitions as f64 * fraction).ceil() as u64;
+            let mut seen = 0;
+            for (index, count) in counts.iter().enumerate() {
+                seen += count;
+                if seen >= rank.max(1) {
+                    return if index == 0 { 0 } else { 1 << (index - 1) };
+                }
+            }
+            0
+        };
+        LockStatsSnapshot {
+            acquisitions,
+            contended: acquisitions - counts[0],
+            p50_wait_us: percentile(0.5),
+            p95_wait_us: percentile(0.95),
+            max_wait_us: self.max_wait_us.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct LockStatsSnapshot {
+    acquisitions: u64,
+    /// Acquisitions that found the lock held and had to wait.
+    contended: u64,
+    /// Wait percentiles, rounded up to a power of two µs; 0 while mostly uncontended.
+    p50_wait_us: u64,
+    p95_wait_us: u64,
+    max_wait_us: u64,
+}
+
+/// State lock wait times since startup or the last reset.
+#[tauri::command]
+pub(crate) fn get_lock_stats(reset: Option<bool>, shared: State<'_, Arc<SharedOverlayState>>) -> LockStatsSnapshot {
+    let snapshot = shared.lock_stats.snapshot();
+    if reset.unwrap_or(false) {
+        shared.lock_stats.reset();
+    }
+    snapshot
+}
@@ -0,0 +1,111 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use crate::{config::update_config, lock_config, SharedOverlayState};
+
+/// After `burst` warnings of one kind within `window_ms`, only every
+/// `one_in`-th is logged until the window ends. `burst: 0` logs everything.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default, rename_all = "snake_case")]
+pub(crate) struct LogSamplingPolicy {
+    burst: u32,
+    window_ms: u64,
+    one_in: u32,
+}
+
+impl Default for LogSamplingPolicy {
+    fn default() -> Self {
+        Self {
+            burst: 10,
+            window_ms: 10_000,
+            one_in: 100,
+        }
+    }
+}
+
+struct SampleWindow {
+    started: Instant,
+    seen: u64,
+    suppressed: u64,
+}
+
+/// Sampling state for the ingestion warnings, keyed by kind rather than text
+/// since the text carries the offending payload.
+#[derive(Default)]
+pub(crate) struct LogSampler {
+    kinds: Mutex<HashMap<&'static str, SampleWindow>>,
+}
+
+impl LogSampler {
+    /// Whether to log this `kind` warning now, and how many were suppressed
+    /// since the last one logged.
+    pub(crate) fn admit(&self, shared: &Arc<SharedOverlayState>, kind: &'static str) -> Option<u64> {
+        let policy = lock_config(shared).map(|config| config.log_sampling).unwrap_or_default();
+        if policy.burst == 0 {
+            return Some(0);
+        }
+        let mut kinds = self.kinds.lock().ok()?;
+        let window = kinds.entry(kind).or_insert_with(|| SampleWindow {
+            started: Instant::now(),
+            seen: 0,
+            suppressed: 0,
+        });
+        if window.started.elapsed() >= Duration::from_millis(policy.window_ms) {
+            if window.suppressed > 0 {
+                log::warn!(
+                    "suppressed {} {} warnings in the last {} ms",
+                    window.suppressed,
+                    kind,
+                    window.started.elapsed().as_millis()
+                );
+            }
+            *window = SampleWindow {
+                started: Instant::now(),
+                seen: 0,
+                suppressed: 0,
+            };
+        }
+        window.seen += 1;
+        let over_burst = window.seen - u64::from(policy.burst);
+        if window.seen <= u64::from(policy.burst) || over_burst % u64::from(policy.one_in.max(1)) == 0 {
+            return Some(std::mem::take(&mut window.suppressed));
+        }
+        window.suppressed += 1;
+        None
+    }
+}
+
+/// `log::warn!` through the sampler, noting how many were skipped before it.
+macro_rules! sampled_warn {
+    ($shared:expr, $kind:expr, $($arg:tt)+) => {
+        let shared: &std::sync::Arc<$crate::SharedOverlayState> = $shared;
+        if let Some(suppressed) = shared.log_sampler.admit(shared, $kind) {
+            if suppressed > 0 {
+                log::warn!("{} ({} similar suppressed)", format_args!($($arg)+), suppressed);
+            } else {
+                log::warn!($($arg)+);
+            }
+        }
+    };
+}
+
+pub(crate) use sampled_warn;
+
+#[tauri::command]
+pub(crate) fn set_log_sampling(
+    policy: LogSamplingPolicy,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if policy.burst > 0 && policy.window_ms == 0 {
+        return Err("log sampling window must be positive".to_string());
+    }
+    update_config(&app, shared.inner(), |config| config.log_sampling = policy)?;
+    Ok(())
+}
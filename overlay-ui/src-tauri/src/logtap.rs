@@ -0,0 +1,118 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, State};
+use tauri_plugin_log::{fern, Target, TargetKind};
+
+use crate::unix_millis;
+
+const LOG_BUFFER_CAPACITY: usize = 500;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct LogRecord {
+    level: String,
+    message: String,
+    timestamp: u64,
+}
+
+/// Lowest record level forwarded to the Windows event log (`eventlog` feature).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum EventLogLevel {
+    Off,
+    #[default]
+    Warn,
+    Error,
+}
+
+impl EventLogLevel {
+    #[cfg_attr(not(feature = "eventlog"), allow(dead_code))]
+    pub(crate) fn admits(self, level: log::Level) -> bool {
+        match self {
+            EventLogLevel::Off => false,
+            EventLogLevel::Warn => level <= log::Level::Warn,
+            EventLogLevel::Error => level == log::Level::Error,
+        }
+    }
+}
+
+/// In-memory tap on the log pipeline, kept for in-app troubleshooting.
+pub(crate) struct LogTap {
+    app: AppHandle,
+    records: Mutex<VecDeque<LogRecord>>,
+    streaming: AtomicBool,
+}
+
+impl LogTap {
+    pub(crate) fn len(&self) -> usize {
+        self.records.lock().map(|records| records.len()).unwrap_or(0)
+    }
+
+    pub(crate) fn new(app: AppHandle) -> Arc<Self> {
+        Arc::new(Self {
+            app,
+            records: Mutex::new(VecDeque::with_capacity(LOG_BUFFER_CAPACITY)),
+            streaming: AtomicBool::new(false),
+        })
+    }
+
+    fn push(&self, record: LogRecord) {
+        if let Ok(mut records) = self.records.lock() {
+            if records.len() == LOG_BUFFER_CAPACITY {
+                records.pop_front();
+            }
+            records.push_back(record.clone());
+        }
+        if self.streaming.load(Ordering::Relaxed) {
+            let _ = self.app.emit("overlay://log", &record);
+        }
+    }
+}
+
+struct LogTapSink(Arc<LogTap>);
+
+impl log::Log for LogTapSink {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        self.0.push(LogRecord {
+            level: record.level().to_string().to_lowercase(),
+            message: record.args().to_string(),
+            timestamp: unix_millis(),
+        });
+    }
+
+    fn flush(&self) {}
+}
+
+/// Log plugin target feeding `tap`, with the raw message rather than the plugin's prefixed format.
+pub(crate) fn log_target(tap: Arc<LogTap>) -> Target {
+    let sink: Box<dyn log::Log> = Box::new(LogTapSink(tap));
+    Target::new(TargetKind::Dispatch(fern::Dispatch::new().chain(sink)))
+        .format(|out, message, _record| out.finish(format_args!("{}", message)))
+}
+
+#[tauri::command]
+pub(crate) fn get_recent_logs(count: usize, tap: State<'_, Arc<LogTap>>) -> Result<Vec<LogRecord>, String> {
+    let records = tap
+        .records
+        .lock()
+        .map_err(|_| "log buffer lock poisoned".to_string())?;
+    let skip = records.len().saturating_sub(count);
+    Ok(records.iter().skip(skip).cloned().collect())
+}
+
+/// Enables `overlay://log` events for each new record, e.g. while a debug panel is open.
+#[tauri::command]
+pub(crate) fn set_log_streaming(enabled: bool, tap: State<'_, Arc<LogTap>>) {
+    tap.streaming.store(enabled, Ordering::Relaxed);
+}
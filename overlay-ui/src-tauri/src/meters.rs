@@ -0,0 +1,52 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use crate::{config::update_config, update_overlay_state, OverlayState, SharedOverlayState};
+
+/// How the named `meters` fold into the single `level` older frontends show.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum MasterMeterMode {
+    #[default]
+    Max,
+    Mean,
+    SumClamped,
+}
+
+impl MasterMeterMode {
+    fn combine(self, meters: &BTreeMap<String, f64>) -> f64 {
+        let values = meters.values().copied();
+        let level = match self {
+            MasterMeterMode::Max => values.fold(0.0, f64::max),
+            MasterMeterMode::Mean => values.sum::<f64>() / meters.len() as f64,
+            MasterMeterMode::SumClamped => values.sum(),
+        };
+        level.clamp(0.0, 1.0)
+    }
+}
+
+pub(crate) fn sanitize_meters(meters: &mut BTreeMap<String, f64>) {
+    meters.retain(|_, level| level.is_finite());
+    for level in meters.values_mut() {
+        *level = level.clamp(0.0, 1.0);
+    }
+}
+
+/// Derives `level` from `meters` whenever any are set.
+pub(crate) fn derive_master_level(mode: MasterMeterMode, next: &mut OverlayState) {
+    if !next.meters.is_empty() {
+        next.level = mode.combine(&next.meters);
+    }
+}
+
+#[tauri::command]
+pub(crate) fn set_master_meter_mode(
+    mode: MasterMeterMode,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    update_config(&app, shared.inner(), |config| config.master_meter = mode)?;
+    update_overlay_state(&app, shared.inner(), |_| {})
+}
@@ -0,0 +1,206 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
+};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::{ingest::Transport, unix_millis, SharedOverlayState};
+
+/// Longest raw payload kept per transport.
+const MAX_KEPT_PAYLOAD_BYTES: usize = 2_048;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ParseShape {
+    State,
+    Patch,
+    Rejected,
+}
+
+/// How the bridge interpreted a datagram, since a full state resets omitted fields and a patch doesn't.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct ParseDecision {
+    shape: ParseShape,
+    reason: String,
+    at: u64,
+}
+
+/// The raw payload a transport last delivered, with how it was parsed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct LastPayload {
+    /// Lossy UTF-8, cut at `MAX_KEPT_PAYLOAD_BYTES`.
+    payload: String,
+    truncated: bool,
+    parse: ParseDecision,
+}
+
+/// Lock-free counters for the UDP bridge, plus the last parse decision.
+pub(crate) struct BridgeMetrics {
+    started: Instant,
+    bound: AtomicBool,
+    datagrams: AtomicU64,
+    state_updates: AtomicU64,
+    patch_updates: AtomicU64,
+    rejected: AtomicU64,
+    throttled: AtomicU64,
+    last_datagram_at: AtomicU64,
+    last_parse: Mutex<Option<ParseDecision>>,
+    last_payloads: Mutex<BTreeMap<Transport, LastPayload>>,
+}
+
+impl Default for BridgeMetrics {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            bound: AtomicBool::new(false),
+            datagrams: AtomicU64::new(0),
+            state_updates: AtomicU64::new(0),
+            patch_updates: AtomicU64::new(0),
+            rejected: AtomicU64::new(0),
+            throttled: AtomicU64::new(0),
+            last_datagram_at: AtomicU64::new(0),
+            last_parse: Mutex::new(None),
+            last_payloads: Mutex::new(BTreeMap::new()),
+        }
+    }
+}
+
+impl BridgeMetrics {
+    pub(crate) fn set_bound(&self, bound: bool) {
+        self.bound.store(bound, Ordering::Relaxed);
+    }
+
+    /// `at` is unix millis from the scheduler clock, which the heartbeat compares it against.
+    pub(crate) fn record_datagram(&self, at: u64) {
+        self.datagrams.fetch_add(1, Ordering::Relaxed);
+        self.last_datagram_at.store(at, Ordering::Relaxed);
+    }
+
+    /// Unix millis of the last datagram, 0 before the first.
+    pub(crate) fn last_datagram_at(&self) -> u64 {
+        self.last_datagram_at.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn record_state(&self) {
+        self.state_updates.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_patch(&self) {
+        self.patch_updates.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_rejected(&self) {
+        self.rejected.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_parse(&self, transport: Transport, payload: &[u8], shape: ParseShape, reason: String) {
+        let decision = ParseDecision {
+            shape,
+            reason,
+            at: unix_millis(),
+        };
+        if let Ok(mut last_parse) = self.last_parse.lock() {
+            *last_parse = Some(decision.clone());
+        }
+        let kept = &payload[..payload.len().min(MAX_KEPT_PAYLOAD_BYTES)];
+        if let Ok(mut last_payloads) = self.last_payloads.lock() {
+            last_payloads.insert(
+                transport,
+                LastPayload {
+                    payload: String::from_utf8_lossy(kept).into_owned(),
+                    truncated: kept.len() < payload.len(),
+                    parse: decision,
+                },
+            );
+        }
+    }
+
+    pub(crate) fn record_throttled(&self) {
+        self.throttled.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// `now_ms` must come from the clock `record_datagram` was given.
+    pub(crate) fn health(&self, now_ms: u64) -> HealthSnapshot {
+        let last_datagram_at = self.last_datagram_at.load(Ordering::Relaxed);
+        HealthSnapshot {
+            uptime_ms: self.started.elapsed().as_millis() as u64,
+            bridge_bound: self.bound.load(Ordering::Relaxed),
+            last_datagram_ago_ms: (last_datagram_at > 0).then(|| now_ms.saturating_sub(last_datagram_at)),
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> BridgeMetricsSnapshot {
+        let last_datagram_at = self.last_datagram_at.load(Ordering::Relaxed);
+        BridgeMetricsSnapshot {
+            uptime_ms: self.started.elapsed().as_millis() as u64,
+            bound: self.bound.load(Ordering::Relaxed),
+            datagrams: self.datagrams.load(Ordering::Relaxed),
+            state_updates: self.state_updates.load(Ordering::Relaxed),
+            patch_updates: self.patch_updates.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+            throttled: self.throttled.load(Ordering::Relaxed),
+            last_datagram_at: (last_datagram_at > 0).then_some(last_datagram_at),
+        }
+    }
+}
+
+/// Liveness summary for external watchdogs; read from atomics only.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct HealthSnapshot {
+    pub(crate) uptime_ms: u64,
+    pub(crate) bridge_bound: bool,
+    pub(crate) last_datagram_ago_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct BridgeMetricsSnapshot {
+    pub(crate) uptime_ms: u64,
+    pub(crate) bound: bool,
+    pub(crate) datagrams: u64,
+    pub(crate) state_updates: u64,
+    pub(crate) patch_updates: u64,
+    pub(crate) rejected: u64,
+    /// Level-only patches skipped by `max_level_hz`.
+    pub(crate) throttled: u64,
+    /// Unix millis of the last datagram, if any arrived.
+    pub(crate) last_datagram_at: Option<u64>,
+}
+
+#[tauri::command]
+pub(crate) fn get_bridge_metrics(shared: State<'_, Arc<SharedOverlayState>>) -> BridgeMetricsSnapshot {
+    shared.metrics.snapshot()
+}
+
+/// `null` until the first datagram arrives.
+#[tauri::command]
+pub(crate) fn get_last_parse(shared: State<'_, Arc<SharedOverlayState>>) -> Result<Option<ParseDecision>, String> {
+    Ok(shared
+        .metrics
+        .last_parse
+        .lock()
+        .map_err(|_| "parse decision lock poisoned".to_string())?
+        .clone())
+}
+
+/// Per transport, the raw payload it last delivered; transports that sent nothing are absent.
+#[tauri::command]
+pub(crate) fn get_last_payloads(
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<BTreeMap<Transport, LastPayload>, String> {
+    Ok(shared
+        .metrics
+        .last_payloads
+        .lock()
+        .map_err(|_| "last payloads lock poisoned".to_string())?
+        .clone())
+}
@@ -0,0 +1,122 @@
+use std::{collections::BTreeMap, sync::Arc, sync::Mutex};
+
+use serde::Serialize;
+use tauri::{AppHandle, State};
+
+use crate::{update_overlay_state, OverlayState, SharedOverlayState};
+
+const MAX_MUTED_MESSAGES: usize = 64;
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(FNV_OFFSET_BASIS, |hash, &byte| (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME))
+}
+
+/// 64-bit FNV-1a of the UTF-8 message, as 16 lowercase hex digits.
+pub(crate) fn message_hash(message: &str) -> String {
+    format!("{:016x}", fnv1a(message.as_bytes()))
+}
+
+fn normalize_hash(hash: &str) -> Result<String, String> {
+    let hash = hash.trim().to_ascii_lowercase();
+    if hash.len() != 16 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("message hash must be 16 hex digits".to_string());
+    }
+    Ok(hash)
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct MutedMessage {
+    hash: String,
+    /// Last message seen with this hash, if any arrived since it was muted.
+    message: Option<String>,
+}
+
+/// Session-only set of message hashes whose messages are dropped.
+#[derive(Default)]
+pub(crate) struct MutedMessages {
+    entries: Mutex<BTreeMap<String, Option<String>>>,
+}
+
+impl MutedMessages {
+    pub(crate) fn len(&self) -> usize {
+        self.entries.lock().map(|entries| entries.len()).unwrap_or(0)
+    }
+
+    /// Keeps the previous message when `next.message` is muted. Returns true if it did.
+    pub(crate) fn filter(&self, previous: &OverlayState, next: &mut OverlayState) -> bool {
+        let Some(message) = next.message.as_ref() else {
+            return false;
+        };
+        if previous.message.as_ref() == Some(message) {
+            return false;
+        }
+        let Ok(mut entries) = self.entries.lock() else {
+            return false;
+        };
+        let Some(seen) = entries.get_mut(&message_hash(message)) else {
+            return false;
+        };
+        *seen = Some(message.clone());
+        next.message = previous.message.clone();
+        true
+    }
+}
+
+/// Mutes messages with `hash` for this session and clears the current message if it matches.
+#[tauri::command]
+pub(crate) fn mute_message_hash(
+    hash: String,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    let hash = normalize_hash(&hash)?;
+    {
+        let mut entries = shared
+            .muted_messages
+            .entries
+            .lock()
+            .map_err(|_| "muted messages lock poisoned".to_string())?;
+        if !entries.contains_key(&hash) && entries.len() >= MAX_MUTED_MESSAGES {
+            return Err(format!("at most {} messages can be muted", MAX_MUTED_MESSAGES));
+        }
+        entries.entry(hash.clone()).or_insert(None);
+    }
+    update_overlay_state(&app, shared.inner(), |state| {
+        if state.message.as_deref().map(message_hash) == Some(hash) {
+            state.message = None;
+        }
+    })
+}
+
+/// Returns whether `hash` was muted.
+#[tauri::command]
+pub(crate) fn unmute_message_hash(hash: String, shared: State<'_, Arc<SharedOverlayState>>) -> Result<bool, String> {
+    let hash = normalize_hash(&hash)?;
+    let mut entries = shared
+        .muted_messages
+        .entries
+        .lock()
+        .map_err(|_| "muted messages lock poisoned".to_string())?;
+    Ok(entries.remove(&hash).is_some())
+}
+
+#[tauri::command]
+pub(crate) fn list_muted_messages(shared: State<'_, Arc<SharedOverlayState>>) -> Result<Vec<MutedMessage>, String> {
+    let entries = shared
+        .muted_messages
+        .entries
+        .lock()
+        .map_err(|_| "muted messages lock poisoned".to_string())?;
+    Ok(entries
+        .iter()
+        .map(|(hash, message)| MutedMessage {
+            hash: hash.clone(),
+            message: message.clone(),
+        })
+        .collect())
+}
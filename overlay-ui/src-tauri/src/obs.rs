@@ -0,0 +1,117 @@
+use std::{
+    fs::{self, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use tauri::{AppHandle, State};
+
+use crate::{
+    capture::{capture_window, Frame},
+    config::update_config,
+    lock_state, main_window, SharedOverlayState,
+};
+
+const MAX_OBS_MIRROR_FPS: u32 = 30;
+
+/// Periodically writes the overlay's frame to a PNG an OBS image source can watch.
+#[derive(Default)]
+pub(crate) struct ObsMirror {
+    generation: AtomicU64,
+}
+
+fn encode_png(frame: &Frame, path: &Path) -> Result<(), String> {
+    let file = File::create(path).map_err(|error| format!("failed to create {}: {}", path.display(), error))?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), frame.width, frame.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|error| error.to_string())?;
+    writer.write_image_data(&frame.rgba).map_err(|error| error.to_string())
+}
+
+/// A fully transparent frame written while the overlay is hidden, so OBS
+/// doesn't show the desktop behind it.
+fn blank_frame(like: Option<&Frame>) -> Frame {
+    let (width, height) = like.map_or((1, 1), |frame| (frame.width, frame.height));
+    Frame {
+        width,
+        height,
+        rgba: vec![0; width as usize * height as usize * 4],
+    }
+}
+
+/// Writes through a temporary file and renames it, so OBS never reads a half-written PNG.
+fn write_frame(frame: &Frame, target: &Path) -> Result<(), String> {
+    let staging = target.with_extension("png.tmp");
+    encode_png(frame, &staging)?;
+    fs::rename(&staging, target).map_err(|error| format!("failed to replace {}: {}", target.display(), error))
+}
+
+/// Starts mirroring to `target` at `fps` (the saved rate when omitted); a
+/// running mirror is replaced. Unchanged frames are not rewritten.
+#[tauri::command]
+pub(crate) fn start_obs_mirror(
+    target: String,
+    fps: Option<u32>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    let target = PathBuf::from(target.trim());
+    if target.as_os_str().is_empty() {
+        return Err("OBS mirror target must not be empty".to_string());
+    }
+    if let Some(fps) = fps {
+        if fps == 0 || fps > MAX_OBS_MIRROR_FPS {
+            return Err(format!("OBS mirror rate must be 1..={} fps", MAX_OBS_MIRROR_FPS));
+        }
+    }
+    let config = update_config(&app, shared.inner(), |config| {
+        if let Some(fps) = fps {
+            config.obs_mirror_fps = fps;
+        }
+    })?;
+    let interval = Duration::from_millis(1000 / u64::from(config.obs_mirror_fps.clamp(1, MAX_OBS_MIRROR_FPS)));
+    let window = main_window(&app)?;
+    let shared = shared.inner().clone();
+    let generation = shared.obs_mirror.generation.fetch_add(1, Ordering::Relaxed) + 1;
+    log::info!("mirroring the overlay to {}", target.display());
+    thread::spawn(move || {
+        let mut last: Option<Frame> = None;
+        while shared.obs_mirror.generation.load(Ordering::Relaxed) == generation {
+            let tick = Instant::now();
+            let visible = lock_state(&shared).map(|state| state.visible).unwrap_or(false);
+            let frame = if visible {
+                capture_window(&window)
+            } else {
+                Ok(blank_frame(last.as_ref()))
+            };
+            match frame {
+                Ok(frame) => {
+                    let unchanged = last.as_ref().is_some_and(|last| {
+                        last.width == frame.width && last.height == frame.height && last.rgba == frame.rgba
+                    });
+                    if !unchanged {
+                        if let Err(error) = write_frame(&frame, &target) {
+                            log::warn!("OBS mirror write failed: {}", error);
+                        }
+                        last = Some(frame);
+                    }
+                }
+                Err(error) => log::warn!("OBS mirror capture failed: {}", error),
+            }
+            thread::sleep(interval.saturating_sub(tick.elapsed()));
+        }
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn stop_obs_mirror(shared: State<'_, Arc<SharedOverlayState>>) {
+    shared.obs_mirror.generation.fetch_add(1, Ordering::Relaxed);
+}
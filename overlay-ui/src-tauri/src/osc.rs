@@ -0,0 +1,151 @@
+use std::{net::UdpSocket, sync::Arc, thread};
+
+use serde_json::{Map, Value};
+use tauri::AppHandle;
+
+use crate::{handle_datagram, ingest::Transport, lock_config, SharedOverlayState};
+
+const OSC_PREFIX: &str = "/overlay/";
+/// Bundles nested deeper than this are dropped.
+const MAX_BUNDLE_DEPTH: usize = 4;
+
+#[derive(Debug, Clone, Copy)]
+enum FieldKind {
+    Number,
+    Flag,
+    Text,
+}
+
+/// State fields OSC addresses may set, as `/overlay/<field>`.
+const OSC_FIELDS: &[(&str, FieldKind)] = &[
+    ("level", FieldKind::Number),
+    ("confidence", FieldKind::Number),
+    ("visible", FieldKind::Flag),
+    ("message", FieldKind::Text),
+    ("connection", FieldKind::Text),
+    ("listening", FieldKind::Text),
+    ("processing", FieldKind::Text),
+    ("target", FieldKind::Text),
+];
+
+/// A null-terminated OSC string padded to four bytes, and the bytes after it.
+fn read_string(bytes: &[u8]) -> Option<(&str, &[u8])> {
+    let end = bytes.iter().position(|byte| *byte == 0)?;
+    let text = std::str::from_utf8(&bytes[..end]).ok()?;
+    let padded = (end + 4) & !3;
+    Some((text, bytes.get(padded..)?))
+}
+
+fn read_u32(bytes: &[u8]) -> Option<(u32, &[u8])> {
+    let word = bytes.get(..4)?;
+    Some((u32::from_be_bytes([word[0], word[1], word[2], word[3]]), &bytes[4..]))
+}
+
+/// The first argument of a message as JSON; only `i`, `f`, `d`, `s`, `T` and `F` are read.
+fn first_argument(tags: &str, arguments: &[u8]) -> Option<Value> {
+    Some(match tags.strip_prefix(',')?.chars().next()? {
+        'i' => Value::from(read_u32(arguments)?.0 as i32),
+        'f' => Value::from(f64::from(f32::from_bits(read_u32(arguments)?.0))),
+        'd' => {
+            let word = arguments.get(..8)?;
+            let mut bits = [0_u8; 8];
+            bits.copy_from_slice(word);
+            Value::from(f64::from_be_bytes(bits))
+        }
+        's' => Value::from(read_string(arguments)?.0),
+        'T' => Value::Bool(true),
+        'F' => Value::Bool(false),
+        _ => return None,
+    })
+}
+
+fn coerce(kind: FieldKind, value: Value) -> Option<Value> {
+    match (kind, value) {
+        (FieldKind::Number, value @ Value::Number(_)) => Some(value),
+        (FieldKind::Flag, Value::Bool(flag)) => Some(Value::Bool(flag)),
+        (FieldKind::Flag, Value::Number(number)) => Some(Value::Bool(number.as_f64() != Some(0.0))),
+        (FieldKind::Text, value @ Value::String(_)) => Some(value),
+        _ => None,
+    }
+}
+
+/// Collects the fields set by one OSC packet, a message or a bundle of them.
+fn collect_fields(packet: &[u8], fields: &mut Map<String, Value>, depth: usize) {
+    if let Some(mut elements) = packet.strip_prefix(b"#bundle\0") {
+        if depth >= MAX_BUNDLE_DEPTH {
+            return;
+        }
+        // Time tags are ignored; bundle contents apply at once.
+        elements = elements.get(8..).unwrap_or_default();
+        while let Some((size, rest)) = read_u32(elements) {
+            let Some(element) = rest.get(..size as usize) else {
+                return;
+            };
+            collect_fields(element, fields, depth + 1);
+            elements = &rest[size as usize..];
+        }
+        return;
+    }
+    let Some((address, rest)) = read_string(packet) else {
+        log::debug!("ignored malformed OSC packet");
+        return;
+    };
+    let field = address.strip_prefix(OSC_PREFIX).and_then(|name| {
+        OSC_FIELDS
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(field, kind)| (*field, *kind))
+    });
+    let Some((field, kind)) = field else {
+        log::debug!("ignored OSC message for unknown address {}", address);
+        return;
+    };
+    let value = read_string(rest)
+        .and_then(|(tags, arguments)| first_argument(tags, arguments))
+        .and_then(|value| coerce(kind, value));
+    match value {
+        Some(value) => {
+            fields.insert(field.to_string(), value);
+        }
+        None => log::debug!("ignored OSC message for {} with an unusable argument", address),
+    }
+}
+
+/// Listens for OSC on `osc_addr` and applies each packet as one bridge patch,
+/// e.g. `/overlay/level f`, `/overlay/visible i` or `/overlay/message s`.
+pub(crate) fn start_osc_bridge(app: AppHandle, shared: Arc<SharedOverlayState>) {
+    let addr = match lock_config(&shared) {
+        Ok(config) => config.osc_addr.clone(),
+        Err(_) => return,
+    };
+    let Some(addr) = addr else {
+        return;
+    };
+    thread::spawn(move || {
+        let socket = match UdpSocket::bind(&addr) {
+            Ok(socket) => socket,
+            Err(error) => {
+                log::error!("failed to bind OSC bridge at {}: {}", addr, error);
+                return;
+            }
+        };
+        log::info!("overlay OSC bridge listening on {}", addr);
+        let mut buffer = [0_u8; 8192];
+        loop {
+            let count = match socket.recv_from(&mut buffer) {
+                Ok((count, _)) => count,
+                Err(error) => {
+                    log::error!("overlay OSC bridge stopped: {}", error);
+                    return;
+                }
+            };
+            let mut fields = Map::new();
+            collect_fields(&buffer[..count], &mut fields, 0);
+            if fields.is_empty() {
+                continue;
+            }
+            fields.insert("kind".to_string(), Value::from("patch"));
+            let _ = handle_datagram(&app, &shared, Transport::Osc, Value::Object(fields).to_string().as_bytes());
+        }
+    });
+}
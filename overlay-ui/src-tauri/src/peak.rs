@@ -0,0 +1,149 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use tauri::{AppHandle, State};
+
+use crate::{
+    config::update_config, lock_state, update_overlay_state, OverlayState,
+    SharedOverlayState,
+};
+
+const PEAK_DECAY_TICK: Duration = Duration::from_millis(33);
+const MAX_PEAK_HOLD_MS: u64 = 10_000;
+const MAX_CLIP_HOLD_MS: u64 = 10_000;
+const CLIP_LEVEL: f64 = 1.0;
+
+struct HeldPeak {
+    level: f64,
+    /// When `level` was last raised; `None` before the first one.
+    since: Option<Instant>,
+    clipped_at: Option<Instant>,
+}
+
+/// Peak-hold marker: jumps to new maxima, holds for `peak_hold_ms`, then falls
+/// at `peak_fall_per_sec` until it meets the live level. A full-scale level
+/// also sets `clipping` and pins the marker at full for `clip_hold_ms`. An
+/// infinite fall rate drops the marker straight to the live level.
+pub(crate) struct PeakMeter {
+    held: Mutex<HeldPeak>,
+    decaying: AtomicBool,
+}
+
+impl Default for PeakMeter {
+    fn default() -> Self {
+        Self {
+            held: Mutex::new(HeldPeak {
+                level: 0.0,
+                since: None,
+                clipped_at: None,
+            }),
+            decaying: AtomicBool::new(false),
+        }
+    }
+}
+
+impl PeakMeter {
+    pub(crate) fn filter(
+        &self,
+        app: &AppHandle,
+        shared: &Arc<SharedOverlayState>,
+        next: &mut OverlayState,
+        hold: Duration,
+        fall_per_sec: f64,
+        clip_hold: Duration,
+    ) {
+        let Ok(mut held) = self.held.lock() else {
+            return;
+        };
+        let now = shared.scheduler.clock().now();
+        if next.level >= CLIP_LEVEL && !clip_hold.is_zero() {
+            held.clipped_at = Some(now);
+        }
+        next.clipping = held
+            .clipped_at
+            .is_some_and(|at| now.duration_since(at) < clip_hold);
+        if next.clipping {
+            // The fall starts once the clip hold ends.
+            held.level = CLIP_LEVEL;
+            held.since = Some(now);
+        }
+        let falling_for = held
+            .since
+            .map_or(Duration::ZERO, |since| now.duration_since(since).saturating_sub(hold));
+        let decayed = if fall_per_sec.is_infinite() && !next.clipping {
+            0.0
+        } else if falling_for.is_zero() {
+            held.level
+        } else {
+            (held.level - fall_per_sec * falling_for.as_secs_f64()).max(0.0)
+        };
+        if next.level >= decayed {
+            held.level = next.level;
+            held.since = Some(now);
+        }
+        next.peak_level = decayed.max(next.level);
+        let pending = next.peak_level > next.level || (next.clipping && next.level < CLIP_LEVEL);
+        if pending && !self.decaying.swap(true, Ordering::Relaxed) {
+            start_decay(app.clone(), shared.clone());
+        }
+    }
+}
+
+/// Re-runs the policies on a timer so the marker keeps falling, and the clip
+/// flag clears, without new datagrams; `saver` only waits out the clip hold, at a
+/// stretched tick.
+fn start_decay(app: AppHandle, shared: Arc<SharedOverlayState>) {
+    shared.scheduler.repeat("peak_decay", PEAK_DECAY_TICK, move |shared| {
+        let settled = update_overlay_state(&app, shared, |_| {}).is_err();
+        let state = lock_state(shared).map(|state| {
+            let settled = state.peak_level <= state.level && (!state.clipping || state.level >= CLIP_LEVEL);
+            (settled, state.power_mode)
+        });
+        match state {
+            Ok((false, mode)) if !settled => Some(mode.stretch_tick(PEAK_DECAY_TICK)),
+            _ => {
+                shared.peak_meter.decaying.store(false, Ordering::Relaxed);
+                None
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub(crate) fn set_peak_hold(
+    hold_ms: u64,
+    fall_per_sec: f64,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if hold_ms > MAX_PEAK_HOLD_MS {
+        return Err(format!("peak hold must be at most {} ms", MAX_PEAK_HOLD_MS));
+    }
+    if !fall_per_sec.is_finite() || fall_per_sec <= 0.0 {
+        return Err("peak fall rate must be a positive number".to_string());
+    }
+    update_config(&app, shared.inner(), |config| {
+        config.peak_hold_ms = hold_ms;
+        config.peak_fall_per_sec = fall_per_sec;
+    })?;
+    Ok(())
+}
+
+/// How long `clipping` stays set after the last full-scale level; 0 disables it.
+#[tauri::command]
+pub(crate) fn set_clip_hold(
+    hold_ms: u64,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if hold_ms > MAX_CLIP_HOLD_MS {
+        return Err(format!("clip hold must be at most {} ms", MAX_CLIP_HOLD_MS));
+    }
+    update_config(&app, shared.inner(), |config| config.clip_hold_ms = hold_ms)?;
+    Ok(())
+}
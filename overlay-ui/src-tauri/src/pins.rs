@@ -0,0 +1,141 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, State};
+
+use crate::{
+    sanitize_confidence,
+    steps::Step,
+    sticky::StateField,
+    timeline::{ChangeSource, SourceScope},
+    update_overlay_state, OverlayState, SharedOverlayState,
+};
+
+#[derive(Debug, Clone)]
+enum PinnedValue {
+    Connection(String),
+    Listening(String),
+    Processing(String),
+    Target(String),
+    Level(f64),
+    Visible(bool),
+    Message(Option<String>),
+    Confidence(Option<f64>),
+    Steps(Vec<Step>),
+}
+
+fn typed<T: serde::de::DeserializeOwned>(field: StateField, value: Value) -> Result<T, String> {
+    serde_json::from_value(value).map_err(|error| format!("invalid value for {:?}: {}", field, error))
+}
+
+impl PinnedValue {
+    fn parse(field: StateField, value: Value) -> Result<Self, String> {
+        Ok(match field {
+            StateField::Connection => PinnedValue::Connection(typed(field, value)?),
+            StateField::Listening => PinnedValue::Listening(typed(field, value)?),
+            StateField::Processing => PinnedValue::Processing(typed(field, value)?),
+            StateField::Target => PinnedValue::Target(typed(field, value)?),
+            StateField::Level => PinnedValue::Level(typed::<f64>(field, value)?.clamp(0.0, 1.0)),
+            StateField::Visible => PinnedValue::Visible(typed(field, value)?),
+            StateField::Message => PinnedValue::Message(typed(field, value)?),
+            StateField::Confidence => PinnedValue::Confidence(sanitize_confidence(typed(field, value)?)),
+            StateField::Steps => PinnedValue::Steps(typed(field, value)?),
+        })
+    }
+
+    fn apply(&self, state: &mut OverlayState) {
+        match self {
+            PinnedValue::Connection(value) => state.connection = value.clone(),
+            PinnedValue::Listening(value) => state.listening = value.clone(),
+            PinnedValue::Processing(value) => state.processing = value.clone(),
+            PinnedValue::Target(value) => state.target = value.clone(),
+            PinnedValue::Level(value) => state.level = *value,
+            PinnedValue::Visible(value) => state.visible = *value,
+            PinnedValue::Message(value) => state.message = value.clone(),
+            PinnedValue::Confidence(value) => state.confidence = *value,
+            PinnedValue::Steps(value) => state.steps = value.clone(),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        match self {
+            PinnedValue::Connection(value)
+            | PinnedValue::Listening(value)
+            | PinnedValue::Processing(value)
+            | PinnedValue::Target(value) => Value::from(value.as_str()),
+            PinnedValue::Level(value) => Value::from(*value),
+            PinnedValue::Visible(value) => Value::from(*value),
+            PinnedValue::Message(value) => Value::from(value.clone()),
+            PinnedValue::Confidence(value) => Value::from(*value),
+            PinnedValue::Steps(value) => serde_json::to_value(value).unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct PinnedField {
+    field: StateField,
+    value: Value,
+}
+
+/// Operator overrides that win over every incoming update until unpinned.
+#[derive(Default)]
+pub(crate) struct PinnedFields {
+    entries: Mutex<BTreeMap<StateField, PinnedValue>>,
+}
+
+impl PinnedFields {
+    pub(crate) fn len(&self) -> usize {
+        self.entries.lock().map(|entries| entries.len()).unwrap_or(0)
+    }
+
+    pub(crate) fn apply(&self, next: &mut OverlayState) {
+        if let Ok(entries) = self.entries.lock() {
+            for value in entries.values() {
+                value.apply(next);
+            }
+        }
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, BTreeMap<StateField, PinnedValue>>, String> {
+        self.entries.lock().map_err(|_| "pinned fields lock poisoned".to_string())
+    }
+}
+
+#[tauri::command]
+pub(crate) fn pin_field(
+    field: StateField,
+    value: Value,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    let value = PinnedValue::parse(field, value)?;
+    shared.pinned_fields.lock()?.insert(field, value);
+    let _source = SourceScope::enter(ChangeSource::Command);
+    // Policies run on every update, so an empty one applies the pin right away.
+    update_overlay_state(&app, shared.inner(), |_| {})
+}
+
+/// Returns whether `field` was pinned; it keeps its pinned value until the next update.
+#[tauri::command]
+pub(crate) fn unpin_field(field: StateField, shared: State<'_, Arc<SharedOverlayState>>) -> Result<bool, String> {
+    Ok(shared.pinned_fields.lock()?.remove(&field).is_some())
+}
+
+#[tauri::command]
+pub(crate) fn list_pinned_fields(shared: State<'_, Arc<SharedOverlayState>>) -> Result<Vec<PinnedField>, String> {
+    Ok(shared
+        .pinned_fields
+        .lock()?
+        .iter()
+        .map(|(field, value)| PinnedField {
+            field: *field,
+            value: value.to_json(),
+        })
+        .collect())
+}
@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, PhysicalPosition, State};
+
+use crate::{
+    config::update_config,
+    layout::{self, DisplayForm, OverlayGeometry},
+    lock_config, main_window,
+    position::{set_overlay_position, PositionTrigger},
+    update_overlay_state, SharedOverlayState,
+};
+
+/// Window geometry captured by `save_placement`; the position is in physical
+/// screen pixels on the named monitor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct Placement {
+    monitor: Option<String>,
+    x: i32,
+    y: i32,
+    geometry: OverlayGeometry,
+    text_scale: f64,
+    display_form: DisplayForm,
+}
+
+fn placement_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("placement name must not be empty".to_string());
+    }
+    Ok(name.to_string())
+}
+
+#[tauri::command]
+pub(crate) fn save_placement(
+    name: String,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<Placement, String> {
+    let name = placement_name(&name)?;
+    let window = main_window(&app)?;
+    let position = window.outer_position().map_err(|error| error.to_string())?;
+    let monitor = window.current_monitor().map_err(|error| error.to_string())?;
+    let config = lock_config(shared.inner())?.clone();
+    let placement = Placement {
+        monitor: monitor.and_then(|monitor| monitor.name().cloned()),
+        x: position.x,
+        y: position.y,
+        geometry: config.geometry,
+        text_scale: config.text_scale,
+        display_form: config.display_form,
+    };
+    update_config(&app, shared.inner(), |config| {
+        config.placements.insert(name, placement.clone());
+    })?;
+    Ok(placement)
+}
+
+/// Re-applies a saved placement and returns warnings. When its monitor is no
+/// longer connected, only the size is restored and the overlay stays anchored
+/// on the current monitor. The position holds until the next re-anchor.
+#[tauri::command]
+pub(crate) fn restore_placement(
+    name: String,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<Vec<String>, String> {
+    let name = placement_name(&name)?;
+    let placement = lock_config(shared.inner())?
+        .placements
+        .get(&name)
+        .cloned()
+        .ok_or_else(|| format!("placement {:?} not found", name))?;
+    let window = main_window(&app)?;
+    let monitors = window.available_monitors().map_err(|error| error.to_string())?;
+    let monitor_present = placement
+        .monitor
+        .as_ref()
+        .is_some_and(|saved| monitors.iter().any(|monitor| monitor.name() == Some(saved)));
+    let mut warnings = Vec::new();
+    if !monitor_present {
+        warnings.push(format!(
+            "monitor {} is not connected, keeping the overlay anchored on the current monitor",
+            placement.monitor.as_deref().unwrap_or("(unknown)")
+        ));
+    }
+
+    let text_scale = layout::clamp_text_scale(placement.text_scale);
+    let config = update_config(&app, shared.inner(), |config| {
+        config.geometry = placement.geometry;
+        config.text_scale = text_scale;
+        config.display_form = placement.display_form;
+    })?;
+    layout::apply_overlay_layout(&window, &config, PositionTrigger::Placement).map_err(|error| error.to_string())?;
+    if monitor_present {
+        set_overlay_position(
+            &window,
+            PhysicalPosition::new(placement.x, placement.y),
+            PositionTrigger::Placement,
+        )
+        .map_err(|error| error.to_string())?;
+    }
+    update_overlay_state(&app, shared.inner(), |state| {
+        state.text_scale = text_scale;
+        state.corner_radius = placement.geometry.corner_radius;
+        state.display_form = placement.display_form;
+    })?;
+    Ok(warnings)
+}
@@ -0,0 +1,112 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use serde::Serialize;
+use tauri::{Manager, PhysicalPosition, Position, State, WebviewWindow};
+
+use crate::{unix_millis, SharedOverlayState};
+
+const POSITION_HISTORY_CAPACITY: usize = 200;
+
+/// Why the overlay was moved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum PositionTrigger {
+    /// First layout at startup.
+    Initial,
+    /// Size, form, text scale or config changed through a command.
+    Layout,
+    /// Scale factor changed, i.e. the overlay crossed onto another monitor.
+    MonitorChange,
+    /// The periodic check found the overlay off-screen.
+    SelfCheck,
+    /// The docked window moved, or docking started or ended.
+    Dock,
+    /// A saved placement was restored.
+    Placement,
+    /// `auto_expand` grew or shrank the window as the message changed.
+    AutoExpand,
+    /// The hidden overlay shrank to its status dot, or grew back.
+    StatusDot,
+    /// The reported video region moved, or letterbox mode was toggled.
+    Letterbox,
+    /// The overlay's display switched to another GPU.
+    AdapterChange,
+    #[cfg(debug_assertions)]
+    ScaleOverride,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct PositionRecord {
+    timestamp: u64,
+    x: i32,
+    y: i32,
+    monitor: Option<String>,
+    trigger: PositionTrigger,
+}
+
+/// Recent `set_position` calls, oldest first, for diagnosing drift.
+#[derive(Default)]
+pub(crate) struct PositionHistory {
+    entries: Mutex<VecDeque<PositionRecord>>,
+}
+
+impl PositionHistory {
+    pub(crate) fn len(&self) -> usize {
+        self.entries.lock().map(|entries| entries.len()).unwrap_or(0)
+    }
+}
+
+/// Moves the window and records where, on which monitor, and why.
+pub(crate) fn set_overlay_position(
+    window: &WebviewWindow,
+    position: PhysicalPosition<i32>,
+    trigger: PositionTrigger,
+) -> tauri::Result<()> {
+    window.set_position(Position::Physical(position))?;
+    let landed_on = window
+        .monitor_from_point(f64::from(position.x), f64::from(position.y))
+        .ok()
+        .flatten();
+    crate::display::record_positioned_monitor(window, landed_on.as_ref());
+    let monitor = landed_on.and_then(|monitor| monitor.name().cloned());
+    log::debug!(
+        "overlay positioned at {},{} on {} ({:?})",
+        position.x,
+        position.y,
+        monitor.as_deref().unwrap_or("no monitor"),
+        trigger
+    );
+    let shared = window.state::<Arc<SharedOverlayState>>();
+    if let Ok(mut entries) = shared.position_history.entries.lock() {
+        if entries.len() == POSITION_HISTORY_CAPACITY {
+            entries.pop_front();
+        }
+        entries.push_back(PositionRecord {
+            timestamp: unix_millis(),
+            x: position.x,
+            y: position.y,
+            monitor,
+            trigger,
+        });
+    }
+    Ok(())
+}
+
+/// The last `count` positions, oldest first.
+#[tauri::command]
+pub(crate) fn get_position_history(
+    count: usize,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<Vec<PositionRecord>, String> {
+    let entries = shared
+        .position_history
+        .entries
+        .lock()
+        .map_err(|_| "position history lock poisoned".to_string())?;
+    let skip = entries.len().saturating_sub(count);
+    Ok(entries.iter().skip(skip).cloned().collect())
+}
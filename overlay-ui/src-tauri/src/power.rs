@@ -0,0 +1,148 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use crate::{
+    config::update_config, emit_overlay_state, lock_config, lock_state,
+    update_overlay_state, SharedOverlayState,
+};
+
+const SAVER_LEVEL_EMIT_INTERVAL: Duration = Duration::from_millis(100);
+const POWER_SOURCE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum PowerMode {
+    Performance,
+    #[default]
+    Balanced,
+    Saver,
+    /// `saver` on battery, `balanced` on AC.
+    Auto,
+}
+
+impl PowerMode {
+    /// The mode actually in effect; only `auto` depends on the power source.
+    pub(crate) fn resolve(self) -> PowerMode {
+        match self {
+            PowerMode::Auto if on_battery() => PowerMode::Saver,
+            PowerMode::Auto => PowerMode::Balanced,
+            mode => mode,
+        }
+    }
+
+    /// Whether decays and entrance animations jump straight to their end values
+    /// instead of ticking there.
+    pub(crate) fn snaps_animations(self) -> bool {
+        self == PowerMode::Saver
+    }
+
+    /// `tick` for a timer that has to keep running, stretched in `saver`.
+    pub(crate) fn stretch_tick(self, tick: Duration) -> Duration {
+        match self {
+            PowerMode::Saver => tick.max(SAVER_LEVEL_EMIT_INTERVAL),
+            _ => tick,
+        }
+    }
+
+    fn level_emit_interval(self) -> Option<Duration> {
+        match self {
+            PowerMode::Saver => Some(SAVER_LEVEL_EMIT_INTERVAL),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(windows)]
+fn on_battery() -> bool {
+    use windows_sys::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    let mut status = SYSTEM_POWER_STATUS::default();
+    // SAFETY: `status` is a valid, writable SYSTEM_POWER_STATUS.
+    let ok = unsafe { GetSystemPowerStatus(&mut status) };
+    ok != 0 && status.ACLineStatus == 0
+}
+
+#[cfg(not(windows))]
+fn on_battery() -> bool {
+    false
+}
+
+/// Caps how often level-only changes are emitted, with a trailing flush so the
+/// last level always reaches the frontend.
+#[derive(Default)]
+pub(crate) struct LevelEmitThrottle {
+    last_emit: Mutex<Option<Instant>>,
+    flush_pending: AtomicBool,
+}
+
+impl LevelEmitThrottle {
+    /// Returns true when this level-only emit should be skipped.
+    pub(crate) fn defer(&self, app: &AppHandle, shared: &Arc<SharedOverlayState>, mode: PowerMode) -> bool {
+        let Some(interval) = mode.level_emit_interval() else {
+            return false;
+        };
+        let recent = self
+            .last_emit
+            .lock()
+            .map(|last| last.is_some_and(|at| shared.scheduler.clock().now().duration_since(at) < interval))
+            .unwrap_or(false);
+        if recent && !self.flush_pending.swap(true, Ordering::AcqRel) {
+            let app = app.clone();
+            shared.scheduler.after("level_flush", interval, move |shared| {
+                shared.level_throttle.flush_pending.store(false, Ordering::Release);
+                if let Ok(state) = lock_state(shared) {
+                    emit_overlay_state(&app, &state);
+                    shared.level_throttle.record_emit(shared.scheduler.clock().now());
+                }
+            });
+        }
+        recent
+    }
+
+    pub(crate) fn record_emit(&self, now: Instant) {
+        if let Ok(mut last) = self.last_emit.lock() {
+            *last = Some(now);
+        }
+    }
+}
+
+/// Re-resolves `auto` periodically so unplugging the charger switches to `saver`.
+pub(crate) fn start_power_source_watch(app: AppHandle, shared: Arc<SharedOverlayState>) {
+    thread::spawn(move || loop {
+        thread::sleep(POWER_SOURCE_CHECK_INTERVAL);
+        let configured = match lock_config(&shared) {
+            Ok(config) => config.power_mode,
+            Err(_) => continue,
+        };
+        if configured != PowerMode::Auto {
+            continue;
+        }
+        let resolved = configured.resolve();
+        let current = lock_state(&shared).map(|state| state.power_mode);
+        if current.is_ok_and(|current| current != resolved) {
+            log::info!("power source changed; overlay power mode is now {:?}", resolved);
+            let _ = update_overlay_state(&app, &shared, |state| state.power_mode = resolved);
+        }
+    });
+}
+
+#[tauri::command]
+pub(crate) fn set_power_mode(
+    mode: PowerMode,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<PowerMode, String> {
+    update_config(&app, shared.inner(), |config| config.power_mode = mode)?;
+    let resolved = mode.resolve();
+    update_overlay_state(&app, shared.inner(), |state| state.power_mode = resolved)?;
+    Ok(resolved)
+}
@@ -0,0 +1,93 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use tauri::{AppHandle, State};
+
+use crate::{
+    config::{read_config_file, write_config_file},
+    replace_overlay_state,
+    timeline::{ChangeSource, SourceScope},
+    OverlayState, SharedOverlayState,
+};
+
+pub(crate) const PRESETS_FILE_NAME: &str = "overlay-presets.json";
+
+/// Named full states, persisted next to the overlay config.
+#[derive(Default)]
+pub(crate) struct Presets {
+    entries: Mutex<BTreeMap<String, OverlayState>>,
+}
+
+impl Presets {
+    pub(crate) fn len(&self) -> usize {
+        self.entries.lock().map(|entries| entries.len()).unwrap_or(0)
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, BTreeMap<String, OverlayState>>, String> {
+        self.entries
+            .lock()
+            .map_err(|_| "preset lock poisoned".to_string())
+    }
+
+    pub(crate) fn load(&self, app: &AppHandle) {
+        if let Ok(mut entries) = self.lock() {
+            *entries = read_config_file(app, PRESETS_FILE_NAME);
+        }
+    }
+}
+
+fn preset_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("preset name must not be empty".to_string());
+    }
+    Ok(name.to_string())
+}
+
+#[tauri::command]
+pub(crate) fn save_preset(
+    name: String,
+    state: OverlayState,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    let mut entries = shared.presets.lock()?;
+    entries.insert(preset_name(&name)?, state.sanitize());
+    write_config_file(&app, PRESETS_FILE_NAME, &*entries)
+}
+
+#[tauri::command]
+pub(crate) fn apply_preset(
+    name: String,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    let preset = shared
+        .presets
+        .lock()?
+        .get(name.trim())
+        .cloned()
+        .ok_or_else(|| format!("unknown preset {:?}", name))?;
+    let _source = SourceScope::enter(ChangeSource::Command);
+    replace_overlay_state(&app, shared.inner(), preset)
+}
+
+#[tauri::command]
+pub(crate) fn list_presets(shared: State<'_, Arc<SharedOverlayState>>) -> Result<Vec<String>, String> {
+    Ok(shared.presets.lock()?.keys().cloned().collect())
+}
+
+#[tauri::command]
+pub(crate) fn delete_preset(
+    name: String,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    let mut entries = shared.presets.lock()?;
+    if entries.remove(name.trim()).is_none() {
+        return Err(format!("unknown preset {:?}", name));
+    }
+    write_config_file(&app, PRESETS_FILE_NAME, &*entries)
+}
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use serde_json::{Map, Value};
+use tauri::{AppHandle, State};
+
+use crate::{config::update_config, lock_config, lock_state, OverlayState, SharedOverlayState};
+
+/// Field names the emitted state carries, in serialization order.
+pub(crate) fn state_field_names() -> Vec<String> {
+    match serde_json::to_value(OverlayState::default()) {
+        Ok(Value::Object(fields)) => fields.keys().cloned().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The emitted shape under a non-empty projection: only the listed fields.
+pub(crate) fn project_state(state: &OverlayState, fields: &[String]) -> Value {
+    let Ok(Value::Object(mut all)) = serde_json::to_value(state) else {
+        return Value::Null;
+    };
+    let projected: Map<String, Value> = fields
+        .iter()
+        .filter_map(|field| all.remove(field).map(|value| (field.clone(), value)))
+        .collect();
+    Value::Object(projected)
+}
+
+/// The shape external mirrors (HTTP, SSE, Unix socket) see: every field but the private ones.
+#[cfg_attr(not(any(feature = "http", feature = "unixsocket")), allow(dead_code))]
+pub(crate) fn public_state(state: &OverlayState, private_fields: &[String]) -> Value {
+    let Ok(mut value) = serde_json::to_value(state) else {
+        return Value::Null;
+    };
+    if let Value::Object(all) = &mut value {
+        for field in private_fields {
+            all.remove(field);
+        }
+    }
+    value
+}
+
+/// The live state as the external mirrors see it.
+#[cfg_attr(not(any(feature = "http", feature = "unixsocket")), allow(dead_code))]
+pub(crate) fn public_state_of(shared: &Arc<SharedOverlayState>) -> Result<Value, String> {
+    let state = lock_state(shared)?;
+    let private_fields = &lock_config(shared)?.private_fields;
+    Ok(public_state(&state, private_fields))
+}
+
+/// `fields` without duplicates, or an error naming the first unknown one.
+fn known_fields(fields: Vec<String>) -> Result<Vec<String>, String> {
+    let known = state_field_names();
+    if let Some(unknown) = fields.iter().find(|field| !known.contains(field)) {
+        return Err(format!("unknown state field {:?}; expected one of {}", unknown, known.join(", ")));
+    }
+    let mut unique: Vec<String> = Vec::new();
+    for field in fields {
+        if !unique.contains(&field) {
+            unique.push(field);
+        }
+    }
+    Ok(unique)
+}
+
+/// Limits `overlay://state` payloads to `fields`; empty emits every field. The
+/// state the backend keeps, and `get_overlay_state`, stay complete.
+#[tauri::command]
+pub(crate) fn set_emit_projection(
+    fields: Vec<String>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    let unique = known_fields(fields)?;
+    update_config(&app, shared.inner(), |config| config.emit_projection = unique)?;
+    Ok(())
+}
+
+/// Keeps `fields` out of the external mirrors while the local frontend still
+/// receives them; empty mirrors everything.
+#[tauri::command]
+pub(crate) fn set_private_fields(
+    fields: Vec<String>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    let unique = known_fields(fields)?;
+    update_config(&app, shared.inner(), |config| config.private_fields = unique)?;
+    Ok(())
+}
+
+/// Just the requested fields of the live state, keyed by name.
+#[tauri::command]
+pub(crate) fn get_fields(fields: Vec<String>, shared: State<'_, Arc<SharedOverlayState>>) -> Result<Value, String> {
+    if fields.is_empty() {
+        return Err("no state fields requested".to_string());
+    }
+    let fields = known_fields(fields)?;
+    let state = lock_state(shared.inner())?;
+    Ok(project_state(&state, &fields))
+}
@@ -0,0 +1,165 @@
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use crate::{config::update_config, SharedOverlayState};
+
+/// Senders tracked at once; the oldest idle ones are forgotten first.
+const MAX_TRACKED_SOURCES: usize = 256;
+
+/// Quarantine a sender after `max_failures` bad payloads within `window_ms`,
+/// for `cooldown_ms`. `max_failures: 0` turns it off.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default, rename_all = "snake_case")]
+pub(crate) struct QuarantinePolicy {
+    max_failures: u32,
+    window_ms: u64,
+    cooldown_ms: u64,
+}
+
+impl Default for QuarantinePolicy {
+    fn default() -> Self {
+        Self {
+            max_failures: 20,
+            window_ms: 10_000,
+            cooldown_ms: 60_000,
+        }
+    }
+}
+
+struct SourceRecord {
+    window_start: Instant,
+    failures: u32,
+    quarantined_until: Option<Instant>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct QuarantinedSource {
+    addr: String,
+    failures: u32,
+    remaining_ms: u64,
+}
+
+/// Drops datagrams from senders that keep sending unparseable payloads.
+#[derive(Default)]
+pub(crate) struct SourceQuarantine {
+    sources: Mutex<HashMap<SocketAddr, SourceRecord>>,
+}
+
+impl SourceQuarantine {
+    /// True while `addr` is quarantined; logs its release once the cooldown ends.
+    pub(crate) fn is_quarantined(&self, addr: SocketAddr, now: Instant) -> bool {
+        let Ok(mut sources) = self.sources.lock() else {
+            return false;
+        };
+        let Some(until) = sources.get(&addr).and_then(|record| record.quarantined_until) else {
+            return false;
+        };
+        if now < until {
+            return true;
+        }
+        sources.remove(&addr);
+        log::info!("released bridge source {} from quarantine", addr);
+        false
+    }
+
+    pub(crate) fn record_failure(&self, addr: SocketAddr, policy: &QuarantinePolicy, now: Instant) {
+        if policy.max_failures == 0 {
+            return;
+        }
+        let Ok(mut sources) = self.sources.lock() else {
+            return;
+        };
+        let window = Duration::from_millis(policy.window_ms);
+        if !sources.contains_key(&addr) && sources.len() >= MAX_TRACKED_SOURCES {
+            sources.retain(|_, record| {
+                record.quarantined_until.is_some() || now.duration_since(record.window_start) < window
+            });
+            if sources.len() >= MAX_TRACKED_SOURCES {
+                return;
+            }
+        }
+        let record = sources.entry(addr).or_insert(SourceRecord {
+            window_start: now,
+            failures: 0,
+            quarantined_until: None,
+        });
+        if now.duration_since(record.window_start) >= window {
+            record.window_start = now;
+            record.failures = 0;
+        }
+        record.failures += 1;
+        if record.failures >= policy.max_failures && record.quarantined_until.is_none() {
+            record.quarantined_until = Some(now + Duration::from_millis(policy.cooldown_ms));
+            log::warn!(
+                "quarantined bridge source {} for {} ms after {} bad payloads",
+                addr,
+                policy.cooldown_ms,
+                record.failures
+            );
+        }
+    }
+}
+
+#[tauri::command]
+pub(crate) fn list_quarantined_sources(
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<Vec<QuarantinedSource>, String> {
+    let sources = shared
+        .source_quarantine
+        .sources
+        .lock()
+        .map_err(|_| "quarantine lock poisoned".to_string())?;
+    let now = shared.scheduler.clock().now();
+    Ok(sources
+        .iter()
+        .filter_map(|(addr, record)| {
+            let until = record.quarantined_until.filter(|until| *until > now)?;
+            Some(QuarantinedSource {
+                addr: addr.to_string(),
+                failures: record.failures,
+                remaining_ms: until.duration_since(now).as_millis() as u64,
+            })
+        })
+        .collect())
+}
+
+/// Lifts a quarantine before its cooldown ends.
+#[tauri::command]
+pub(crate) fn release_source(addr: String, shared: State<'_, Arc<SharedOverlayState>>) -> Result<(), String> {
+    let addr: SocketAddr = addr
+        .parse()
+        .map_err(|error| format!("invalid source address {:?}: {}", addr, error))?;
+    let mut sources = shared
+        .source_quarantine
+        .sources
+        .lock()
+        .map_err(|_| "quarantine lock poisoned".to_string())?;
+    match sources.remove(&addr) {
+        Some(record) if record.quarantined_until.is_some() => {
+            log::info!("released bridge source {} from quarantine", addr);
+            Ok(())
+        }
+        _ => Err(format!("{} is not quarantined", addr)),
+    }
+}
+
+#[tauri::command]
+pub(crate) fn set_quarantine_policy(
+    policy: QuarantinePolicy,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if policy.max_failures > 0 && (policy.window_ms == 0 || policy.cooldown_ms == 0) {
+        return Err("quarantine window and cooldown must be above 0".to_string());
+    }
+    update_config(&app, shared.inner(), |config| config.quarantine_policy = policy)?;
+    Ok(())
+}
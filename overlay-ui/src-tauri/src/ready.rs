@@ -0,0 +1,86 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use tauri::{AppHandle, State};
+
+use crate::{
+    mark_listening_requested, mark_visible_requested,
+    timeline::{ChangeSource, SourceScope},
+    update_overlay_state, SharedOverlayState,
+};
+
+const READY_TICK: Duration = Duration::from_secs(1);
+const MAX_READY_COUNTDOWN_SECS: u32 = 10;
+/// What `listening` becomes at zero unless the caller names another value.
+const DEFAULT_READY_LISTENING: &str = "listening";
+
+/// The running "get ready" countdown; a new start or a cancel stops the old one.
+#[derive(Default)]
+pub(crate) struct ReadyCountdown {
+    generation: AtomicU64,
+}
+
+impl ReadyCountdown {
+    fn is_current(&self, generation: u64) -> bool {
+        self.generation.load(Ordering::Relaxed) == generation
+    }
+}
+
+/// Shows the overlay counting `ready_countdown` down from `seconds`, once a
+/// second; at zero clears it and sets `listening`.
+#[tauri::command]
+pub(crate) fn start_ready_countdown(
+    seconds: u32,
+    listening: Option<String>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if !(1..=MAX_READY_COUNTDOWN_SECS).contains(&seconds) {
+        return Err(format!("countdown must be 1-{} seconds", MAX_READY_COUNTDOWN_SECS));
+    }
+    let listening = listening.unwrap_or_else(|| DEFAULT_READY_LISTENING.to_string());
+    if listening.trim().is_empty() {
+        return Err("listening value must not be empty".to_string());
+    }
+    let generation = shared.ready_countdown.generation.fetch_add(1, Ordering::Relaxed) + 1;
+    {
+        let _source = SourceScope::enter(ChangeSource::Command);
+        update_overlay_state(&app, shared.inner(), |state| {
+            state.ready_countdown = Some(seconds);
+            state.visible = true;
+            mark_visible_requested();
+        })?;
+    }
+    let mut remaining = seconds;
+    shared.scheduler.repeat("ready_countdown", READY_TICK, move |shared| {
+        if !shared.ready_countdown.is_current(generation) {
+            return None;
+        }
+        remaining -= 1;
+        let finished = remaining == 0;
+        let _ = update_overlay_state(&app, shared, |state| {
+            if finished {
+                state.ready_countdown = None;
+                state.listening = listening.clone();
+                mark_listening_requested();
+            } else {
+                state.ready_countdown = Some(remaining);
+            }
+        });
+        (!finished).then_some(READY_TICK)
+    });
+    Ok(())
+}
+
+/// Stops the countdown and clears it, leaving `listening` as it was.
+#[tauri::command]
+pub(crate) fn cancel_ready_countdown(app: AppHandle, shared: State<'_, Arc<SharedOverlayState>>) -> Result<(), String> {
+    shared.ready_countdown.generation.fetch_add(1, Ordering::Relaxed);
+    let _source = SourceScope::enter(ChangeSource::Command);
+    update_overlay_state(&app, shared.inner(), |state| state.ready_countdown = None)
+}
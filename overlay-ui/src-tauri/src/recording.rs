@@ -0,0 +1,76 @@
+use std::{
+    fs::File,
+    io::BufWriter,
+    path::Path,
+    thread,
+    time::{Duration, Instant},
+};
+
+use tauri::{AppHandle, WebviewWindow};
+
+use crate::{
+    capture::{capture_window, Frame},
+    main_window,
+};
+
+const RECORDING_FPS: u64 = 15;
+const MAX_RECORDING_MS: u64 = 30_000;
+const GIF_ENCODE_SPEED: i32 = 10;
+
+fn capture_frames(window: &WebviewWindow, duration: Duration) -> Result<Vec<Frame>, String> {
+    let interval = Duration::from_millis(1000 / RECORDING_FPS);
+    let started = Instant::now();
+    let mut frames: Vec<Frame> = Vec::new();
+    while started.elapsed() < duration {
+        let tick = Instant::now();
+        let frame = capture_window(window)?;
+        // A resize mid-recording would break the GIF's fixed canvas; keep the first size.
+        let same_size = match frames.first() {
+            Some(first) => first.width == frame.width && first.height == frame.height,
+            None => true,
+        };
+        if same_size {
+            frames.push(frame);
+        }
+        thread::sleep(interval.saturating_sub(tick.elapsed()));
+    }
+    Ok(frames)
+}
+
+fn encode_gif(frames: Vec<Frame>, path: &Path) -> Result<(), String> {
+    let Some(first) = frames.first() else {
+        return Err("no frames captured".to_string());
+    };
+    let (width, height) = (
+        u16::try_from(first.width).map_err(|_| "overlay too wide for GIF".to_string())?,
+        u16::try_from(first.height).map_err(|_| "overlay too tall for GIF".to_string())?,
+    );
+    let file = File::create(path).map_err(|error| format!("failed to create {}: {}", path.display(), error))?;
+    let mut encoder = gif::Encoder::new(BufWriter::new(file), width, height, &[])
+        .map_err(|error| error.to_string())?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(|error| error.to_string())?;
+    let delay_cs = (100 / RECORDING_FPS) as u16;
+    for mut frame in frames {
+        let mut gif_frame = gif::Frame::from_rgba_speed(width, height, &mut frame.rgba, GIF_ENCODE_SPEED);
+        gif_frame.delay = delay_cs;
+        encoder.write_frame(&gif_frame).map_err(|error| error.to_string())?;
+    }
+    Ok(())
+}
+
+/// Records the overlay's on-screen area at ~15fps into an animated GIF at `path`.
+#[tauri::command]
+pub(crate) async fn record_overlay(duration_ms: u64, path: String, app: AppHandle) -> Result<(), String> {
+    if duration_ms == 0 || duration_ms > MAX_RECORDING_MS {
+        return Err(format!("recording duration must be 1..={} ms", MAX_RECORDING_MS));
+    }
+    let window = main_window(&app)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let frames = capture_frames(&window, Duration::from_millis(duration_ms))?;
+        encode_gif(frames, Path::new(&path))
+    })
+    .await
+    .map_err(|error| error.to_string())?
+}
@@ -0,0 +1,90 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::SharedOverlayState;
+
+const STATS_WINDOW: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct RenderStatsSnapshot {
+    /// Frames per second the webview reported over the window.
+    pub(crate) fps: f64,
+    /// `overlay://state` emits per second over the same window.
+    pub(crate) emit_rate: f64,
+    window_ms: u64,
+}
+
+/// Rolling frame and emit rates, to tell whether the webview keeps up with emits.
+#[derive(Default)]
+pub(crate) struct RenderStats {
+    frames: Mutex<VecDeque<(Instant, u32)>>,
+    emits: Mutex<VecDeque<Instant>>,
+}
+
+fn prune<T>(entries: &mut VecDeque<T>, at: impl Fn(&T) -> Instant, now: Instant) {
+    while entries.front().is_some_and(|entry| now.duration_since(at(entry)) > STATS_WINDOW) {
+        entries.pop_front();
+    }
+}
+
+impl RenderStats {
+    pub(crate) fn record_emit(&self) {
+        let now = Instant::now();
+        if let Ok(mut emits) = self.emits.lock() {
+            prune(&mut emits, |at| *at, now);
+            emits.push_back(now);
+        }
+    }
+
+    fn record_frames(&self, count: u32) {
+        let now = Instant::now();
+        if let Ok(mut frames) = self.frames.lock() {
+            prune(&mut frames, |(at, _)| *at, now);
+            frames.push_back((now, count));
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> RenderStatsSnapshot {
+        let now = Instant::now();
+        let seconds = STATS_WINDOW.as_secs_f64();
+        let frames = self
+            .frames
+            .lock()
+            .map(|mut frames| {
+                prune(&mut frames, |(at, _)| *at, now);
+                frames.iter().map(|(_, count)| u64::from(*count)).sum::<u64>()
+            })
+            .unwrap_or(0);
+        let emits = self
+            .emits
+            .lock()
+            .map(|mut emits| {
+                prune(&mut emits, |at| *at, now);
+                emits.len()
+            })
+            .unwrap_or(0);
+        RenderStatsSnapshot {
+            fps: frames as f64 / seconds,
+            emit_rate: emits as f64 / seconds,
+            window_ms: STATS_WINDOW.as_millis() as u64,
+        }
+    }
+}
+
+/// Frame ticks counted by the frontend since its last report.
+#[tauri::command]
+pub(crate) fn report_frames(count: u32, shared: State<'_, Arc<SharedOverlayState>>) {
+    shared.render_stats.record_frames(count);
+}
+
+#[tauri::command]
+pub(crate) fn get_render_stats(shared: State<'_, Arc<SharedOverlayState>>) -> RenderStatsSnapshot {
+    shared.render_stats.snapshot()
+}
@@ -0,0 +1,151 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tauri::{AppHandle, Manager, State, WebviewWindow};
+
+use crate::{
+    config::update_config, layout::apply_overlay_layout, lock_config,
+    position::PositionTrigger, position_overlay_window, SharedOverlayState,
+};
+
+const MAX_REPOSITION_THROTTLE_MS: u64 = 5_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PendingReposition {
+    trigger: PositionTrigger,
+    /// Re-apply the layout (size and rounding) as well as the position.
+    relayout: bool,
+}
+
+/// Coalesces bursts of monitor-change triggers, e.g. while docking, into one
+/// window move per `reposition_throttle_ms`.
+#[derive(Default)]
+pub(crate) struct RepositionThrottle {
+    pending: Mutex<Option<PendingReposition>>,
+}
+
+fn reposition(window: &WebviewWindow, shared: &Arc<SharedOverlayState>, pending: PendingReposition) {
+    let result = if pending.relayout {
+        let config = match lock_config(shared) {
+            Ok(config) => config.clone(),
+            Err(_) => return,
+        };
+        apply_overlay_layout(window, &config, pending.trigger)
+    } else {
+        position_overlay_window(window, pending.trigger)
+    };
+    if let Err(error) = result {
+        log::warn!("failed to reposition overlay ({:?}): {}", pending.trigger, error);
+    }
+}
+
+/// Schedules a reposition at the end of the throttle interval, folding in any
+/// already scheduled one; the geometry is read when it runs.
+pub(crate) fn request_reposition(window: &WebviewWindow, trigger: PositionTrigger, relayout: bool) {
+    let shared = window.state::<Arc<SharedOverlayState>>().inner().clone();
+    let interval_ms = lock_config(&shared).map(|config| config.reposition_throttle_ms).unwrap_or(0);
+    let request = PendingReposition { trigger, relayout };
+    if interval_ms == 0 {
+        reposition(window, &shared, request);
+        return;
+    }
+    let window = window.clone();
+    throttle(&shared, interval_ms, request, move |shared, pending| reposition(&window, shared, pending));
+}
+
+/// Folds `request` into the scheduled one, or schedules `apply` to run with
+/// everything requested by the end of the interval.
+fn throttle(
+    shared: &Arc<SharedOverlayState>,
+    interval_ms: u64,
+    request: PendingReposition,
+    apply: impl FnOnce(&Arc<SharedOverlayState>, PendingReposition) + Send + 'static,
+) {
+    {
+        let Ok(mut pending) = shared.reposition_throttle.pending.lock() else {
+            return;
+        };
+        if let Some(scheduled) = pending.as_mut() {
+            scheduled.trigger = request.trigger;
+            scheduled.relayout |= request.relayout;
+            return;
+        }
+        *pending = Some(request);
+    }
+    shared.scheduler.after("reposition", Duration::from_millis(interval_ms), move |shared| {
+        let pending = match shared.reposition_throttle.pending.lock() {
+            Ok(mut pending) => pending.take(),
+            Err(_) => return,
+        };
+        if let Some(pending) = pending {
+            apply(shared, pending);
+        }
+    });
+}
+
+#[tauri::command]
+pub(crate) fn set_reposition_throttle(
+    interval_ms: u64,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if interval_ms > MAX_REPOSITION_THROTTLE_MS {
+        return Err(format!(
+            "reposition throttle must be at most {} ms",
+            MAX_REPOSITION_THROTTLE_MS
+        ));
+    }
+    update_config(&app, shared.inner(), |config| config.reposition_throttle_ms = interval_ms)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use super::{throttle, PendingReposition};
+    use crate::{
+        clock::{Clock, MockClock, SharedClock},
+        position::PositionTrigger,
+        scheduler::Scheduler,
+        SharedOverlayState,
+    };
+
+    #[test]
+    fn a_burst_of_triggers_repositions_once() {
+        let clock = MockClock::new();
+        let shared = Arc::new(SharedOverlayState {
+            scheduler: Scheduler::with_clock(SharedClock::from(clock.clone() as Arc<dyn Clock>)),
+            ..Default::default()
+        });
+        let applied = Arc::new(Mutex::new(Vec::new()));
+        let burst = [
+            (PositionTrigger::MonitorChange, true),
+            (PositionTrigger::MonitorChange, false),
+            (PositionTrigger::Dock, false),
+            (PositionTrigger::MonitorChange, false),
+        ];
+        for (trigger, relayout) in burst {
+            let applied = applied.clone();
+            throttle(&shared, 250, PendingReposition { trigger, relayout }, move |_, pending| {
+                applied.lock().unwrap().push(pending);
+            });
+            clock.advance(Duration::from_millis(50));
+            shared.scheduler.run_due(&shared);
+        }
+        assert!(applied.lock().unwrap().is_empty());
+
+        clock.advance(Duration::from_millis(100));
+        shared.scheduler.run_due(&shared);
+        let expected = PendingReposition {
+            trigger: PositionTrigger::MonitorChange,
+            relayout: true,
+        };
+        assert_eq!(*applied.lock().unwrap(), vec![expected]);
+    }
+}
@@ -0,0 +1,137 @@
+use std::{
+    io::Write,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use socket2::Socket;
+use tauri::AppHandle;
+
+use crate::{
+    decode_payload,
+    projection::public_state_of,
+    timeline::{ChangeSource, SourceScope},
+    SharedOverlayState,
+};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct RpcMethod {
+    method: &'static str,
+    params: &'static str,
+    result: &'static str,
+}
+
+/// Methods a Unix socket client may call with `{ "id", "method", "params" }`.
+const RPC_METHODS: &[RpcMethod] = &[
+    RpcMethod {
+        method: "get",
+        params: "none",
+        result: "the current overlay state, less private fields",
+    },
+    RpcMethod {
+        method: "set",
+        params: "a full overlay state; omitted optional fields reset",
+        result: "null",
+    },
+    RpcMethod {
+        method: "patch",
+        params: "any subset of the patch fields",
+        result: "null",
+    },
+    RpcMethod {
+        method: "subscribe",
+        params: "none",
+        result: "null; then { \"event\": \"state\", \"state\": ... } per emitted state",
+    },
+];
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RpcRequest {
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// The frame as an RPC request, or `None` for a plain bridge payload.
+pub(crate) fn parse_request(frame: &[u8]) -> Option<RpcRequest> {
+    let value: Value = serde_json::from_slice(frame).ok()?;
+    if !value.get("method").is_some_and(Value::is_string) {
+        return None;
+    }
+    serde_json::from_value(value).ok()
+}
+
+/// Writes one newline-terminated JSON line; the socket is shared with the subscription pusher.
+fn write_line(socket: &Mutex<Socket>, value: &Value) -> std::io::Result<()> {
+    let mut line = value.to_string();
+    line.push('\n');
+    let mut socket = socket
+        .lock()
+        .map_err(|_| std::io::Error::other("socket lock poisoned"))?;
+    socket.write_all(line.as_bytes())
+}
+
+/// Applies `params` as a tagged bridge payload of `kind`.
+fn apply_tagged(app: &AppHandle, shared: &Arc<SharedOverlayState>, kind: &str, params: Value) -> Result<Value, String> {
+    let Value::Object(mut fields) = params else {
+        return Err("params must be an object".to_string());
+    };
+    fields.insert("kind".to_string(), Value::from(kind));
+    let (update, _) = decode_payload(&Value::Object(fields).to_string())?;
+    let _source = SourceScope::enter(ChangeSource::Udp);
+    update.apply(app, shared)?;
+    Ok(Value::Null)
+}
+
+fn subscribe(shared: &Arc<SharedOverlayState>, socket: &Arc<Mutex<Socket>>) -> Result<Value, String> {
+    let events = shared
+        .state_subscribers
+        .subscribe()
+        .ok_or_else(|| "too many state subscribers".to_string())?;
+    let socket = socket.clone();
+    thread::spawn(move || {
+        for data in events {
+            let Ok(state) = serde_json::from_str::<Value>(&data) else {
+                continue;
+            };
+            if write_line(&socket, &json!({ "event": "state", "state": state })).is_err() {
+                break;
+            }
+        }
+    });
+    Ok(Value::Null)
+}
+
+/// Answers one request on `socket` with `{ "id", "result" }` or `{ "id", "error" }`.
+pub(crate) fn handle_request(
+    app: &AppHandle,
+    shared: &Arc<SharedOverlayState>,
+    socket: &Arc<Mutex<Socket>>,
+    request: RpcRequest,
+) -> std::io::Result<()> {
+    let outcome = match request.method.as_str() {
+        "get" => public_state_of(shared),
+        "set" => apply_tagged(app, shared, "state", request.params),
+        "patch" => apply_tagged(app, shared, "patch", request.params),
+        "subscribe" => subscribe(shared, socket),
+        other => Err(format!("unknown method {:?}", other)),
+    };
+    let mut response = Map::new();
+    response.insert("id".to_string(), request.id);
+    match outcome {
+        Ok(result) => response.insert("result".to_string(), result),
+        Err(error) => response.insert("error".to_string(), Value::from(error)),
+    };
+    write_line(socket, &Value::Object(response))
+}
+
+/// The request/response methods the Unix socket bridge serves.
+#[tauri::command]
+pub(crate) fn get_rpc_contract() -> Vec<RpcMethod> {
+    RPC_METHODS.to_vec()
+}
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, State};
+
+use crate::{
+    callback::{resolve, VisibilityCallback, LAST_SENDER},
+    config::update_config,
+    sticky::{field_value, StateField},
+    OverlayState, SharedOverlayState,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct RuleCondition {
+    field: StateField,
+    equals: Value,
+}
+
+/// Sends `payload` to `send_to` each time the state starts matching `when`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct TransitionRule {
+    when: RuleCondition,
+    send_to: String,
+    payload: Value,
+}
+
+impl RuleCondition {
+    fn matches(&self, state: &OverlayState) -> bool {
+        field_value(state, self.field) == self.equals
+    }
+}
+
+/// Fires every rule whose condition holds for `next` but not for `previous`.
+pub(crate) fn fire_transition_rules(
+    sender: &VisibilityCallback,
+    rules: &[TransitionRule],
+    previous: &OverlayState,
+    next: &OverlayState,
+) {
+    if rules.is_empty() {
+        return;
+    }
+    for rule in rules {
+        if rule.when.matches(next) && !rule.when.matches(previous) {
+            sender.send(&rule.send_to, &rule.payload.to_string());
+        }
+    }
+}
+
+/// Replaces the configured rules; each `send_to` is `"sender"` or a `host:port`.
+#[tauri::command]
+pub(crate) fn set_transition_rules(
+    rules: Vec<TransitionRule>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if let Some(rule) = rules
+        .iter()
+        .find(|rule| rule.send_to != LAST_SENDER && resolve(&rule.send_to).is_none())
+    {
+        return Err(format!("invalid transition rule address: {}", rule.send_to));
+    }
+    update_config(&app, shared.inner(), |config| config.transition_rules = rules)?;
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, PhysicalPosition, PhysicalSize, State};
+
+use crate::{
+    config::update_config, main_window, position::PositionTrigger, position_overlay_window, SharedOverlayState,
+};
+
+/// Region, in physical pixels, the window must stay inside whatever the anchor says.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct SafeRect {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+impl SafeRect {
+    /// Pins the window to the rect's top-left where it is larger than the rect.
+    pub(crate) fn clamp(&self, position: PhysicalPosition<i32>, size: PhysicalSize<u32>) -> PhysicalPosition<i32> {
+        let max_x = self.x + (self.width as i32 - size.width as i32).max(0);
+        let max_y = self.y + (self.height as i32 - size.height as i32).max(0);
+        PhysicalPosition::new(position.x.clamp(self.x, max_x), position.y.clamp(self.y, max_y))
+    }
+}
+
+/// Confines the overlay to `rect`, which must lie on one monitor; `None` removes the constraint.
+#[tauri::command]
+pub(crate) fn set_safe_rect(
+    rect: Option<SafeRect>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    let window = main_window(&app)?;
+    if let Some(rect) = rect {
+        if rect.width == 0 || rect.height == 0 {
+            return Err("safe rect must have a non-zero size".to_string());
+        }
+        let on_monitor = window
+            .available_monitors()
+            .map_err(|error| error.to_string())?
+            .iter()
+            .any(|monitor| {
+                let origin = monitor.position();
+                let extent = monitor.size();
+                rect.x >= origin.x
+                    && rect.y >= origin.y
+                    && i64::from(rect.x) + i64::from(rect.width) <= i64::from(origin.x) + i64::from(extent.width)
+                    && i64::from(rect.y) + i64::from(rect.height) <= i64::from(origin.y) + i64::from(extent.height)
+            });
+        if !on_monitor {
+            return Err("safe rect must lie within a single monitor".to_string());
+        }
+    }
+    update_config(&app, shared.inner(), |config| config.safe_rect = rect)?;
+    position_overlay_window(&window, PositionTrigger::Layout).map_err(|error| error.to_string())
+}
@@ -0,0 +1,278 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use tauri::{AppHandle, State};
+
+use crate::{
+    config::update_config, lock_config, lock_state, update_overlay_state, OverlayState,
+    SharedOverlayState,
+};
+
+const MAX_MIN_VISIBLE_MS: u64 = 10_000;
+
+#[derive(Default)]
+struct FloorState {
+    shown_at: Option<Instant>,
+    /// Generation of the deferred hide, if one is waiting.
+    pending_hide: Option<u64>,
+}
+
+/// Keeps the overlay up for at least `min_visible_ms` once shown, deferring early hides.
+#[derive(Default)]
+pub(crate) struct VisibleFloor {
+    state: Mutex<FloorState>,
+    generation: AtomicU64,
+}
+
+impl VisibleFloor {
+    pub(crate) fn filter(
+        &self,
+        app: &AppHandle,
+        shared: &Arc<SharedOverlayState>,
+        previous: &OverlayState,
+        next: &mut OverlayState,
+        floor: Duration,
+        visible_requested: bool,
+    ) {
+        let now = shared.scheduler.clock().now();
+        if let Some((generation, remaining)) = self.hold(previous, next, floor, visible_requested, now) {
+            schedule_deferred_hide(app.clone(), shared.clone(), generation, remaining);
+        }
+    }
+
+    /// Keeps an early hide on screen; returns the deferred hide to schedule
+    /// when the hide is newly deferred.
+    fn hold(
+        &self,
+        previous: &OverlayState,
+        next: &mut OverlayState,
+        floor: Duration,
+        visible_requested: bool,
+        now: Instant,
+    ) -> Option<(u64, Duration)> {
+        let mut floor_state = self.state.lock().ok()?;
+        if next.visible {
+            if !previous.visible {
+                floor_state.shown_at = Some(now);
+            }
+            // A sender asking to be shown again withdraws its earlier hide.
+            if visible_requested && floor_state.pending_hide.take().is_some() {
+                self.generation.fetch_add(1, Ordering::Relaxed);
+            }
+            return None;
+        }
+        if let (true, Some(shown_at)) = (previous.visible, floor_state.shown_at) {
+            let remaining = floor.saturating_sub(now.duration_since(shown_at));
+            if !remaining.is_zero() {
+                next.visible = true;
+                if floor_state.pending_hide.is_some() {
+                    return None;
+                }
+                let generation = self.generation.fetch_add(1, Ordering::Relaxed) + 1;
+                floor_state.pending_hide = Some(generation);
+                return Some((generation, remaining));
+            }
+        }
+        *floor_state = FloorState::default();
+        None
+    }
+
+    fn is_pending(&self, generation: u64) -> bool {
+        self.state
+            .lock()
+            .map(|floor_state| floor_state.pending_hide == Some(generation))
+            .unwrap_or(false)
+    }
+}
+
+fn schedule_deferred_hide(app: AppHandle, shared: Arc<SharedOverlayState>, generation: u64, delay: Duration) {
+    shared.scheduler.after("visible_floor", delay, move |shared| {
+        if shared.visible_floor.is_pending(generation) {
+            let _ = update_overlay_state(&app, shared, |state| state.visible = false);
+        }
+    });
+}
+
+#[derive(Default)]
+struct CapState {
+    visible_since: Option<Instant>,
+    /// Set once the cap fired; holds the overlay hidden until the sender re-arms it.
+    tripped: bool,
+    /// The sender asked for `visible: false` since the cap fired, so its next
+    /// `visible: true` is a fresh show.
+    rearmed: bool,
+}
+
+/// Force-hides the overlay once it has been visible continuously for `max_visible_ms`.
+#[derive(Default)]
+pub(crate) struct VisibleCap {
+    state: Mutex<CapState>,
+    generation: AtomicU64,
+}
+
+impl VisibleCap {
+    pub(crate) fn filter(
+        &self,
+        app: &AppHandle,
+        shared: &Arc<SharedOverlayState>,
+        next: &mut OverlayState,
+        cap: Duration,
+        visible_requested: bool,
+    ) {
+        let Ok(mut cap_state) = self.state.lock() else {
+            return;
+        };
+        if cap_state.tripped {
+            if visible_requested && !next.visible {
+                cap_state.rearmed = true;
+            } else if visible_requested && cap_state.rearmed {
+                cap_state.tripped = false;
+                cap_state.rearmed = false;
+            } else {
+                next.visible = false;
+            }
+        }
+        if !next.visible {
+            if cap_state.visible_since.take().is_some() {
+                self.cancel();
+            }
+        } else if cap_state.visible_since.is_none() {
+            cap_state.visible_since = Some(shared.scheduler.clock().now());
+            if cap.is_zero() {
+                self.cancel();
+            } else {
+                self.schedule(app, shared, cap);
+            }
+        }
+    }
+
+    /// Time left before the cap fires, or `None` while hidden or uncapped.
+    fn remaining(&self, cap: Duration, now: Instant) -> Option<Duration> {
+        if cap.is_zero() {
+            return None;
+        }
+        let since = self.state.lock().ok()?.visible_since?;
+        Some(cap.saturating_sub(now.duration_since(since)))
+    }
+
+    fn cancel(&self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn schedule(&self, app: &AppHandle, shared: &Arc<SharedOverlayState>, delay: Duration) {
+        let generation = self.generation.fetch_add(1, Ordering::Relaxed) + 1;
+        let app = app.clone();
+        shared.scheduler.after("visible_cap", delay, move |shared| {
+            let cap = &shared.visible_cap;
+            if cap.generation.load(Ordering::Relaxed) != generation {
+                return;
+            }
+            match cap.state.lock() {
+                Ok(mut cap_state) if cap_state.visible_since.is_some() => cap_state.tripped = true,
+                _ => return,
+            }
+            log::warn!("overlay visible longer than {} ms; force-hiding", delay.as_millis());
+            let _ = update_overlay_state(&app, shared, |state| state.visible = false);
+        });
+    }
+}
+
+#[tauri::command]
+pub(crate) fn set_max_visible_duration(
+    ms: u64,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    update_config(&app, shared.inner(), |config| config.max_visible_ms = ms)?;
+    let cap = Duration::from_millis(ms);
+    // Hold the state lock so the rescheduled timer can't race a visibility change.
+    let _state = lock_state(shared.inner())?;
+    match shared.visible_cap.remaining(cap, shared.scheduler.clock().now()) {
+        Some(remaining) => shared.visible_cap.schedule(&app, shared.inner(), remaining),
+        None => shared.visible_cap.cancel(),
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn set_min_visible_duration(
+    ms: u64,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if ms > MAX_MIN_VISIBLE_MS {
+        return Err(format!("minimum visible duration must be at most {} ms", MAX_MIN_VISIBLE_MS));
+    }
+    update_config(&app, shared.inner(), |config| config.min_visible_ms = ms)?;
+    Ok(())
+}
+
+/// Remaining visible time in ms for UI countdowns; `null` while hidden or uncapped.
+#[tauri::command]
+pub(crate) fn get_visible_time_remaining(shared: State<'_, Arc<SharedOverlayState>>) -> Result<Option<u64>, String> {
+    let cap = Duration::from_millis(lock_config(shared.inner())?.max_visible_ms);
+    Ok(shared
+        .visible_cap
+        .remaining(cap, shared.scheduler.clock().now())
+        .map(|remaining| remaining.as_millis() as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::VisibleFloor;
+    use crate::OverlayState;
+
+    fn with_visible(visible: bool) -> OverlayState {
+        OverlayState {
+            visible,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn early_hide_stays_up_for_the_minimum() {
+        let floor = VisibleFloor::default();
+        let min_visible = Duration::from_millis(500);
+        let shown_at = Instant::now();
+
+        let mut next = with_visible(true);
+        assert_eq!(floor.hold(&with_visible(false), &mut next, min_visible, true, shown_at), None);
+
+        let mut next = with_visible(false);
+        let hide_at = shown_at + Duration::from_millis(50);
+        let deferred = floor.hold(&with_visible(true), &mut next, min_visible, true, hide_at);
+        assert!(next.visible);
+        let (generation, delay) = deferred.expect("hide deferred");
+        assert_eq!(delay, Duration::from_millis(450));
+
+        // A level patch in between inherits `visible` and keeps the hide pending.
+        let mut next = with_visible(true);
+        floor.hold(&with_visible(true), &mut next, min_visible, false, shown_at + Duration::from_millis(300));
+        assert!(floor.is_pending(generation));
+
+        let mut next = with_visible(false);
+        floor.hold(&with_visible(true), &mut next, min_visible, false, shown_at + Duration::from_millis(500));
+        assert!(!next.visible);
+    }
+
+    #[test]
+    fn a_new_show_withdraws_the_deferred_hide() {
+        let floor = VisibleFloor::default();
+        let min_visible = Duration::from_millis(500);
+        let shown_at = Instant::now();
+        floor.hold(&with_visible(false), &mut with_visible(true), min_visible, true, shown_at);
+        let (generation, _) = floor
+            .hold(&with_visible(true), &mut with_visible(false), min_visible, true, shown_at)
+            .expect("hide deferred");
+
+        floor.hold(&with_visible(true), &mut with_visible(true), min_visible, true, shown_at);
+        assert!(!floor.is_pending(generation));
+    }
+}
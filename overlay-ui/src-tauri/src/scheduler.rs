@@ -0,0 +1,214 @@
+use std::{
+    collections::BTreeMap,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::{clock::SharedClock, SharedOverlayState};
+
+/// One animation frame; `advance_clock` moves time in steps of this so
+/// repeating tasks fire as often as they would in real time.
+pub(crate) const SCHEDULER_TICK: Duration = Duration::from_millis(16);
+
+type OnceTask = Box<dyn FnOnce(&Arc<SharedOverlayState>) + Send>;
+/// Returns the delay until its next call, or `None` when finished.
+type RepeatingTask = Box<dyn FnMut(&Arc<SharedOverlayState>) -> Option<Duration> + Send>;
+
+enum TaskKind {
+    Once(OnceTask),
+    Repeating(RepeatingTask),
+}
+
+struct Task {
+    name: &'static str,
+    due: Instant,
+    kind: TaskKind,
+}
+
+/// One ticker thread driving every delayed action and animation (decays,
+/// streams, sequences, debounces) instead of a thread per timer.
+pub(crate) struct Scheduler {
+    tasks: Mutex<Vec<Task>>,
+    wake: Condvar,
+    ticks: AtomicU64,
+    clock: SharedClock,
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::with_clock(SharedClock::default())
+    }
+}
+
+impl Scheduler {
+    pub(crate) fn with_clock(clock: SharedClock) -> Self {
+        Self {
+            tasks: Mutex::default(),
+            wake: Condvar::new(),
+            ticks: AtomicU64::new(0),
+            clock,
+        }
+    }
+
+    /// The time source task due times, and the heartbeat, are measured with.
+    pub(crate) fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.tasks.lock().map(|tasks| tasks.len()).unwrap_or(0)
+    }
+
+    /// Queues `task`, waking the ticker when it is due before everything queued.
+    fn push(&self, task: Task) {
+        let Ok(mut tasks) = self.tasks.lock() else {
+            return;
+        };
+        let earliest = tasks.iter().all(|queued| task.due < queued.due);
+        tasks.push(task);
+        drop(tasks);
+        if earliest {
+            self.wake.notify_one();
+        }
+    }
+
+    /// Runs `run` once on the scheduler thread after `delay`.
+    pub(crate) fn after(
+        &self,
+        name: &'static str,
+        delay: Duration,
+        run: impl FnOnce(&Arc<SharedOverlayState>) + Send + 'static,
+    ) {
+        self.push(Task {
+            name,
+            due: self.clock.now() + delay,
+            kind: TaskKind::Once(Box::new(run)),
+        });
+    }
+
+    /// Calls `tick` after `delay`, then again after each delay it returns, until it returns `None`.
+    pub(crate) fn repeat(
+        &self,
+        name: &'static str,
+        delay: Duration,
+        tick: impl FnMut(&Arc<SharedOverlayState>) -> Option<Duration> + Send + 'static,
+    ) {
+        self.push(Task {
+            name,
+            due: self.clock.now() + delay,
+            kind: TaskKind::Repeating(Box::new(tick)),
+        });
+    }
+
+    /// Parks until the earliest task is due by the clock, or until woken by an earlier one.
+    fn wait_until_due(&self) -> Option<()> {
+        let mut tasks = self.tasks.lock().ok()?;
+        loop {
+            let now = self.clock.now();
+            tasks = match tasks.iter().map(|task| task.due).min() {
+                None => self.wake.wait(tasks).ok()?,
+                Some(due) if due <= now => return Some(()),
+                Some(due) => self.wake.wait_timeout(tasks, due - now).ok()?.0,
+            };
+        }
+    }
+
+    /// Runs, once and without waiting, every task due by the clock; a test
+    /// advancing its own clock can call this in place of the ticker thread.
+    pub(crate) fn run_due(&self, shared: &Arc<SharedOverlayState>) -> Option<()> {
+        let due: Vec<Task> = {
+            let mut tasks = self.tasks.lock().ok()?;
+            let now = self.clock.now();
+            let (due, waiting) = std::mem::take(&mut *tasks).into_iter().partition(|task| task.due <= now);
+            *tasks = waiting;
+            due
+        };
+        run_tasks(shared, due);
+        Some(())
+    }
+}
+
+fn run_tasks(shared: &Arc<SharedOverlayState>, due: Vec<Task>) {
+    for task in due {
+        let name = task.name;
+        let next = catch_unwind(AssertUnwindSafe(|| match task.kind {
+            TaskKind::Once(run) => {
+                run(shared);
+                None
+            }
+            TaskKind::Repeating(mut tick) => tick(shared).map(|delay| (delay, tick)),
+        }));
+        match next {
+            Ok(Some((delay, tick))) => shared.scheduler.push(Task {
+                name,
+                due: shared.scheduler.clock.now() + delay,
+                kind: TaskKind::Repeating(tick),
+            }),
+            Ok(None) => {}
+            Err(_) => log::error!("scheduled task {} panicked", name),
+        }
+    }
+    shared.scheduler.ticks.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Sleeps until the next task is due, then runs everything due; parks while
+/// nothing is scheduled. Tasks run without the queue locked, so they may
+/// schedule more.
+pub(crate) fn start_scheduler(shared: Arc<SharedOverlayState>) {
+    thread::spawn(move || loop {
+        if shared.scheduler.wait_until_due().and_then(|_| shared.scheduler.run_due(&shared)).is_none() {
+            log::error!("scheduler queue lock poisoned; timers stopped");
+            return;
+        }
+    });
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct SchedulerStats {
+    /// Until the earliest pending task is due; `None` while parked.
+    next_due_ms: Option<u64>,
+    ticks: u64,
+    /// Pending one-shot delays.
+    timers: usize,
+    /// Running repeating tasks (decays, streams, sequences).
+    animations: usize,
+    /// Pending tasks per name.
+    tasks: BTreeMap<&'static str, usize>,
+}
+
+#[tauri::command]
+pub(crate) fn get_scheduler_stats(shared: State<'_, Arc<SharedOverlayState>>) -> Result<SchedulerStats, String> {
+    let tasks = shared
+        .scheduler
+        .tasks
+        .lock()
+        .map_err(|_| "scheduler lock poisoned".to_string())?;
+    let now = shared.scheduler.clock.now();
+    let mut stats = SchedulerStats {
+        next_due_ms: tasks
+            .iter()
+            .map(|task| task.due.saturating_duration_since(now).as_millis() as u64)
+            .min(),
+        ticks: shared.scheduler.ticks.load(Ordering::Relaxed),
+        timers: 0,
+        animations: 0,
+        tasks: BTreeMap::new(),
+    };
+    for task in tasks.iter() {
+        match task.kind {
+            TaskKind::Once(_) => stats.timers += 1,
+            TaskKind::Repeating(_) => stats.animations += 1,
+        }
+        *stats.tasks.entry(task.name).or_default() += 1;
+    }
+    Ok(stats)
+}
@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use crate::{
+    config::update_config,
+    timeline::{ChangeSource, SourceScope},
+    update_overlay_state, SharedOverlayState,
+};
+
+/// Hard ceiling on `max_segments`, and the cap before the configured one applies.
+pub(crate) const MAX_SEGMENTS: usize = 1_000;
+
+/// One finalized stretch of transcript, timed from the start of the recording.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct Segment {
+    pub(crate) text: String,
+    pub(crate) start_ms: u64,
+    pub(crate) end_ms: u64,
+}
+
+/// Appends to, or clears, the transcript without resending it.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "snake_case")]
+pub(crate) struct SegmentPatch {
+    /// Empties the transcript before appending.
+    clear: bool,
+    append: Vec<Segment>,
+}
+
+impl SegmentPatch {
+    pub(crate) fn apply(self, segments: &mut Vec<Segment>) {
+        if self.clear {
+            segments.clear();
+        }
+        segments.extend(self.append);
+        sanitize_segments(segments, MAX_SEGMENTS);
+    }
+}
+
+/// Drops segments that end before they start, then the oldest beyond `cap`.
+pub(crate) fn sanitize_segments(segments: &mut Vec<Segment>, cap: usize) {
+    segments.retain(|segment| segment.end_ms >= segment.start_ms);
+    if segments.len() > cap {
+        segments.drain(..segments.len() - cap);
+    }
+}
+
+/// Applies a `segment_patch` as a command, for embedders without a bridge sender.
+#[tauri::command]
+pub(crate) fn apply_segment_patch(
+    patch: SegmentPatch,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    let _source = SourceScope::enter(ChangeSource::Command);
+    update_overlay_state(&app, shared.inner(), |state| patch.apply(&mut state.segments))
+}
+
+/// Sets how many segments the transcript keeps; older ones are evicted first.
+#[tauri::command]
+pub(crate) fn set_max_segments(
+    count: usize,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if !(1..=MAX_SEGMENTS).contains(&count) {
+        return Err(format!("segment cap must be 1-{}", MAX_SEGMENTS));
+    }
+    update_config(&app, shared.inner(), |config| config.max_segments = count)?;
+    // Evict right away when the cap shrank.
+    update_overlay_state(&app, shared.inner(), |_| {})
+}
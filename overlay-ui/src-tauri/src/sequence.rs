@@ -0,0 +1,175 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use crate::{
+    apply_overlay_patch, config::update_config, lock_config, replace_overlay_state,
+    OverlayPatch, OverlayState, SharedOverlayState,
+};
+
+const MAX_SEQUENCE_STEPS: usize = 100;
+const MAX_HOLD_MS: u64 = 60_000;
+const MAX_QUEUED_UPDATES: usize = 256;
+
+/// What happens to bridge datagrams that arrive while a sequence is playing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SequenceConflict {
+    /// Cancel the sequence and apply the datagram.
+    #[default]
+    Interrupt,
+    /// Hold datagrams and apply them, in order, once the sequence ends.
+    Queue,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct SequenceStep {
+    state: OverlayPatch,
+    hold_ms: u64,
+}
+
+/// A decoded bridge datagram.
+pub(crate) enum BridgeUpdate {
+    State(OverlayState),
+    Patch(OverlayPatch),
+}
+
+impl BridgeUpdate {
+    pub(crate) fn apply(self, app: &AppHandle, shared: &Arc<SharedOverlayState>) -> Result<(), String> {
+        match self {
+            BridgeUpdate::State(next) => replace_overlay_state(app, shared, next),
+            BridgeUpdate::Patch(patch) => apply_overlay_patch(app, shared, patch),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Playback {
+    /// Generation of the sequence currently playing, if any.
+    active: Option<u64>,
+    queued: VecDeque<BridgeUpdate>,
+}
+
+#[derive(Default)]
+pub(crate) struct Sequencer {
+    playback: Mutex<Playback>,
+    generation: AtomicU64,
+}
+
+impl Sequencer {
+    pub(crate) fn queued_len(&self) -> usize {
+        self.playback.lock().map(|playback| playback.queued.len()).unwrap_or(0)
+    }
+
+    /// Returns `update` when it should be applied now, or `None` if the sequence queued it.
+    pub(crate) fn intercept(&self, conflict: SequenceConflict, update: BridgeUpdate) -> Option<BridgeUpdate> {
+        let Ok(mut playback) = self.playback.lock() else {
+            return Some(update);
+        };
+        playback.active?;
+        match conflict {
+            SequenceConflict::Interrupt => {
+                log::info!("bridge update interrupted the playing sequence");
+                playback.active = None;
+                self.generation.fetch_add(1, Ordering::Relaxed);
+                Some(update)
+            }
+            SequenceConflict::Queue => {
+                if playback.queued.len() == MAX_QUEUED_UPDATES {
+                    playback.queued.pop_front();
+                }
+                playback.queued.push_back(update);
+                None
+            }
+        }
+    }
+
+    fn is_current(&self, generation: u64) -> bool {
+        self.playback
+            .lock()
+            .map(|playback| playback.active == Some(generation))
+            .unwrap_or(false)
+    }
+
+    /// Ends `generation` (or whatever is playing, if `None`) and applies any queued updates.
+    fn finish(&self, app: &AppHandle, shared: &Arc<SharedOverlayState>, generation: Option<u64>) {
+        let queued = match self.playback.lock() {
+            Ok(mut playback) if generation.is_none() || playback.active == generation => {
+                playback.active = None;
+                std::mem::take(&mut playback.queued)
+            }
+            _ => return,
+        };
+        self.generation.fetch_add(1, Ordering::Relaxed);
+        for update in queued {
+            let _ = update.apply(app, shared);
+        }
+    }
+}
+
+/// Applies each step's `state` patch and holds it for `hold_ms`, replacing any playing sequence.
+#[tauri::command]
+pub(crate) fn play_sequence(
+    steps: Vec<SequenceStep>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if steps.is_empty() || steps.len() > MAX_SEQUENCE_STEPS {
+        return Err(format!("a sequence needs 1 to {} steps", MAX_SEQUENCE_STEPS));
+    }
+    if steps.iter().any(|step| step.hold_ms > MAX_HOLD_MS) {
+        return Err(format!("hold_ms must be at most {}", MAX_HOLD_MS));
+    }
+    let shared = shared.inner().clone();
+    let generation = shared.sequencer.generation.fetch_add(1, Ordering::Relaxed) + 1;
+    shared
+        .sequencer
+        .playback
+        .lock()
+        .map_err(|_| "sequence lock poisoned".to_string())?
+        .active = Some(generation);
+    let mut steps = steps.into_iter();
+    shared.scheduler.repeat("sequence", Duration::ZERO, move |shared| {
+        if !shared.sequencer.is_current(generation) {
+            return None;
+        }
+        let Some(step) = steps.next() else {
+            shared.sequencer.finish(&app, shared, Some(generation));
+            return None;
+        };
+        let _ = apply_overlay_patch(&app, shared, step.state);
+        Some(Duration::from_millis(step.hold_ms))
+    });
+    Ok(())
+}
+
+/// Stops the playing sequence, leaving its last applied state, and flushes queued updates.
+#[tauri::command]
+pub(crate) fn cancel_sequence(app: AppHandle, shared: State<'_, Arc<SharedOverlayState>>) {
+    shared.sequencer.finish(&app, shared.inner(), None);
+}
+
+#[tauri::command]
+pub(crate) fn set_sequence_conflict(
+    conflict: SequenceConflict,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    update_config(&app, shared.inner(), |config| config.sequence_conflict = conflict)?;
+    Ok(())
+}
+
+pub(crate) fn sequence_conflict(shared: &Arc<SharedOverlayState>) -> SequenceConflict {
+    lock_config(shared)
+        .map(|config| config.sequence_conflict)
+        .unwrap_or_default()
+}
@@ -0,0 +1,125 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use crate::{
+    callback::LAST_SENDER, config::update_config, lock_config, mark_visible_requested,
+    update_overlay_state, OverlayState, SharedOverlayState,
+};
+
+const SESSION_LOCK_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What the overlay does while the Windows session is locked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum LockBehavior {
+    /// Keep the overlay hidden; bridge updates still apply.
+    #[default]
+    Hide,
+    /// Keep it hidden and drop bridge updates until unlock.
+    Pause,
+    Ignore,
+}
+
+#[derive(Default)]
+pub(crate) struct SessionLock {
+    locked: AtomicBool,
+    /// The `visible` senders asked for while it was suppressed, restored on unlock.
+    wanted_visible: AtomicBool,
+}
+
+impl SessionLock {
+    /// Forces the overlay hidden while the session is locked.
+    pub(crate) fn filter(&self, behavior: LockBehavior, next: &mut OverlayState) {
+        if behavior == LockBehavior::Ignore || !self.locked.load(Ordering::Relaxed) {
+            return;
+        }
+        self.wanted_visible.store(next.visible, Ordering::Relaxed);
+        next.visible = false;
+    }
+
+    /// True when bridge updates should be dropped.
+    pub(crate) fn pauses_bridge(&self, behavior: LockBehavior) -> bool {
+        behavior == LockBehavior::Pause && self.locked.load(Ordering::Relaxed)
+    }
+}
+
+/// The input desktop can't be opened while the lock screen (or UAC prompt) has it.
+#[cfg(windows)]
+fn session_locked() -> bool {
+    use windows_sys::Win32::System::StationsAndDesktops::{CloseDesktop, OpenInputDesktop, DESKTOP_SWITCHDESKTOP};
+
+    // SAFETY: plain query; a non-null desktop handle is closed right away.
+    unsafe {
+        let desktop = OpenInputDesktop(0, 0, DESKTOP_SWITCHDESKTOP);
+        if desktop.is_null() {
+            return true;
+        }
+        CloseDesktop(desktop);
+        false
+    }
+}
+
+#[cfg(not(windows))]
+fn session_locked() -> bool {
+    false
+}
+
+fn set_locked(app: &AppHandle, shared: &Arc<SharedOverlayState>, locked: bool) {
+    let behavior = match lock_config(shared) {
+        Ok(config) => config.lock_behavior,
+        Err(_) => return,
+    };
+    if locked {
+        log::info!("session locked; overlay lock behavior is {:?}", behavior);
+        shared.session_lock.locked.store(true, Ordering::Relaxed);
+        let _ = update_overlay_state(app, shared, |_| {});
+        return;
+    }
+    log::info!("session unlocked");
+    shared.session_lock.locked.store(false, Ordering::Relaxed);
+    if behavior == LockBehavior::Ignore {
+        return;
+    }
+    let visible = shared.session_lock.wanted_visible.load(Ordering::Relaxed);
+    let _ = update_overlay_state(app, shared, |state| {
+        state.visible = visible;
+        mark_visible_requested();
+    });
+    if behavior == LockBehavior::Pause {
+        // Updates were dropped meanwhile; ask the sender for its current state.
+        shared
+            .visibility_callback
+            .send(LAST_SENDER, &serde_json::json!({ "event": "resync" }).to_string());
+    }
+}
+
+/// Polls for lock and unlock; Tauri doesn't expose the window messages that
+/// carry session change notifications.
+pub(crate) fn start_session_lock_watch(app: AppHandle, shared: Arc<SharedOverlayState>) {
+    thread::spawn(move || loop {
+        thread::sleep(SESSION_LOCK_CHECK_INTERVAL);
+        let locked = session_locked();
+        if shared.session_lock.locked.load(Ordering::Relaxed) != locked {
+            set_locked(&app, &shared, locked);
+        }
+    });
+}
+
+#[tauri::command]
+pub(crate) fn set_lock_behavior(
+    behavior: LockBehavior,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    update_config(&app, shared.inner(), |config| config.lock_behavior = behavior)?;
+    Ok(())
+}
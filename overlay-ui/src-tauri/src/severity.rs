@@ -0,0 +1,295 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::{
+    config::update_config, mark_visible_requested, timeline::{ChangeSource, SourceScope}, unix_millis,
+    update_overlay_state, OverlayState, SharedOverlayState,
+};
+
+const ERROR: &str = "error";
+const OFFLINE: &str = "offline";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Severity {
+    Info,
+    Error,
+}
+
+/// A message is an error while `listening` or `processing` reports one, or the
+/// backend is offline.
+pub(crate) fn message_severity(state: &OverlayState) -> Severity {
+    if state.listening == ERROR || state.processing == ERROR || state.connection == OFFLINE {
+        Severity::Error
+    } else {
+        Severity::Info
+    }
+}
+
+/// True when `next` carries a message that just settled: newly set, or a stream that completed.
+pub(crate) fn message_settled(previous: &OverlayState, next: &OverlayState) -> bool {
+    !next.streaming
+        && next.message.as_deref().is_some_and(|text| !text.trim().is_empty())
+        && (previous.message != next.message || previous.streaming)
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "snake_case")]
+pub(crate) struct SeverityAction {
+    /// Show the overlay when such a message is set.
+    force_visible: bool,
+    /// Clear the message this long after it settles; 0 keeps it until replaced.
+    auto_clear_ms: u64,
+    /// Flash for attention until the message changes, subject to the attention cooldown.
+    attention: bool,
+    /// Emit `overlay://toast` with the message.
+    toast: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+struct Toast<'a> {
+    text: &'a str,
+    severity: Severity,
+}
+
+/// What happens when a message of each severity is set. The default does
+/// nothing extra: one persistent message.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "snake_case")]
+pub(crate) struct SeverityPolicy {
+    info: SeverityAction,
+    error: SeverityAction,
+}
+
+impl SeverityPolicy {
+    fn action(&self, severity: Severity) -> SeverityAction {
+        match severity {
+            Severity::Info => self.info,
+            Severity::Error => self.error,
+        }
+    }
+}
+
+/// Applies the severity policy to each message that settles.
+#[derive(Default)]
+pub(crate) struct MessageAutoClear {
+    generation: AtomicU64,
+    /// Set while the current message flashes.
+    flashing: AtomicBool,
+}
+
+impl MessageAutoClear {
+    pub(crate) fn apply(
+        &self,
+        app: &AppHandle,
+        shared: &Arc<SharedOverlayState>,
+        policy: &SeverityPolicy,
+        attention_cooldown: Duration,
+        previous: &OverlayState,
+        next: &mut OverlayState,
+    ) {
+        if previous.message != next.message {
+            self.generation.fetch_add(1, Ordering::Relaxed);
+            self.flashing.store(false, Ordering::Relaxed);
+        }
+        self.settle(app, shared, policy, attention_cooldown, previous, next);
+        next.attention |= self.flashing.load(Ordering::Relaxed);
+    }
+
+    fn settle(
+        &self,
+        app: &AppHandle,
+        shared: &Arc<SharedOverlayState>,
+        policy: &SeverityPolicy,
+        attention_cooldown: Duration,
+        previous: &OverlayState,
+        next: &mut OverlayState,
+    ) {
+        if !message_settled(previous, next) || shared.last_error.is_replaying() || shared.message_ack.is_pending() {
+            return;
+        }
+        let severity = message_severity(next);
+        let action = policy.action(severity);
+        if action.force_visible {
+            next.visible = true;
+        }
+        // Shares the cooldown with held messages and connection behaviors, so a burst flashes once.
+        if action.attention && shared.message_ack.admit_flash(attention_cooldown, shared.scheduler.clock().now()) {
+            self.flashing.store(true, Ordering::Relaxed);
+        }
+        if let (true, Some(text)) = (action.toast, next.message.as_deref()) {
+            let _ = app.emit("overlay://toast", Toast { text, severity });
+        }
+        if action.auto_clear_ms > 0 {
+            let app = app.clone();
+            self.schedule_clear(shared, Duration::from_millis(action.auto_clear_ms), move |shared| {
+                let _ = update_overlay_state(&app, shared, |state| state.message = None);
+            });
+        }
+    }
+
+    /// Runs `clear` after `delay` unless the message changes first.
+    fn schedule_clear(
+        &self,
+        shared: &Arc<SharedOverlayState>,
+        delay: Duration,
+        clear: impl FnOnce(&Arc<SharedOverlayState>) + Send + 'static,
+    ) {
+        let generation = self.generation.fetch_add(1, Ordering::Relaxed) + 1;
+        shared.scheduler.after("message_auto_clear", delay, move |shared| {
+            if shared.message_auto_clear.generation.load(Ordering::Relaxed) == generation {
+                clear(shared);
+            }
+        });
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct LastError {
+    message: String,
+    timestamp: u64,
+}
+
+/// The last error-severity message, kept for `show_last_error` until dismissed.
+#[derive(Default)]
+pub(crate) struct LastErrorReplay {
+    last: Mutex<Option<LastError>>,
+    /// Set while `show_last_error` holds the message on screen.
+    replaying: AtomicBool,
+}
+
+impl LastErrorReplay {
+    pub(crate) fn is_replaying(&self) -> bool {
+        self.replaying.load(Ordering::Relaxed)
+    }
+
+    /// Records settled error messages and, during a replay, keeps the overlay
+    /// shown until the message is replaced.
+    pub(crate) fn track(&self, previous: &OverlayState, next: &mut OverlayState) {
+        let Ok(mut last) = self.last.lock() else {
+            return;
+        };
+        if self.is_replaying() {
+            let replayed = last.as_ref().map(|error| error.message.as_str());
+            if next.message.as_deref() == replayed {
+                next.visible = true;
+                return;
+            }
+            self.replaying.store(false, Ordering::Relaxed);
+        }
+        if message_settled(previous, next) && message_severity(next) == Severity::Error {
+            *last = next.message.clone().map(|message| LastError {
+                message,
+                timestamp: unix_millis(),
+            });
+        }
+    }
+
+    pub(crate) fn clear(&self) {
+        self.replaying.store(false, Ordering::Relaxed);
+        if let Ok(mut last) = self.last.lock() {
+            *last = None;
+        }
+    }
+}
+
+#[tauri::command]
+pub(crate) fn get_last_error(shared: State<'_, Arc<SharedOverlayState>>) -> Result<Option<LastError>, String> {
+    let last = shared
+        .last_error
+        .last
+        .lock()
+        .map_err(|_| "last error lock poisoned".to_string())?;
+    Ok(last.clone())
+}
+
+/// Shows the last error message again, visible and exempt from auto-clear
+/// until it is replaced or dismissed.
+#[tauri::command]
+pub(crate) fn show_last_error(app: AppHandle, shared: State<'_, Arc<SharedOverlayState>>) -> Result<(), String> {
+    let message = shared
+        .last_error
+        .last
+        .lock()
+        .map_err(|_| "last error lock poisoned".to_string())?
+        .as_ref()
+        .map(|error| error.message.clone())
+        .ok_or_else(|| "no error message to show".to_string())?;
+    shared.last_error.replaying.store(true, Ordering::Relaxed);
+    let _source = SourceScope::enter(ChangeSource::Command);
+    update_overlay_state(&app, shared.inner(), |state| {
+        state.message = Some(message);
+        state.visible = true;
+        mark_visible_requested();
+    })
+}
+
+#[tauri::command]
+pub(crate) fn set_severity_policy(
+    policy: SeverityPolicy,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    update_config(&app, shared.inner(), |config| config.severity_policy = policy)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use super::{message_severity, Severity};
+    use crate::{
+        clock::{Clock, MockClock, SharedClock},
+        lock_state,
+        scheduler::Scheduler,
+        OverlayState, SharedOverlayState,
+    };
+
+    #[test]
+    fn listening_processing_and_offline_are_errors() {
+        let state = |edit: fn(&mut OverlayState)| {
+            let mut state = OverlayState::default();
+            edit(&mut state);
+            message_severity(&state)
+        };
+        assert_eq!(state(|_| {}), Severity::Info);
+        assert_eq!(state(|state| state.listening = "error".to_string()), Severity::Error);
+        assert_eq!(state(|state| state.processing = "error".to_string()), Severity::Error);
+        assert_eq!(state(|state| state.connection = "offline".to_string()), Severity::Error);
+        assert_eq!(state(|state| state.connection = "reconnecting".to_string()), Severity::Info);
+    }
+
+    #[test]
+    fn advancing_the_clock_auto_clears_the_message() {
+        let clock = MockClock::new();
+        let shared = Arc::new(SharedOverlayState {
+            scheduler: Scheduler::with_clock(SharedClock::from(clock.clone() as Arc<dyn Clock>)),
+            ..Default::default()
+        });
+        lock_state(&shared).unwrap().message = Some("Saved".to_string());
+        shared
+            .message_auto_clear
+            .schedule_clear(&shared, Duration::from_millis(3_000), |shared| {
+                lock_state(shared).unwrap().message = None;
+            });
+
+        clock.advance(Duration::from_millis(2_999));
+        shared.scheduler.run_due(&shared);
+        assert_eq!(lock_state(&shared).unwrap().message.as_deref(), Some("Saved"));
+
+        clock.advance(Duration::from_millis(1));
+        shared.scheduler.run_due(&shared);
+        assert_eq!(lock_state(&shared).unwrap().message, None);
+    }
+}
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::{
+    config::OverlayConfig, lock_config, lock_state, metrics::BridgeMetricsSnapshot, unix_millis, OverlayState,
+    SharedOverlayState, UDP_ADDR,
+};
+
+/// Bumped whenever the snapshot layout changes incompatibly.
+const SNAPSHOT_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct BridgeStatus {
+    address: &'static str,
+    bound: bool,
+}
+
+/// Everything the backend manages, captured together for support dumps.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct FullSnapshot {
+    schema_version: u32,
+    app_version: &'static str,
+    captured_at: u64,
+    state: OverlayState,
+    config: OverlayConfig,
+    metrics: BridgeMetricsSnapshot,
+    bridge: BridgeStatus,
+}
+
+/// Holds the state and config locks while reading, so the parts don't tear.
+#[tauri::command]
+pub(crate) fn get_full_snapshot(shared: State<'_, Arc<SharedOverlayState>>) -> Result<FullSnapshot, String> {
+    let state = lock_state(shared.inner())?;
+    let config = lock_config(shared.inner())?;
+    let metrics = shared.metrics.snapshot();
+    Ok(FullSnapshot {
+        schema_version: SNAPSHOT_SCHEMA_VERSION,
+        app_version: env!("CARGO_PKG_VERSION"),
+        captured_at: unix_millis(),
+        bridge: BridgeStatus {
+            address: UDP_ADDR,
+            bound: metrics.bound,
+        },
+        state: state.clone(),
+        config: config.clone(),
+        metrics,
+    })
+}
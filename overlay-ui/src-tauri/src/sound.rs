@@ -0,0 +1,121 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tauri::{AppHandle, State};
+
+use crate::{config::update_config, OverlayConfig, OverlayState, SharedOverlayState};
+
+const MIN_CUE_INTERVAL: Duration = Duration::from_millis(150);
+#[cfg(feature = "audio")]
+const CUE_VOLUME: f32 = 0.2;
+
+/// Bundled cues as `(frequency_hz, duration_ms)` tone sequences.
+const CUES: &[(&str, &[(f32, u64)])] = &[
+    ("start", &[(660.0, 70), (880.0, 90)]),
+    ("stop", &[(880.0, 70), (660.0, 90)]),
+    ("done", &[(784.0, 80), (1047.0, 120)]),
+    ("error", &[(330.0, 140), (262.0, 200)]),
+];
+
+fn cue_tones(name: &str) -> Option<&'static [(f32, u64)]> {
+    CUES.iter()
+        .find(|(cue, _)| *cue == name)
+        .map(|(_, tones)| *tones)
+}
+
+/// Plays state-transition cues, rate-limited so bursts don't stutter.
+#[derive(Default)]
+pub(crate) struct SoundCues {
+    last_played: Mutex<Option<Instant>>,
+}
+
+impl SoundCues {
+    /// Consumes a one-shot `sound` trigger from `next` and plays it, or an
+    /// automatic cue for a `processing` transition to `done`/`error`.
+    pub(crate) fn trigger(&self, config: &OverlayConfig, previous: &OverlayState, next: &mut OverlayState, now: Instant) {
+        let requested = next.sound.take();
+        if !config.sound_enabled {
+            return;
+        }
+        let automatic = (config.auto_sound_cues && next.processing != previous.processing)
+            .then_some(next.processing.as_str())
+            .filter(|processing| matches!(*processing, "done" | "error"));
+        let Some(name) = requested.as_deref().or(automatic) else {
+            return;
+        };
+        let Some(tones) = cue_tones(name) else {
+            log::warn!("ignored unknown sound cue {:?}", name);
+            return;
+        };
+        let Ok(mut last_played) = self.last_played.lock() else {
+            return;
+        };
+        if last_played.is_some_and(|at| now.duration_since(at) < MIN_CUE_INTERVAL) {
+            return;
+        }
+        *last_played = Some(now);
+        play_tones(tones);
+    }
+}
+
+#[cfg(feature = "audio")]
+fn play_tones(tones: &'static [(f32, u64)]) {
+    use rodio::{
+        source::{SineWave, Source},
+        OutputStream, Sink,
+    };
+
+    // The output stream isn't Send, so each cue owns a short-lived thread.
+    std::thread::spawn(move || {
+        let (_stream, handle) = match OutputStream::try_default() {
+            Ok(output) => output,
+            Err(error) => {
+                log::warn!("no audio output for sound cue: {}", error);
+                return;
+            }
+        };
+        let sink = match Sink::try_new(&handle) {
+            Ok(sink) => sink,
+            Err(error) => {
+                log::warn!("failed to open audio sink: {}", error);
+                return;
+            }
+        };
+        for &(frequency, duration_ms) in tones {
+            sink.append(
+                SineWave::new(frequency)
+                    .take_duration(Duration::from_millis(duration_ms))
+                    .amplify(CUE_VOLUME),
+            );
+        }
+        sink.sleep_until_end();
+    });
+}
+
+#[cfg(not(feature = "audio"))]
+fn play_tones(_tones: &'static [(f32, u64)]) {
+    log::debug!("sound cue skipped: built without the `audio` feature");
+}
+
+#[tauri::command]
+pub(crate) fn set_sound_enabled(
+    enabled: bool,
+    auto_cues: Option<bool>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    update_config(&app, shared.inner(), |config| {
+        config.sound_enabled = enabled;
+        if let Some(auto_cues) = auto_cues {
+            config.auto_sound_cues = auto_cues;
+        }
+    })?;
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn list_sound_cues() -> Vec<&'static str> {
+    CUES.iter().map(|(name, _)| *name).collect()
+}
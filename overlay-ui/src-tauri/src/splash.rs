@@ -0,0 +1,95 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use serde_json::{Map, Value};
+use tauri::{AppHandle, State};
+
+use crate::{
+    config::{update_config, OverlayConfig},
+    decode_patch, lock_state, update_overlay_state, OverlayState, SharedOverlayState,
+};
+
+const MAX_SPLASH_TIMEOUT_MS: u64 = 60_000;
+
+/// The startup splash, shown until the first bridge update or `splash_timeout_ms`.
+#[derive(Default)]
+pub(crate) struct Splash {
+    /// The state the splash was laid over, put back when it ends.
+    underneath: Mutex<Option<OverlayState>>,
+}
+
+impl Splash {
+    /// Lays the configured splash over `state` at startup; called before the first emit.
+    pub(crate) fn show(&self, app: &AppHandle, shared: &Arc<SharedOverlayState>, config: &OverlayConfig) {
+        let Some(fields) = &config.splash_state else {
+            return;
+        };
+        let patch = match decode_patch(&Value::Object(fields.clone()).to_string()) {
+            Ok(patch) => patch,
+            Err(error) => {
+                log::warn!("ignored invalid splash state: {}", error);
+                return;
+            }
+        };
+        let (Ok(mut state), Ok(mut underneath)) = (lock_state(shared), self.underneath.lock()) else {
+            return;
+        };
+        *underneath = Some(state.clone());
+        patch.apply(&mut state);
+        let app = app.clone();
+        let timeout = Duration::from_millis(config.splash_timeout_ms);
+        shared.scheduler.after("splash_timeout", timeout, move |shared| {
+            if let Some(restored) = shared.splash.take() {
+                let _ = update_overlay_state(&app, shared, |state| restore(state, restored));
+            }
+        });
+    }
+
+    fn take(&self) -> Option<OverlayState> {
+        self.underneath.lock().ok()?.take()
+    }
+
+    /// Drops the splash without emitting, so the bridge update about to be applied replaces it in one emit.
+    pub(crate) fn end_for_update(&self, shared: &Arc<SharedOverlayState>) {
+        if let Some(restored) = self.take() {
+            if let Ok(mut state) = lock_state(shared) {
+                restore(&mut state, restored);
+            }
+        }
+    }
+}
+
+fn restore(state: &mut OverlayState, mut restored: OverlayState) {
+    restored.keep_backend_fields(state);
+    *state = restored;
+}
+
+/// Sets the patch fields shown from launch until the first bridge update or
+/// the timeout; takes effect at the next start. `None` shows the plain defaults.
+#[tauri::command]
+pub(crate) fn set_splash_state(
+    fields: Option<Map<String, Value>>,
+    timeout_ms: Option<u64>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if let Some(fields) = &fields {
+        decode_patch(&Value::Object(fields.clone()).to_string())
+            .map_err(|error| format!("invalid splash state: {}", error))?;
+    }
+    if timeout_ms.is_some_and(|ms| ms == 0 || ms > MAX_SPLASH_TIMEOUT_MS) {
+        return Err(format!(
+            "splash timeout must be between 1 and {} ms",
+            MAX_SPLASH_TIMEOUT_MS
+        ));
+    }
+    update_config(&app, shared.inner(), |config| {
+        config.splash_state = fields;
+        if let Some(ms) = timeout_ms {
+            config.splash_timeout_ms = ms;
+        }
+    })?;
+    Ok(())
+}
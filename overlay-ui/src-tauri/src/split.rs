@@ -0,0 +1,47 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use crate::{
+    layout::apply_overlay_layout, lock_config, main_window, position::PositionTrigger, theme::SystemTheme,
+    update_overlay_state, SharedOverlayState,
+};
+
+/// Themes for the two halves of an A/B comparison, left then right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct SplitThemes {
+    left: SystemTheme,
+    right: SystemTheme,
+}
+
+/// Whether the window is widened to hold both halves; read by the layout.
+#[derive(Default)]
+pub(crate) struct SplitView {
+    active: AtomicBool,
+}
+
+impl SplitView {
+    pub(crate) fn is_active(&self) -> bool {
+        self.active.load(Ordering::Relaxed)
+    }
+}
+
+/// Renders the state twice side by side, one half per theme, in a window
+/// twice as wide; `None` restores the single view. Not saved in config.
+#[tauri::command]
+pub(crate) fn set_split_view(
+    themes: Option<(SystemTheme, SystemTheme)>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    let themes = themes.map(|(left, right)| SplitThemes { left, right });
+    shared.split_view.active.store(themes.is_some(), Ordering::Relaxed);
+    let config = lock_config(shared.inner())?.clone();
+    apply_overlay_layout(&main_window(&app)?, &config, PositionTrigger::Layout).map_err(|error| error.to_string())?;
+    update_overlay_state(&app, shared.inner(), |state| state.split_view = themes)
+}
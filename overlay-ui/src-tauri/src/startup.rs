@@ -0,0 +1,69 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::SharedOverlayState;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum StartupMilestone {
+    BuilderStart,
+    SetupStart,
+    WindowAcquired,
+    Positioned,
+    BridgeBound,
+    FirstEmit,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct StartupTiming {
+    milestone: StartupMilestone,
+    /// Since the shared state was created, just before the Tauri builder runs.
+    elapsed_ms: f64,
+}
+
+/// Cold-start milestones, each recorded the first time it is reached.
+pub(crate) struct StartupTimings {
+    started: Instant,
+    reached: Mutex<Vec<StartupTiming>>,
+}
+
+impl Default for StartupTimings {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            reached: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl StartupTimings {
+    /// Logged at `info`; milestones before the logger is installed are only kept.
+    pub(crate) fn record(&self, milestone: StartupMilestone) {
+        let Ok(mut reached) = self.reached.lock() else {
+            return;
+        };
+        if reached.iter().any(|timing| timing.milestone == milestone) {
+            return;
+        }
+        let elapsed_ms = self.started.elapsed().as_secs_f64() * 1_000.0;
+        log::info!("startup: {:?} after {:.1} ms", milestone, elapsed_ms);
+        reached.push(StartupTiming { milestone, elapsed_ms });
+    }
+}
+
+/// Startup milestones reached so far, in the order they were reached.
+#[tauri::command]
+pub(crate) fn get_startup_timings(shared: State<'_, Arc<SharedOverlayState>>) -> Result<Vec<StartupTiming>, String> {
+    shared
+        .startup_timings
+        .reached
+        .lock()
+        .map(|reached| reached.clone())
+        .map_err(|_| "startup timings lock poisoned".to_string())
+}
@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use crate::{config::update_config, lock_config, lock_state, OverlayState, SharedOverlayState};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum StateLineField {
+    Conn,
+    Listen,
+    Proc,
+    Lvl,
+    Tgt,
+    Vis,
+    Msg,
+    Conf,
+}
+
+impl StateLineField {
+    fn key(self) -> &'static str {
+        match self {
+            StateLineField::Conn => "conn",
+            StateLineField::Listen => "listen",
+            StateLineField::Proc => "proc",
+            StateLineField::Lvl => "lvl",
+            StateLineField::Tgt => "tgt",
+            StateLineField::Vis => "vis",
+            StateLineField::Msg => "msg",
+            StateLineField::Conf => "conf",
+        }
+    }
+}
+
+pub(crate) fn default_state_line_fields() -> Vec<StateLineField> {
+    use StateLineField::*;
+    vec![Conn, Listen, Proc, Lvl, Tgt, Vis]
+}
+
+/// Quotes values that would otherwise break `key=value` splitting.
+fn push_value(line: &mut String, value: &str) {
+    if value.is_empty() || value.contains(|c: char| c.is_whitespace() || c == '=' || c == '"') {
+        line.push_str(&format!("{:?}", value));
+    } else {
+        line.push_str(value);
+    }
+}
+
+/// Renders `state` as one `key=value` line, e.g.
+/// `conn=online listen=listening proc=idle lvl=0.42 tgt=selected vis=1`.
+pub(crate) fn format_state_line(state: &OverlayState, fields: &[StateLineField]) -> String {
+    let mut line = String::new();
+    for field in fields {
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(field.key());
+        line.push('=');
+        match field {
+            StateLineField::Conn => push_value(&mut line, &state.connection),
+            StateLineField::Listen => push_value(&mut line, &state.listening),
+            StateLineField::Proc => push_value(&mut line, &state.processing),
+            StateLineField::Lvl => line.push_str(&format!("{:.2}", state.level)),
+            StateLineField::Tgt => push_value(&mut line, &state.target),
+            StateLineField::Vis => line.push(if state.visible { '1' } else { '0' }),
+            StateLineField::Msg => push_value(&mut line, state.message.as_deref().unwrap_or_default()),
+            StateLineField::Conf => match state.confidence {
+                Some(confidence) => line.push_str(&format!("{:.2}", confidence)),
+                None => line.push('-'),
+            },
+        }
+    }
+    line
+}
+
+#[tauri::command]
+pub(crate) fn get_state_line(shared: State<'_, Arc<SharedOverlayState>>) -> Result<String, String> {
+    let state = lock_state(shared.inner())?;
+    let config = lock_config(shared.inner())?;
+    Ok(format_state_line(&state, &config.state_line_fields))
+}
+
+/// Turns the per-emit `info` state line on or off; `fields` replaces the field list when given.
+#[tauri::command]
+pub(crate) fn set_state_line_logging(
+    enabled: bool,
+    fields: Option<Vec<StateLineField>>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if fields.as_ref().is_some_and(|fields| fields.is_empty()) {
+        return Err("state line needs at least one field".to_string());
+    }
+    update_config(&app, shared.inner(), |config| {
+        config.state_line_logging = enabled;
+        if let Some(fields) = fields {
+            config.state_line_fields = fields;
+        }
+    })?;
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, LogicalSize, PhysicalPosition, PhysicalSize, State, WebviewWindow};
+
+use crate::{
+    config::{update_config, OverlayConfig},
+    current_or_primary_monitor,
+    layout::apply_overlay_layout,
+    lock_config, main_window,
+    position::PositionTrigger,
+    update_overlay_state, OverlayState, SharedOverlayState,
+};
+
+pub(crate) const DOT_SIZE: f64 = 20.0;
+/// Gap between the dot and the monitor's work area edges.
+const DOT_MARGIN: f64 = 12.0;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum DotCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+/// Whether the window is currently shrunk to the status dot; read by the
+/// layout, which can run while the state lock is held.
+#[derive(Default)]
+pub(crate) struct StatusDot {
+    active: AtomicBool,
+}
+
+impl StatusDot {
+    pub(crate) fn is_active(&self) -> bool {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    /// Derives `status_dot` from `visible` and resizes the window when it flips.
+    pub(crate) fn follow_visible(
+        &self,
+        app: &AppHandle,
+        shared: &Arc<SharedOverlayState>,
+        config: &OverlayConfig,
+        next: &mut OverlayState,
+    ) {
+        next.status_dot = config.status_dot.is_some() && !next.visible;
+        if self.active.swap(next.status_dot, Ordering::Relaxed) == next.status_dot {
+            return;
+        }
+        // Resize off this thread: the caller holds the state lock.
+        let app = app.clone();
+        shared.scheduler.after("status_dot", Duration::ZERO, move |shared| {
+            let Ok(config) = lock_config(shared).map(|config| config.clone()) else {
+                return;
+            };
+            let result = main_window(&app).and_then(|window| {
+                apply_overlay_layout(&window, &config, PositionTrigger::StatusDot).map_err(|error| error.to_string())
+            });
+            if let Err(error) = result {
+                log::warn!("failed to switch the overlay to or from the status dot: {}", error);
+            }
+        });
+    }
+}
+
+pub(crate) fn dot_size() -> LogicalSize<f64> {
+    LogicalSize::new(DOT_SIZE, DOT_SIZE)
+}
+
+/// The configured corner of the current monitor's work area while the dot is shown.
+pub(crate) fn dot_position(
+    window: &WebviewWindow,
+    shared: &Arc<SharedOverlayState>,
+    size: PhysicalSize<u32>,
+) -> tauri::Result<Option<PhysicalPosition<i32>>> {
+    if !shared.status_dot.is_active() {
+        return Ok(None);
+    }
+    let Some(corner) = lock_config(shared).ok().and_then(|config| config.status_dot) else {
+        return Ok(None);
+    };
+    let Some(monitor) = current_or_primary_monitor(window)? else {
+        return Ok(None);
+    };
+    let area = monitor.work_area();
+    let margin = (DOT_MARGIN * monitor.scale_factor()) as i32;
+    let left = area.position.x + margin;
+    let top = area.position.y + margin;
+    let right = area.position.x + area.size.width as i32 - margin - size.width as i32;
+    let bottom = area.position.y + area.size.height as i32 - margin - size.height as i32;
+    Ok(Some(match corner {
+        DotCorner::TopLeft => PhysicalPosition::new(left, top),
+        DotCorner::TopRight => PhysicalPosition::new(right, top),
+        DotCorner::BottomLeft => PhysicalPosition::new(left, bottom),
+        DotCorner::BottomRight => PhysicalPosition::new(right, bottom),
+    }))
+}
+
+/// While hidden, shows a small status dot in `corner` instead of nothing; `None` turns it off.
+#[tauri::command]
+pub(crate) fn set_status_dot(
+    corner: Option<DotCorner>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    let config = update_config(&app, shared.inner(), |config| config.status_dot = corner)?;
+    if shared.status_dot.is_active() {
+        // Already a dot: only the corner moved.
+        apply_overlay_layout(&main_window(&app)?, &config, PositionTrigger::StatusDot)
+            .map_err(|error| error.to_string())?;
+    }
+    // Re-derive `status_dot`, which resizes the window if it flipped.
+    update_overlay_state(&app, shared.inner(), |_| {})
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum StepStatus {
+    #[default]
+    Pending,
+    Active,
+    Done,
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct Step {
+    pub(crate) label: String,
+    #[serde(default)]
+    pub(crate) status: StepStatus,
+}
+
+/// Updates the step at `index` without resending the whole list.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct StepPatch {
+    index: usize,
+    #[serde(default)]
+    label: Option<String>,
+    #[serde(default)]
+    status: Option<StepStatus>,
+}
+
+impl StepPatch {
+    pub(crate) fn apply(self, steps: &mut [Step]) {
+        let count = steps.len();
+        let Some(step) = steps.get_mut(self.index) else {
+            log::warn!("ignored step_patch for index {} ({} steps)", self.index, count);
+            return;
+        };
+        if let Some(label) = self.label {
+            step.label = label;
+        }
+        if let Some(status) = self.status {
+            step.status = status;
+        }
+    }
+}
+
+/// The legacy `processing` value implied by `steps`, or `None` when there are none.
+pub(crate) fn processing_for(steps: &[Step]) -> Option<&'static str> {
+    if steps.is_empty() {
+        return None;
+    }
+    let any = |status| steps.iter().any(|step| step.status == status);
+    Some(if any(StepStatus::Failed) {
+        "error"
+    } else if any(StepStatus::Active) {
+        "processing"
+    } else if steps.iter().all(|step| step.status == StepStatus::Done) {
+        "done"
+    } else {
+        "idle"
+    })
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, State};
+
+use crate::{config::update_config, OverlayState, SharedOverlayState};
+
+/// Sender-owned state fields, as named in payloads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum StateField {
+    Connection,
+    Listening,
+    Processing,
+    Target,
+    Level,
+    Visible,
+    Message,
+    Confidence,
+    Steps,
+}
+
+macro_rules! keep_if_default {
+    ($field:ident, $defaults:expr, $previous:expr, $next:expr) => {
+        if $next.$field == $defaults.$field {
+            $next.$field = $previous.$field.clone();
+        }
+    };
+}
+
+/// The JSON value of one field of `state`, as it would be emitted.
+pub(crate) fn field_value(state: &OverlayState, field: StateField) -> Value {
+    let value = match field {
+        StateField::Connection => serde_json::to_value(&state.connection),
+        StateField::Listening => serde_json::to_value(&state.listening),
+        StateField::Processing => serde_json::to_value(&state.processing),
+        StateField::Target => serde_json::to_value(&state.target),
+        StateField::Level => serde_json::to_value(state.level),
+        StateField::Visible => serde_json::to_value(state.visible),
+        StateField::Message => serde_json::to_value(&state.message),
+        StateField::Confidence => serde_json::to_value(state.confidence),
+        StateField::Steps => serde_json::to_value(&state.steps),
+    };
+    value.unwrap_or(Value::Null)
+}
+
+/// Keeps the previous value of each sticky field that `next` carries at its default.
+pub(crate) fn retain_sticky_fields(fields: &[StateField], previous: &OverlayState, next: &mut OverlayState) {
+    if fields.is_empty() {
+        return;
+    }
+    let defaults = OverlayState::default();
+    for field in fields {
+        match field {
+            StateField::Connection => keep_if_default!(connection, defaults, previous, next),
+            StateField::Listening => keep_if_default!(listening, defaults, previous, next),
+            StateField::Processing => keep_if_default!(processing, defaults, previous, next),
+            StateField::Target => keep_if_default!(target, defaults, previous, next),
+            StateField::Level => keep_if_default!(level, defaults, previous, next),
+            StateField::Visible => keep_if_default!(visible, defaults, previous, next),
+            StateField::Message => keep_if_default!(message, defaults, previous, next),
+            StateField::Confidence => keep_if_default!(confidence, defaults, previous, next),
+            StateField::Steps => keep_if_default!(steps, defaults, previous, next),
+        }
+    }
+}
+
+/// Fields that full-state updates keep when they arrive at their default value.
+#[tauri::command]
+pub(crate) fn set_sticky_fields(
+    fields: Vec<StateField>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    update_config(&app, shared.inner(), |config| config.sticky_fields = fields)?;
+    Ok(())
+}
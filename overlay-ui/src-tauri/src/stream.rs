@@ -0,0 +1,86 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use tauri::{AppHandle, State};
+
+use crate::{update_overlay_state, SharedOverlayState};
+
+const MAX_CHARS_PER_SEC: f64 = 1_000.0;
+const MIN_REVEAL_TICK: Duration = Duration::from_millis(16);
+
+/// Reveals a message a few characters at a time, like live transcription.
+#[derive(Default)]
+pub(crate) struct MessageStream {
+    generation: AtomicU64,
+}
+
+impl MessageStream {
+    fn is_current(&self, generation: u64) -> bool {
+        self.generation.load(Ordering::Relaxed) == generation
+    }
+}
+
+fn prefix(chars: &[char], count: usize) -> String {
+    chars[..count.min(chars.len())].iter().collect()
+}
+
+/// Streams `text` into `message`, replacing any stream still running. Stops
+/// early, clearing `streaming`, when something else changes the message.
+#[tauri::command]
+pub(crate) fn stream_message(
+    text: String,
+    chars_per_sec: f64,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if !chars_per_sec.is_finite() || chars_per_sec <= 0.0 || chars_per_sec > MAX_CHARS_PER_SEC {
+        return Err(format!(
+            "stream rate must be above 0 and at most {} chars per second",
+            MAX_CHARS_PER_SEC
+        ));
+    }
+    let chars: Vec<char> = text.chars().collect();
+    if chars.is_empty() {
+        return Err("streamed message must not be empty".to_string());
+    }
+    let shared = shared.inner().clone();
+    let generation = shared.message_stream.generation.fetch_add(1, Ordering::Relaxed) + 1;
+    let first = prefix(&chars, 1);
+    update_overlay_state(&app, &shared, |state| {
+        state.message = Some(first.clone());
+        state.streaming = chars.len() > 1;
+    })?;
+    let tick = Duration::from_secs_f64(1.0 / chars_per_sec).max(MIN_REVEAL_TICK);
+    let started = shared.scheduler.clock().now();
+    let mut shown = first;
+    shared.scheduler.repeat("message_stream", tick, move |shared| {
+        if !shared.message_stream.is_current(generation) {
+            return None;
+        }
+        let elapsed = shared.scheduler.clock().now().duration_since(started);
+        let count = 1 + (elapsed.as_secs_f64() * chars_per_sec) as usize;
+        let next = prefix(&chars, count);
+        let done = count >= chars.len();
+        let mut interrupted = false;
+        let result = update_overlay_state(&app, shared, |state| {
+            if state.message.as_deref() != Some(shown.as_str()) {
+                interrupted = true;
+                state.streaming = false;
+                return;
+            }
+            state.message = Some(next.clone());
+            state.streaming = !done;
+        });
+        if result.is_err() || interrupted || done {
+            return None;
+        }
+        shown = next;
+        Some(tick)
+    });
+    Ok(())
+}
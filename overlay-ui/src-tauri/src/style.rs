@@ -0,0 +1,97 @@
+use serde::Serialize;
+
+/// One state field as the frontend exposes it to theme CSS.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct StyleHook {
+    field: &'static str,
+    pub(crate) attribute: &'static str,
+    pub(crate) values: &'static [&'static str],
+}
+
+const BOOLEAN: &[&str] = &["true", "false"];
+
+/// Attributes set on the overlay's root `<main>`; keep in step with `App.tsx`.
+pub(crate) const STYLE_CONTRACT: &[StyleHook] = &[
+    StyleHook {
+        field: "connection",
+        attribute: "data-connection",
+        values: &["checking", "online", "reconnecting", "offline"],
+    },
+    StyleHook {
+        field: "listening",
+        attribute: "data-listening",
+        values: &["ready", "arming", "listening", "error"],
+    },
+    StyleHook {
+        field: "processing",
+        attribute: "data-processing",
+        values: &["idle", "processing", "done", "error"],
+    },
+    StyleHook {
+        field: "target",
+        attribute: "data-target",
+        values: &["unknown", "selected", "not_selected"],
+    },
+    StyleHook {
+        field: "mode",
+        attribute: "data-mode",
+        values: &[
+            "idle",
+            "loading",
+            "listening_wait",
+            "listening_audio",
+            "processing",
+            "done",
+            "warning",
+            "error",
+        ],
+    },
+    StyleHook {
+        field: "visible",
+        attribute: "data-visible",
+        values: BOOLEAN,
+    },
+    StyleHook {
+        field: "display_form",
+        attribute: "data-display-form",
+        values: &["overlay", "banner"],
+    },
+    StyleHook {
+        field: "power_mode",
+        attribute: "data-power-mode",
+        values: &["performance", "balanced", "saver"],
+    },
+    StyleHook {
+        field: "system_theme",
+        attribute: "data-theme",
+        values: &["light", "dark"],
+    },
+    StyleHook {
+        field: "interactive",
+        attribute: "data-interactive",
+        values: BOOLEAN,
+    },
+    StyleHook {
+        field: "streaming",
+        attribute: "data-streaming",
+        values: BOOLEAN,
+    },
+    StyleHook {
+        field: "split_view",
+        attribute: "data-split",
+        values: BOOLEAN,
+    },
+    StyleHook {
+        field: "collapsed",
+        attribute: "data-collapsed",
+        values: BOOLEAN,
+    },
+];
+
+/// Which root attribute each state field drives and the values it can take,
+/// for theme authors targeting them from CSS. `mode` is derived from several fields.
+#[tauri::command]
+pub(crate) fn get_style_contract() -> Vec<StyleHook> {
+    STYLE_CONTRACT.to_vec()
+}
@@ -0,0 +1,39 @@
+use std::sync::{
+    mpsc::{self, Receiver, Sender},
+    Mutex,
+};
+
+use crate::{projection::public_state, OverlayState};
+
+const MAX_STATE_SUBSCRIBERS: usize = 16;
+
+/// Streaming clients (SSE, Unix socket RPC), each fed serialized states by `broadcast`.
+#[derive(Default)]
+pub(crate) struct StateSubscribers {
+    senders: Mutex<Vec<Sender<String>>>,
+}
+
+impl StateSubscribers {
+    /// Queues the state, less `private_fields`, for every subscriber, dropping those that disconnected.
+    pub(crate) fn broadcast(&self, state: &OverlayState, private_fields: &[String]) {
+        let Ok(mut senders) = self.senders.lock() else {
+            return;
+        };
+        if senders.is_empty() {
+            return;
+        }
+        let data = public_state(state, private_fields).to_string();
+        senders.retain(|sender| sender.send(data.clone()).is_ok());
+    }
+
+    /// None once `MAX_STATE_SUBSCRIBERS` are connected.
+    pub(crate) fn subscribe(&self) -> Option<Receiver<String>> {
+        let mut senders = self.senders.lock().ok()?;
+        if senders.len() >= MAX_STATE_SUBSCRIBERS {
+            return None;
+        }
+        let (sender, receiver) = mpsc::channel();
+        senders.push(sender);
+        Some(receiver)
+    }
+}
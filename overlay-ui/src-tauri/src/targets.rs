@@ -0,0 +1,52 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use tauri::{AppHandle, State};
+
+use crate::{config::update_config, update_overlay_state, OverlayState, SharedOverlayState};
+
+/// Hides the overlay while `target` isn't in `visible_only_for_targets`.
+#[derive(Default)]
+pub(crate) struct TargetGate {
+    gated: AtomicBool,
+    /// The `visible` senders asked for while gated, restored when the target is allowed again.
+    wanted_visible: AtomicBool,
+}
+
+impl TargetGate {
+    pub(crate) fn filter(&self, targets: &[String], previous: &OverlayState, next: &mut OverlayState) {
+        let gated = self.gated.load(Ordering::Relaxed);
+        // While gated `visible` reads false, so only a change is a sender's request.
+        if !gated || next.visible != previous.visible {
+            self.wanted_visible.store(next.visible, Ordering::Relaxed);
+        }
+        let allowed = targets.is_empty() || targets.contains(&next.target);
+        if allowed {
+            if gated {
+                next.visible = self.wanted_visible.load(Ordering::Relaxed);
+                self.gated.store(false, Ordering::Relaxed);
+            }
+        } else {
+            next.visible = false;
+            self.gated.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Shows the overlay only while `target` is one of `targets`; empty shows it for any target.
+#[tauri::command]
+pub(crate) fn set_visible_only_for_targets(
+    targets: Vec<String>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    let targets: Vec<String> = targets
+        .into_iter()
+        .map(|target| target.trim().to_string())
+        .filter(|target| !target.is_empty())
+        .collect();
+    update_config(&app, shared.inner(), |config| config.visible_only_for_targets = targets)?;
+    update_overlay_state(&app, shared.inner(), |_| {})
+}
@@ -0,0 +1,97 @@
+use std::{
+    collections::BTreeSet,
+    sync::{Arc, Mutex},
+};
+
+use serde_json::Value;
+use tauri::{AppHandle, State};
+
+use crate::{config::update_config, update_overlay_state, OverlayState, SharedOverlayState};
+
+/// Fills `display_template` placeholders from the state into `rendered_text`.
+#[derive(Default)]
+pub(crate) struct DisplayTemplate {
+    /// Unknown placeholder names already logged.
+    warned: Mutex<BTreeSet<String>>,
+}
+
+fn placeholder_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Number(number) => number.to_string(),
+        Value::Bool(flag) => flag.to_string(),
+        _ => String::new(),
+    }
+}
+
+impl DisplayTemplate {
+    /// `{field}` becomes that state field's value; nulls, lists and unknown names render empty.
+    pub(crate) fn render(&self, template: Option<&str>, next: &mut OverlayState) {
+        let Some(template) = template else {
+            next.rendered_text = None;
+            return;
+        };
+        let fields = match serde_json::to_value(&*next) {
+            Ok(Value::Object(fields)) => fields,
+            _ => return,
+        };
+        let mut rendered = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(open) = rest.find('{') {
+            rendered.push_str(&rest[..open]);
+            let Some(close) = rest[open..].find('}') else {
+                break;
+            };
+            let name = &rest[open + 1..open + close];
+            match fields.get(name) {
+                Some(value) => rendered.push_str(&placeholder_text(value)),
+                None => self.warn_unknown(name),
+            }
+            rest = &rest[open + close + 1..];
+        }
+        rendered.push_str(rest);
+        next.rendered_text = Some(rendered);
+    }
+
+    fn warn_unknown(&self, name: &str) {
+        if let Ok(mut warned) = self.warned.lock() {
+            if warned.insert(name.to_string()) {
+                log::warn!("display template placeholder {{{}}} matches no state field", name);
+            }
+        }
+    }
+}
+
+/// Every `{` closed by a `}` before the next `{`, and no stray `}`.
+fn check_braces(template: &str) -> Result<(), String> {
+    let mut open = false;
+    for (index, ch) in template.char_indices() {
+        match (ch, open) {
+            ('{', true) => return Err(format!("nested '{{' at {}", index)),
+            ('}', false) => return Err(format!("unmatched '}}' at {}", index)),
+            ('{', false) | ('}', true) => open = !open,
+            _ => {}
+        }
+    }
+    if open {
+        return Err("unclosed '{'".to_string());
+    }
+    Ok(())
+}
+
+/// Sets the template rendered into `rendered_text`; `None` stops rendering.
+#[tauri::command]
+pub(crate) fn set_display_template(
+    template: Option<String>,
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<(), String> {
+    if let Some(template) = &template {
+        check_braces(template).map_err(|error| format!("invalid display template: {}", error))?;
+    }
+    if let Ok(mut warned) = shared.display_template.warned.lock() {
+        warned.clear();
+    }
+    update_config(&app, shared.inner(), |config| config.display_template = template)?;
+    update_overlay_state(&app, shared.inner(), |_| {})
+}
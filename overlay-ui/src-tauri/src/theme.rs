@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State, WebviewWindow, WindowEvent};
+
+use crate::{lock_state, update_overlay_state, SharedOverlayState};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SystemTheme {
+    Light,
+    #[default]
+    Dark,
+}
+
+#[cfg(windows)]
+fn read_apps_use_light_theme() -> Result<bool, String> {
+    use windows_sys::{
+        core::w,
+        Win32::System::Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD},
+    };
+
+    let mut value = 0_u32;
+    let mut size = std::mem::size_of::<u32>() as u32;
+    // SAFETY: both strings are static wide literals and `value`/`size` describe a DWORD buffer.
+    let status = unsafe {
+        RegGetValueW(
+            HKEY_CURRENT_USER,
+            w!("Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize"),
+            w!("AppsUseLightTheme"),
+            RRF_RT_REG_DWORD,
+            std::ptr::null_mut(),
+            (&mut value as *mut u32).cast(),
+            &mut size,
+        )
+    };
+    if status != 0 {
+        return Err(format!("AppsUseLightTheme lookup failed with error {}", status));
+    }
+    Ok(value != 0)
+}
+
+#[cfg(not(windows))]
+fn read_apps_use_light_theme() -> Result<bool, String> {
+    Err("system theme detection is only supported on Windows".to_string())
+}
+
+/// The OS app theme, defaulting to dark when it can't be read.
+pub(crate) fn read_system_theme() -> SystemTheme {
+    match read_apps_use_light_theme() {
+        Ok(true) => SystemTheme::Light,
+        Ok(false) => SystemTheme::Dark,
+        Err(error) => {
+            log::warn!("{}; defaulting to dark theme", error);
+            SystemTheme::Dark
+        }
+    }
+}
+
+fn refresh_system_theme(app: &AppHandle, shared: &Arc<SharedOverlayState>) -> Result<SystemTheme, String> {
+    let theme = read_system_theme();
+    let changed = lock_state(shared)?.system_theme != theme;
+    if changed {
+        log::info!("system theme changed to {:?}", theme);
+        update_overlay_state(app, shared, |state| state.system_theme = theme)?;
+    }
+    Ok(theme)
+}
+
+/// Re-reads the theme whenever Windows broadcasts a theme change to the overlay window.
+pub(crate) fn start_theme_watch(window: &WebviewWindow, app: AppHandle, shared: Arc<SharedOverlayState>) {
+    window.on_window_event(move |event| {
+        if let WindowEvent::ThemeChanged(_) = event {
+            if let Err(error) = refresh_system_theme(&app, &shared) {
+                log::warn!("failed to refresh system theme: {}", error);
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub(crate) fn get_system_theme(
+    app: AppHandle,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<SystemTheme, String> {
+    refresh_system_theme(&app, shared.inner())
+}
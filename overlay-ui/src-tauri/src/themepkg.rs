@@ -0,0 +1,164 @@
+use std::{collections::BTreeMap, fs};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::{config::write_config_file, style::STYLE_CONTRACT};
+
+/// A user theme: CSS colors per value of each style-contract attribute, e.g.
+/// `attributes["data-connection"]["offline"]["color"] = "#ffd0d7"`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct ThemePackage {
+    name: String,
+    attributes: BTreeMap<String, BTreeMap<String, BTreeMap<String, String>>>,
+}
+
+/// Where to read a theme package from: `{ "path": ... }` or `{ "contents": ... }`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ThemeSource {
+    Path(String),
+    Contents(String),
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct ThemeProblem {
+    /// Dotted location in the package, e.g. `attributes.data-connection.offline.color`.
+    path: String,
+    message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct ThemeReport {
+    name: Option<String>,
+    valid: bool,
+    problems: Vec<ThemeProblem>,
+}
+
+fn is_hex_color(value: &str) -> bool {
+    value
+        .strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 3 | 4 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// `#rgb[a]`, `#rrggbb[aa]`, `rgb()`/`rgba()`/`hsl()`/`hsla()`, or `transparent`.
+fn is_color(value: &str) -> bool {
+    let value = value.trim();
+    if is_hex_color(value) || value.eq_ignore_ascii_case("transparent") {
+        return true;
+    }
+    ["rgb(", "rgba(", "hsl(", "hsla("]
+        .iter()
+        .any(|prefix| value.to_ascii_lowercase().starts_with(prefix) && value.ends_with(')'))
+}
+
+fn problem(path: impl Into<String>, message: impl Into<String>) -> ThemeProblem {
+    ThemeProblem {
+        path: path.into(),
+        message: message.into(),
+    }
+}
+
+fn check_package(package: &ThemePackage) -> Vec<ThemeProblem> {
+    let mut problems = Vec::new();
+    if package.name.trim().is_empty() {
+        problems.push(problem("name", "theme name must not be empty"));
+    }
+    for hook in STYLE_CONTRACT {
+        let Some(values) = package.attributes.get(hook.attribute) else {
+            problems.push(problem(format!("attributes.{}", hook.attribute), "missing attribute"));
+            continue;
+        };
+        for value in hook.values {
+            if !values.contains_key(*value) {
+                problems.push(problem(
+                    format!("attributes.{}.{}", hook.attribute, value),
+                    "missing state",
+                ));
+            }
+        }
+        for (value, properties) in values {
+            let path = format!("attributes.{}.{}", hook.attribute, value);
+            if !hook.values.contains(&value.as_str()) {
+                problems.push(problem(&path, format!("{} never takes this value", hook.attribute)));
+            }
+            for (property, color) in properties {
+                if !is_color(color) {
+                    problems.push(problem(format!("{}.{}", path, property), format!("invalid color {:?}", color)));
+                }
+            }
+        }
+    }
+    for attribute in package.attributes.keys() {
+        if !STYLE_CONTRACT.iter().any(|hook| hook.attribute == attribute) {
+            problems.push(problem(format!("attributes.{}", attribute), "not in the style contract"));
+        }
+    }
+    problems
+}
+
+fn read_source(source: ThemeSource) -> Result<String, String> {
+    match source {
+        ThemeSource::Path(path) => fs::read_to_string(&path).map_err(|error| format!("failed to read {}: {}", path, error)),
+        ThemeSource::Contents(contents) => Ok(contents),
+    }
+}
+
+fn validate(source: ThemeSource) -> Result<(Option<ThemePackage>, ThemeReport), String> {
+    let text = read_source(source)?;
+    let package = match serde_json::from_str::<ThemePackage>(&text) {
+        Ok(package) => package,
+        Err(error) => {
+            let report = ThemeReport {
+                name: None,
+                valid: false,
+                problems: vec![problem("", format!("not a theme package: {}", error))],
+            };
+            return Ok((None, report));
+        }
+    };
+    let problems = check_package(&package);
+    let report = ThemeReport {
+        name: Some(package.name.clone()),
+        valid: problems.is_empty(),
+        problems,
+    };
+    Ok((Some(package), report))
+}
+
+/// Checks a theme package against the style contract without installing it.
+#[tauri::command]
+pub(crate) fn validate_theme(source: ThemeSource) -> Result<ThemeReport, String> {
+    validate(source).map(|(_, report)| report)
+}
+
+/// Validates and saves the package under `themes/` in the config dir; a
+/// package with problems is refused unless `force` is set. Returns the report.
+#[tauri::command]
+pub(crate) fn install_theme(source: ThemeSource, force: bool, app: AppHandle) -> Result<ThemeReport, String> {
+    let (package, report) = validate(source)?;
+    let Some(package) = package else {
+        return Err("theme package could not be parsed".to_string());
+    };
+    if !report.valid && !force {
+        return Err(format!(
+            "theme {:?} has {} problem(s); validate it or install with force",
+            package.name,
+            report.problems.len()
+        ));
+    }
+    let file_name: String = package
+        .name
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    if file_name.is_empty() {
+        return Err("theme name must not be empty".to_string());
+    }
+    write_config_file(&app, &format!("themes/{}.json", file_name), &package)?;
+    Ok(report)
+}
@@ -0,0 +1,127 @@
+use std::{
+    cell::Cell,
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::State;
+
+use crate::{sticky::StateField, unix_millis, OverlayState, SharedOverlayState};
+
+const TIMELINE_CAPACITY: usize = 500;
+
+/// What caused a state change. Changes default to `backend` (timers, watchers,
+/// policies) unless the update runs inside a `SourceScope`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ChangeSource {
+    Udp,
+    Command,
+    Backend,
+}
+
+thread_local! {
+    static CURRENT_SOURCE: Cell<ChangeSource> = const { Cell::new(ChangeSource::Backend) };
+}
+
+/// Attributes updates on this thread to `source` until dropped.
+pub(crate) struct SourceScope(ChangeSource);
+
+impl SourceScope {
+    pub(crate) fn enter(source: ChangeSource) -> Self {
+        Self(CURRENT_SOURCE.with(|current| current.replace(source)))
+    }
+}
+
+/// The source updates on this thread are attributed to.
+pub(crate) fn current_source() -> ChangeSource {
+    CURRENT_SOURCE.with(Cell::get)
+}
+
+impl Drop for SourceScope {
+    fn drop(&mut self) {
+        CURRENT_SOURCE.with(|current| current.set(self.0));
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct Transition {
+    timestamp: u64,
+    field: StateField,
+    old: Value,
+    new: Value,
+    source: ChangeSource,
+}
+
+/// Recent per-field changes, oldest first. `level` is left out; it changes on
+/// nearly every datagram and would crowd out everything else.
+#[derive(Default)]
+pub(crate) struct Timeline {
+    entries: Mutex<VecDeque<Transition>>,
+}
+
+macro_rules! record_if_changed {
+    ($changes:ident, $field:ident, $variant:ident, $previous:expr, $next:expr) => {
+        if $previous.$field != $next.$field {
+            $changes.push((StateField::$variant, json!($previous.$field), json!($next.$field)));
+        }
+    };
+}
+
+impl Timeline {
+    pub(crate) fn len(&self) -> usize {
+        self.entries.lock().map(|entries| entries.len()).unwrap_or(0)
+    }
+
+    pub(crate) fn record(&self, previous: &OverlayState, next: &OverlayState) {
+        let mut changes = Vec::new();
+        record_if_changed!(changes, connection, Connection, previous, next);
+        record_if_changed!(changes, listening, Listening, previous, next);
+        record_if_changed!(changes, processing, Processing, previous, next);
+        record_if_changed!(changes, target, Target, previous, next);
+        record_if_changed!(changes, visible, Visible, previous, next);
+        record_if_changed!(changes, message, Message, previous, next);
+        record_if_changed!(changes, confidence, Confidence, previous, next);
+        record_if_changed!(changes, steps, Steps, previous, next);
+        if changes.is_empty() {
+            return;
+        }
+        let (Ok(mut entries), timestamp) = (self.entries.lock(), unix_millis()) else {
+            return;
+        };
+        let source = current_source();
+        for (field, old, new) in changes {
+            if entries.len() == TIMELINE_CAPACITY {
+                entries.pop_front();
+            }
+            entries.push_back(Transition {
+                timestamp,
+                field,
+                old,
+                new,
+                source,
+            });
+        }
+    }
+}
+
+/// Field changes at or after `since_ms` (unix milliseconds), oldest first.
+#[tauri::command]
+pub(crate) fn get_transition_timeline(
+    since_ms: u64,
+    shared: State<'_, Arc<SharedOverlayState>>,
+) -> Result<Vec<Transition>, String> {
+    let entries = shared
+        .timeline
+        .entries
+        .lock()
+        .map_err(|_| "timeline lock poisoned".to_string())?;
+    Ok(entries
+        .iter()
+        .filter(|entry| entry.timestamp >= since_ms)
+        .cloned()
+        .collect())
+}
@@ -0,0 +1,144 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, State};
+
+use crate::{
+    replace_state,
+    timeline::{current_source, ChangeSource, SourceScope},
+    update_overlay_state, OverlayState, SharedOverlayState,
+};
+
+const UNDO_CAPACITY: usize = 50;
+
+/// States before each command-originated change, for `undo_overlay` and
+/// `redo_overlay`. Bridge and backend changes are not recorded, so level
+/// traffic cannot crowd out manual edits.
+#[derive(Default)]
+pub(crate) struct UndoStack {
+    undo: Mutex<VecDeque<OverlayState>>,
+    redo: Mutex<Vec<OverlayState>>,
+    /// Set while an undo or redo applies, so it is not recorded as a new edit.
+    restoring: AtomicBool,
+}
+
+impl UndoStack {
+    /// Called after each emitted change; records `previous` when a command
+    /// made it and it differs from `next`.
+    pub(crate) fn record(&self, previous: &OverlayState, next: &OverlayState) {
+        if current_source() != ChangeSource::Command || self.restoring.load(Ordering::Relaxed) || previous == next {
+            return;
+        }
+        if let Ok(mut undo) = self.undo.lock() {
+            if undo.len() == UNDO_CAPACITY {
+                undo.pop_front();
+            }
+            undo.push_back(previous.clone());
+        }
+        if let Ok(mut redo) = self.redo.lock() {
+            redo.clear();
+        }
+    }
+
+    fn lengths(&self) -> UndoDepth {
+        UndoDepth {
+            undo: self.undo.lock().map(|undo| undo.len()).unwrap_or(0),
+            redo: self.redo.lock().map(|redo| redo.len()).unwrap_or(0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct UndoDepth {
+    undo: usize,
+    redo: usize,
+}
+
+/// Puts `snapshot` back, keeping the live level and meters, and hands the replaced state to `keep`.
+fn restore(
+    app: &AppHandle,
+    shared: &Arc<SharedOverlayState>,
+    mut snapshot: OverlayState,
+    keep: impl FnOnce(OverlayState),
+) -> Result<(), String> {
+    let _source = SourceScope::enter(ChangeSource::Command);
+    shared.undo_stack.restoring.store(true, Ordering::Relaxed);
+    let result = update_overlay_state(app, shared, |state| {
+        snapshot.level = state.level;
+        snapshot.meters = state.meters.clone();
+        keep(state.clone());
+        replace_state(state, snapshot, &[]);
+    });
+    shared.undo_stack.restoring.store(false, Ordering::Relaxed);
+    result
+}
+
+/// Restores the state before the last command-originated change.
+#[tauri::command]
+pub(crate) fn undo_overlay(app: AppHandle, shared: State<'_, Arc<SharedOverlayState>>) -> Result<UndoDepth, String> {
+    let snapshot = shared
+        .undo_stack
+        .undo
+        .lock()
+        .map_err(|_| "undo stack lock poisoned".to_string())?
+        .pop_back()
+        .ok_or_else(|| "nothing to undo".to_string())?;
+    restore(&app, shared.inner(), snapshot, |current| {
+        if let Ok(mut redo) = shared.undo_stack.redo.lock() {
+            redo.push(current);
+        }
+    })?;
+    Ok(shared.undo_stack.lengths())
+}
+
+/// Re-applies the last undone change.
+#[tauri::command]
+pub(crate) fn redo_overlay(app: AppHandle, shared: State<'_, Arc<SharedOverlayState>>) -> Result<UndoDepth, String> {
+    let snapshot = shared
+        .undo_stack
+        .redo
+        .lock()
+        .map_err(|_| "undo stack lock poisoned".to_string())?
+        .pop()
+        .ok_or_else(|| "nothing to redo".to_string())?;
+    restore(&app, shared.inner(), snapshot, |current| {
+        if let Ok(mut undo) = shared.undo_stack.undo.lock() {
+            undo.push_back(current);
+        }
+    })?;
+    Ok(shared.undo_stack.lengths())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UndoStack;
+    use crate::{
+        timeline::{ChangeSource, SourceScope},
+        OverlayState,
+    };
+
+    #[test]
+    fn a_no_op_command_keeps_the_redo_stack() {
+        let stack = UndoStack::default();
+        let _source = SourceScope::enter(ChangeSource::Command);
+        let state = OverlayState::default();
+        stack.redo.lock().unwrap().push(state.clone());
+
+        stack.record(&state, &state.clone());
+        assert_eq!((stack.lengths().undo, stack.lengths().redo), (0, 1));
+
+        let next = OverlayState {
+            visible: true,
+            ..state.clone()
+        };
+        stack.record(&state, &next);
+        assert_eq!((stack.lengths().undo, stack.lengths().redo), (1, 0));
+    }
+}
@@ -0,0 +1,94 @@
+use std::{
+    fs,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use socket2::{Domain, SockAddr, Socket, Type};
+use tauri::AppHandle;
+
+use crate::{handle_datagram, ingest::Transport, lock_config, rpc, SharedOverlayState};
+
+const MAX_FRAME_BYTES: usize = 8192;
+const LISTEN_BACKLOG: i32 = 8;
+
+fn bind(path: &Path) -> std::io::Result<Socket> {
+    // A socket file left by a previous run would make bind fail.
+    let _ = fs::remove_file(path);
+    let socket = Socket::new(Domain::UNIX, Type::STREAM, None)?;
+    socket.bind(&SockAddr::unix(path)?)?;
+    socket.listen(LISTEN_BACKLOG)?;
+    Ok(socket)
+}
+
+/// Each connection sends newline-delimited JSON frames, handled exactly like
+/// UDP datagrams unless they are RPC requests (see `rpc`). AF_UNIX works on
+/// Windows 10 1803+, and WSL can reach a socket file on a shared drive.
+pub(crate) fn start_unix_socket_bridge(app: AppHandle, shared: Arc<SharedOverlayState>) {
+    let path = match lock_config(&shared) {
+        Ok(config) => config.unix_socket_path.clone(),
+        Err(_) => return,
+    };
+    let Some(path) = path.map(PathBuf::from) else {
+        return;
+    };
+    thread::spawn(move || {
+        let listener = match bind(&path) {
+            Ok(listener) => listener,
+            Err(error) => {
+                log::error!("failed to bind Unix socket bridge at {}: {}", path.display(), error);
+                return;
+            }
+        };
+        log::info!("overlay Unix socket bridge listening on {}", path.display());
+        loop {
+            let connection = match listener.accept() {
+                Ok((connection, _)) => connection,
+                Err(error) => {
+                    log::error!("overlay Unix socket bridge stopped: {}", error);
+                    break;
+                }
+            };
+            let replies = match connection.try_clone() {
+                Ok(replies) => Arc::new(Mutex::new(replies)),
+                Err(error) => {
+                    log::warn!("failed to accept Unix socket connection: {}", error);
+                    continue;
+                }
+            };
+            let (app, shared) = (app.clone(), shared.clone());
+            thread::spawn(move || {
+                for line in BufReader::new(connection).split(b'\n') {
+                    let Ok(mut frame) = line else {
+                        break;
+                    };
+                    if frame.last() == Some(&b'\r') {
+                        frame.pop();
+                    }
+                    if frame.is_empty() {
+                        continue;
+                    }
+                    if frame.len() > MAX_FRAME_BYTES {
+                        shared.metrics.record_rejected();
+                        crate::logsample::sampled_warn!(
+                            &shared,
+                            "oversized_frame",
+                            "dropped {}-byte Unix socket frame",
+                            frame.len()
+                        );
+                        continue;
+                    }
+                    if let Some(request) = rpc::parse_request(&frame) {
+                        if rpc::handle_request(&app, &shared, &replies, request).is_err() {
+                            break;
+                        }
+                        continue;
+                    }
+                    let _ = handle_datagram(&app, &shared, Transport::UnixSocket, &frame);
+                }
+            });
+        }
+    });
+}
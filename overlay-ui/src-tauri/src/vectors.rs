@@ -0,0 +1,112 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::{decode_payload, replace_state, sequence::BridgeUpdate, OverlayState};
+
+/// Payloads applied in order to a default state, with the expected outcome.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct TestVector {
+    name: &'static str,
+    payloads: Vec<Value>,
+    /// The state after the last payload; absent when it is rejected.
+    result: Option<OverlayState>,
+    /// Why the last payload was rejected.
+    error: Option<String>,
+}
+
+fn vector_cases() -> Vec<(&'static str, Vec<Value>)> {
+    let full_state = json!({
+        "connection": "online",
+        "listening": "listening",
+        "processing": "idle",
+        "target": "selected",
+        "level": 0.4,
+        "visible": true,
+    });
+    vec![
+        ("untagged full state", vec![full_state.clone()]),
+        (
+            "full state resets omitted optional fields",
+            vec![json!({ "kind": "patch", "message": "Hello" }), full_state.clone()],
+        ),
+        ("untagged partial payload is a patch", vec![json!({ "visible": true })]),
+        ("tagged patch", vec![json!({ "kind": "patch", "target": "not_selected" })]),
+        ("tagged state missing fields", vec![json!({ "kind": "state", "visible": true })]),
+        ("level clamped above", vec![json!({ "kind": "patch", "level": 1.7 })]),
+        ("level clamped below", vec![json!({ "kind": "patch", "level": -0.2 })]),
+        ("confidence clamped", vec![json!({ "kind": "patch", "confidence": 3.0 })]),
+        (
+            "null confidence clears it",
+            vec![
+                json!({ "kind": "patch", "confidence": 0.8 }),
+                json!({ "kind": "patch", "confidence": null }),
+            ],
+        ),
+        (
+            "empty message clears it",
+            vec![
+                json!({ "kind": "patch", "message": "Hello" }),
+                json!({ "kind": "patch", "message": "   " }),
+            ],
+        ),
+        (
+            "active step sets processing",
+            vec![json!({
+                "kind": "patch",
+                "steps": [{ "label": "Transcribe", "status": "done" }, { "label": "Insert", "status": "active" }],
+            })],
+        ),
+        (
+            "failed step sets processing to error",
+            vec![json!({ "kind": "patch", "steps": [{ "label": "Insert", "status": "failed" }] })],
+        ),
+        (
+            "unknown step status",
+            vec![json!({ "kind": "patch", "steps": [{ "label": "Insert", "status": "skipped" }] })],
+        ),
+        (
+            "word patch appends and finalizes",
+            vec![
+                json!({ "kind": "patch", "words": [{ "text": "hello", "status": "interim" }] }),
+                json!({
+                    "kind": "patch",
+                    "word_patch": { "append": [{ "text": "world" }], "finalize_through": 1 },
+                }),
+            ],
+        ),
+        ("wrong field type", vec![json!({ "kind": "patch", "visible": "yes" })]),
+        ("not an object", vec![json!([1, 2, 3])]),
+    ]
+}
+
+/// Applies one payload the way the bridge does, short of the config-driven
+/// policies (pins, muting, hysteresis), which vectors leave at their defaults.
+fn apply_payload(state: &mut OverlayState, payload: &Value) -> Result<(), String> {
+    match decode_payload(&payload.to_string())?.0 {
+        BridgeUpdate::State(next) => replace_state(state, next, &[]),
+        BridgeUpdate::Patch(patch) => patch.apply(state),
+    }
+    Ok(())
+}
+
+fn run_vector(name: &'static str, payloads: Vec<Value>) -> TestVector {
+    let mut state = OverlayState::default();
+    let outcome = payloads.iter().try_for_each(|payload| apply_payload(&mut state, payload));
+    TestVector {
+        name,
+        result: outcome.is_ok().then(|| state.clone()),
+        error: outcome.err(),
+        payloads,
+    }
+}
+
+/// Canonical payloads and the resulting state, produced by the real decode and
+/// apply code so sender test suites can check conformance against them.
+#[tauri::command]
+pub(crate) fn generate_test_vectors() -> Vec<TestVector> {
+    vector_cases()
+        .into_iter()
+        .map(|(name, payloads)| run_vector(name, payloads))
+        .collect()
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+/// Longest word list kept; older words drop off the front.
+pub(crate) const MAX_WORDS: usize = 200;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum WordStatus {
+    #[default]
+    Interim,
+    Final,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct Word {
+    pub(crate) text: String,
+    #[serde(default)]
+    pub(crate) status: WordStatus,
+}
+
+/// Grows or settles the word list without resending it.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "snake_case")]
+pub(crate) struct WordPatch {
+    /// Drops the words from this index on, so a revised interim hypothesis can replace them.
+    truncate: Option<usize>,
+    append: Vec<Word>,
+    /// Marks every word before this index as final.
+    finalize_through: Option<usize>,
+}
+
+impl WordPatch {
+    pub(crate) fn apply(self, words: &mut Vec<Word>) {
+        if let Some(index) = self.truncate {
+            words.truncate(index);
+        }
+        words.extend(self.append);
+        if let Some(index) = self.finalize_through {
+            for word in words.iter_mut().take(index) {
+                word.status = WordStatus::Final;
+            }
+        }
+        cap_words(words);
+    }
+}
+
+pub(crate) fn cap_words(words: &mut Vec<Word>) {
+    if words.len() > MAX_WORDS {
+        words.drain(..words.len() - MAX_WORDS);
+    }
+}
@@ -4,7 +4,8 @@ import { invoke } from "@tauri-apps/api/core";
 
 import { DevToolbar } from "@/components/overlay/dev-toolbar";
 import { VoiceOverlay } from "@/components/overlay/voice-overlay";
-import { defaultOverlayState, type OverlayState } from "@/types/overlay";
+import { deriveMode } from "@/lib/overlay";
+import { defaultOverlayState, type OverlayAnnouncement, type OverlayState } from "@/types/overlay";
 
 const DEV_MODE = import.meta.env.DEV;
 const IS_TAURI_RUNTIME = typeof window !== "undefined" && "__TAURI_INTERNALS__" in window;
@@ -23,6 +24,7 @@ function isOverlayState(value: unknown): value is OverlayState {
 
 export default function App() {
   const [state, setState] = useState<OverlayState>(defaultOverlayState);
+  const [announcement, setAnnouncement] = useState<OverlayAnnouncement | null>(null);
   const showDevToolbar = DEV_MODE && !IS_TAURI_RUNTIME;
 
   useEffect(() => {
@@ -50,6 +52,18 @@ export default function App() {
         // In browser-only mode there is no Tauri event bridge.
       });
 
+    // Delta mode sends only the fields that changed since the last event.
+    let unlistenDelta: (() => void) | undefined;
+    listen<Partial<OverlayState>>("overlay://delta", (event) => {
+      setState((current) => ({ ...current, ...event.payload }));
+    })
+      .then((dispose) => {
+        unlistenDelta = dispose;
+      })
+      .catch(() => {
+        // In browser-only mode there is no Tauri event bridge.
+      });
+
     const poll = window.setInterval(() => {
       invoke<OverlayState>("get_overlay_state")
         .then((current) => {
@@ -68,9 +82,56 @@ export default function App() {
       if (unlisten) {
         unlisten();
       }
+      if (unlistenDelta) {
+        unlistenDelta();
+      }
+    };
+  }, []);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen<OverlayAnnouncement>("overlay://announce", (event) => {
+      setAnnouncement(event.payload);
+    })
+      .then((dispose) => {
+        unlisten = dispose;
+      })
+      .catch(() => {
+        // In browser-only mode there is no Tauri event bridge.
+      });
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
     };
   }, []);
 
+  const splitView = state.split_view ?? null;
+  useEffect(() => {
+    // In split view each half carries its own theme class instead.
+    document.documentElement.classList.toggle("dark", !splitView && (state.system_theme ?? "dark") === "dark");
+  }, [state.system_theme, splitView]);
+
+  const reportFrames = IS_TAURI_RUNTIME && state.power_mode !== "saver";
+  useEffect(() => {
+    if (!reportFrames) return;
+    let frames = 0;
+    let frame = requestAnimationFrame(function tick() {
+      frames += 1;
+      frame = requestAnimationFrame(tick);
+    });
+    const report = window.setInterval(() => {
+      invoke("report_frames", { count: frames }).catch(() => {
+        // Stats are best-effort.
+      });
+      frames = 0;
+    }, 1000);
+    return () => {
+      cancelAnimationFrame(frame);
+      clearInterval(report);
+    };
+  }, [reportFrames]);
+
   const applyState = useCallback((next: OverlayState) => {
     setState(next);
     invoke("set_overlay_state", { next }).catch(() => {
@@ -86,8 +147,47 @@ export default function App() {
   }, [showDevToolbar]);
 
   return (
-    <main className={rootClassName}>
-      <VoiceOverlay state={state} />
+    <main
+      className={rootClassName}
+      data-connection={state.connection}
+      data-listening={state.listening}
+      data-processing={state.processing}
+      data-target={state.target}
+      data-mode={deriveMode(state, state.level)}
+      data-visible={String(state.visible)}
+      data-display-form={state.display_form ?? "overlay"}
+      data-power-mode={state.power_mode ?? "balanced"}
+      data-theme={state.system_theme ?? "dark"}
+      data-interactive={String(state.interactive ?? false)}
+      data-streaming={String(state.streaming ?? false)}
+      data-split={String(splitView !== null)}
+      data-collapsed={String(state.collapsed ?? false)}
+      style={{
+        ["--overlay-text-scale" as string]: String(state.text_scale ?? 1),
+        ["--overlay-corner-radius" as string]: `${state.corner_radius ?? 7}px`,
+      }}
+    >
+      {splitView ? (
+        <div className="flex h-full w-full">
+          {[splitView.left, splitView.right].map((theme, index) => (
+            <div
+              key={index}
+              className={`flex h-full w-1/2 items-center justify-center ${theme === "dark" ? "dark" : ""}`}
+              data-theme={theme}
+            >
+              <VoiceOverlay state={state} />
+            </div>
+          ))}
+        </div>
+      ) : (
+        <VoiceOverlay state={state} />
+      )}
+      <div className="sr-only" role="status" aria-live="polite">
+        {announcement?.politeness === "polite" ? announcement.text : ""}
+      </div>
+      <div className="sr-only" role="alert" aria-live="assertive">
+        {announcement?.politeness === "assertive" ? announcement.text : ""}
+      </div>
       {showDevToolbar ? <DevToolbar onSetState={applyState} /> : null}
     </main>
   );
@@ -1,5 +1,16 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { CSSProperties } from "react";
+
 import { BarVisualizer, type AgentState } from "@/components/ui/bar-visualizer";
-import { bubbleLabel, deriveMode, modePalette } from "@/lib/overlay";
+import {
+  bubbleLabel,
+  deriveMode,
+  formatSegmentTime,
+  modePalette,
+  segmentsText,
+  statusDotColor,
+  wordsText,
+} from "@/lib/overlay";
 import type { OverlayState } from "@/types/overlay";
 
 interface VoiceOverlayProps {
@@ -10,13 +21,19 @@ export function VoiceOverlay({ state }: VoiceOverlayProps) {
   const level = Math.max(0, Math.min(1, Number.isFinite(state.level) ? state.level : 0));
   const mode = deriveMode(state, level);
   const palette = modePalette(mode);
-  const bubbleText = bubbleLabel(state, mode);
+  const bubbleText = state.rendered_text ?? bubbleLabel(state, mode);
   const isLoading = mode === "loading";
   const isProcessing = state.processing === "processing";
   const isListening = state.listening === "listening" && !isProcessing;
   const waveformLevel = isListening ? Math.max(level, 0.012) : level;
+  const activity = Math.max(0, Math.min(1, Number.isFinite(state.activity) ? state.activity ?? 0 : 0));
+  const peakLevel = Math.max(0, Math.min(1, Number.isFinite(state.peak_level) ? state.peak_level ?? 0 : 0));
   const idleLineStyle = (isListening || isLoading) ? "none" : mode === "listening_wait" ? "solid" : "dotted";
-  const bubbleKey = `${mode}|${state.message ?? ""}|${state.target}|${state.connection}|${state.listening}|${state.processing}`;
+  const words = state.words ?? [];
+  const showWords = words.length > 0 && bubbleText === wordsText(words);
+  const segments = state.segments ?? [];
+  const showSegments = !showWords && segments.length > 0 && bubbleText === segmentsText(segments);
+  const bubbleKey = `${mode}|${state.rendered_text ?? ""}|${state.message ?? ""}|${words.length}|${segments.length}|${state.target}|${state.connection}|${state.listening}|${state.processing}`;
   const visualizerState: AgentState | undefined = isLoading
     ? "initializing"
     : isProcessing
@@ -29,28 +46,126 @@ export function VoiceOverlay({ state }: VoiceOverlayProps) {
           ? "listening"
           : undefined;
   const minBarHeight = isLoading ? 4 : 12;
+  const command = state.command_feedback ?? null;
+  const countdown = state.ready_countdown ?? null;
+
+  const entrance = Math.max(0, Math.min(1, state.entrance ?? (state.visible ? 1 : 0)));
+  // Slides toward the anchored edge: down under the taskbar, or up for the top banner.
+  const entranceStyle: CSSProperties =
+    state.show_animation === "fade"
+      ? { opacity: entrance }
+      : state.show_animation === "slide"
+        ? { transform: `translateY(${(1 - entrance) * (state.display_form === "banner" ? -100 : 100)}%)` }
+        : {};
+
+  if (state.status_dot && !state.visible) {
+    return (
+      <div className="pointer-events-none flex h-full w-full select-none items-center justify-center rounded-full border border-[#75757566] bg-[#2c2c2cf2]">
+        <span className="h-2.5 w-2.5 rounded-full" style={{ backgroundColor: statusDotColor(mode) }} />
+      </div>
+    );
+  }
+
+  if (!state.visible && entrance <= 0) return null;
 
-  if (!state.visible) return null;
+  if (state.collapsed && state.display_form !== "banner") {
+    return (
+      <div
+        className="pointer-events-none flex h-full w-full select-none items-center justify-center rounded-full border border-[#75757566] bg-[#2c2c2cf2]"
+        style={entranceStyle}
+      >
+        <span className="h-2 w-2 rounded-full" style={{ backgroundColor: palette.main, opacity: 0.5 + level * 0.5 }} />
+      </div>
+    );
+  }
+
+  if (state.display_form === "banner") {
+    return (
+      <div
+        className="pointer-events-none flex h-full w-full select-none items-center justify-center gap-3 border-b border-[#75757566] bg-[#2c2c2cf2] px-4 text-[14px] leading-5 text-white"
+        style={entranceStyle}
+      >
+        <span className="h-2 w-2 shrink-0 rounded-full" style={{ backgroundColor: palette.main }} />
+        <span className="truncate">{bubbleText ?? state.message ?? ""}</span>
+      </div>
+    );
+  }
 
   return (
-    <div className="pointer-events-none relative select-none" style={{ width: 194, height: 126 }}>
-      {bubbleText ? (
+    <div className="pointer-events-none relative select-none" style={{ width: 194, height: 126, ...entranceStyle }}>
+      {countdown !== null ? (
+        <div
+          key={countdown}
+          className="absolute left-1/2 top-[14px] z-10 flex h-9 w-9 -translate-x-1/2 items-center justify-center rounded-full border border-[#75757566] bg-[#2c2c2cf2] text-[18px] font-semibold leading-none text-white shadow-[0_8px_16px_rgba(0,0,0,0.16)] backdrop-blur-[22px] [animation:voicekey-command-pulse_0.6s_ease-out]"
+        >
+          {countdown}
+        </div>
+      ) : command ? (
+        <div
+          key={`${command.phrase}|${command.matched}|${command.action ?? ""}`}
+          className={`absolute left-1/2 top-[14px] z-10 flex max-w-[180px] -translate-x-1/2 items-center gap-1.5 rounded-full border px-3 py-[5px] text-[13px] leading-5 text-white shadow-[0_8px_16px_rgba(0,0,0,0.16)] backdrop-blur-[22px] ${command.matched ? "border-[#4ade8099] bg-[#1f3a2bf2] [animation:voicekey-command-pulse_0.6s_ease-out]" : "border-[#75757566] bg-[#2c2c2cf2] opacity-70"}`}
+        >
+          <span className={`truncate ${command.matched ? "" : "line-through"}`}>{command.phrase}</span>
+          {command.matched && command.action ? <span className="shrink-0 text-white/60">{`\u2192 ${command.action}`}</span> : null}
+        </div>
+      ) : bubbleText ? (
         <div
           key={bubbleKey}
-          className="absolute left-1/2 top-[14px] z-10 -translate-x-1/2 [animation:voicekey-bubble-hide_2s_ease-out_forwards]"
+          className={`absolute left-1/2 top-[14px] z-10 -translate-x-1/2 ${state.awaiting_ack || state.attention ? "" : "[animation:voicekey-bubble-hide_2s_ease-out_forwards]"}`}
         >
-          <div className="relative rounded-[7px] border border-[#75757566] bg-[#2c2c2cf2] px-3 py-[9px] text-[14px] leading-5 text-white shadow-[0_8px_16px_rgba(0,0,0,0.16)] backdrop-blur-[22px]">
-            {bubbleText}
+          <div className={`relative rounded-[7px] border ${state.awaiting_ack || state.attention ? "border-[#ff4d5e]" : "border-[#75757566]"}${state.attention ? " [animation:voicekey-attention-flash_1s_ease-in-out_infinite]" : ""} bg-[#2c2c2cf2] px-3 py-[9px] text-[14px] leading-5 text-white shadow-[0_8px_16px_rgba(0,0,0,0.16)] backdrop-blur-[22px]`}>
+            {showWords
+              ? words.map((word, index) => (
+                  <span key={index} className={word.status === "final" ? undefined : "opacity-60"}>
+                    {index > 0 ? " " : ""}
+                    {word.text}
+                  </span>
+                ))
+              : showSegments
+                ? (
+                    // Newest at the bottom; older lines scroll off the top.
+                    <div className="flex max-h-[60px] max-w-[170px] flex-col justify-end overflow-hidden">
+                      {segments.map((segment, index) => (
+                        <span key={index}>
+                          <span className="tabular-nums text-white/50">{formatSegmentTime(segment.start_ms)}</span> {segment.text}
+                        </span>
+                      ))}
+                    </div>
+                  )
+                : bubbleText}
             <span className="absolute left-1/2 top-full h-0 w-0 -translate-x-1/2 border-l-[7px] border-r-[7px] border-t-[7px] border-l-transparent border-r-transparent border-t-[#2c2c2cf2]" />
           </div>
         </div>
       ) : null}
 
+      {state.interactive ? (
+        <button
+          type="button"
+          onClick={() => {
+            const command = state.awaiting_ack
+              ? invoke("acknowledge_message", { id: state.message_id ?? null })
+              : invoke("dismiss_overlay");
+            command.catch(() => {
+              // Browser-only mode fallback.
+            });
+          }}
+          className="pointer-events-auto absolute right-[17px] top-[56px] z-20 rounded-full border border-[#75757566] bg-[#2c2c2cf2] px-2 text-[11px] leading-5 text-white"
+        >
+          {state.awaiting_ack ? "Acknowledge" : "Dismiss"}
+        </button>
+      ) : null}
+
       <div className="absolute bottom-4 left-1/2 h-[47px] w-[160px] -translate-x-1/2">
-        <div className="absolute inset-0 rounded-[7px] bg-[#2e2e2eeb] shadow-[0_2px_6px_rgba(0,0,0,0.15),0_9px_18px_rgba(0,0,0,0.19)] backdrop-blur-[30px]" />
-        <div className="absolute inset-0 rounded-[7px] border border-[#75757566]" />
+        <div className="absolute inset-0 rounded-[var(--overlay-corner-radius,7px)] bg-[#2e2e2eeb] shadow-[0_2px_6px_rgba(0,0,0,0.15),0_9px_18px_rgba(0,0,0,0.19)] backdrop-blur-[30px]" />
+        <div className="absolute inset-0 rounded-[var(--overlay-corner-radius,7px)] border border-[#75757566]" />
+        {activity > 0 ? (
+          <div
+            className="absolute inset-0 rounded-[var(--overlay-corner-radius,7px)]"
+            style={{ boxShadow: `0 0 ${4 + activity * 10}px ${palette.main}`, opacity: activity * 0.6 }}
+          />
+        ) : null}
 
-        <div className="absolute inset-[1px] overflow-hidden rounded-[6px]">
+        <div className="absolute inset-[1px] overflow-hidden rounded-[calc(var(--overlay-corner-radius,7px)-1px)]">
           <BarVisualizer
             state={visualizerState}
             level={waveformLevel}
@@ -62,6 +177,15 @@ export function VoiceOverlay({ state }: VoiceOverlayProps) {
             highlightColor={palette.main}
             className="h-full w-full rounded-none bg-transparent px-3 py-2"
           />
+          {isListening && peakLevel > level ? (
+            <div
+              className="absolute left-3 right-3 h-px"
+              style={{ bottom: `calc(8px + (100% - 16px) * ${peakLevel * 0.96})`, backgroundColor: palette.main }}
+            />
+          ) : null}
+          {isListening && state.clipping ? (
+            <div className="absolute right-1.5 top-1.5 h-1.5 w-1.5 rounded-full bg-[#ff4d5e]" />
+          ) : null}
           {!isListening && !isProcessing && !isLoading && idleLineStyle !== "none" ? (
             <div
               className={`absolute left-3 right-3 top-1/2 -translate-y-1/2 border-t border-white/35 ${idleLineStyle === "dotted" ? "border-dotted" : "border-solid"}`}
@@ -1,4 +1,4 @@
-import type { OverlayState } from "@/types/overlay";
+import type { OverlaySegment, OverlayState, OverlayWord } from "@/types/overlay";
 
 export type OverlayMode =
   | "idle"
@@ -28,7 +28,7 @@ export function deriveMode(state: OverlayState, filteredLevel: number): OverlayM
   if (state.listening === "error" || state.processing === "error") return "error";
   if (state.connection === "offline") return "error";
   if (state.target === "not_selected") return "warning";
-  if (state.listening === "arming") return "loading";
+  if (state.listening === "arming" || state.connection === "reconnecting") return "loading";
   if (state.processing === "processing") return "processing";
   if (state.listening === "listening") {
     return filteredLevel >= LISTENING_AUDIO_THRESHOLD ? "listening_audio" : "listening_wait";
@@ -37,11 +37,32 @@ export function deriveMode(state: OverlayState, filteredLevel: number): OverlayM
   return "idle";
 }
 
+export function wordsText(words: OverlayWord[]): string {
+  return words.map((word) => word.text).join(" ").trim();
+}
+
+export function segmentsText(segments: OverlaySegment[]): string {
+  return segments.map((segment) => segment.text.trim()).filter(Boolean).join(" ");
+}
+
+export function formatSegmentTime(ms: number): string {
+  const seconds = Math.max(0, Math.floor(ms / 1000));
+  return `${Math.floor(seconds / 60)}:${String(seconds % 60).padStart(2, "0")}`;
+}
+
 export function bubbleLabel(state: OverlayState, mode: OverlayMode): string | null {
   if (state.target === "not_selected") return "Select a text box";
   if (state.connection === "offline") return "No connection";
+  if (state.connection === "reconnecting") return "Reconnecting...";
   if (mode === "error") return "Try again";
   if (state.message && state.message.trim()) return state.message.trim();
+  const transcript = wordsText(state.words ?? []);
+  if (transcript) return transcript;
+  const segmentTranscript = segmentsText(state.segments ?? []);
+  if (segmentTranscript) return segmentTranscript;
+  const steps = state.steps ?? [];
+  const active = steps.findIndex((step) => step.status === "active");
+  if (active >= 0) return `${steps[active].label} (${active + 1}/${steps.length})`;
   if (mode === "loading") return "Starting...";
   if (mode === "listening_wait") return "Listening...";
   return null;
@@ -63,6 +84,14 @@ export function modePalette(mode: OverlayMode): OverlayPalette {
   return { main: "#ffffff", soft: "#ececec", dim: "#cfcfcf" };
 }
 
+export function statusDotColor(mode: OverlayMode): string {
+  if (mode === "error") return "#ff4d5e";
+  if (mode === "warning") return "#f4cf8f";
+  if (mode === "listening_wait" || mode === "listening_audio") return "#4ade80";
+  if (mode === "processing" || mode === "loading") return "#f6f6f6";
+  return "#9ca3af";
+}
+
 interface Point {
   x: number;
   y: number;
@@ -1,7 +1,45 @@
-export type ConnectionState = "checking" | "online" | "offline";
+export type ConnectionState = "checking" | "online" | "reconnecting" | "offline";
 export type ListeningState = "ready" | "arming" | "listening" | "error";
 export type ProcessingState = "idle" | "processing" | "done" | "error";
 export type TargetState = "unknown" | "selected" | "not_selected";
+export type StepStatus = "pending" | "active" | "done" | "failed";
+export type WordStatus = "interim" | "final";
+
+export interface OverlayStep {
+  label: string;
+  status: StepStatus;
+}
+
+export interface OverlayWord {
+  text: string;
+  status: WordStatus;
+}
+
+export interface OverlaySegment {
+  text: string;
+  start_ms: number;
+  end_ms: number;
+}
+
+export interface OverlayCommandFeedback {
+  phrase: string;
+  matched: boolean;
+  action?: string | null;
+}
+
+export interface OverlayVideoRegion {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+export type OverlayTheme = "light" | "dark";
+
+export interface OverlaySplitThemes {
+  left: OverlayTheme;
+  right: OverlayTheme;
+}
 
 export interface OverlayState {
   connection: ConnectionState;
@@ -9,8 +47,47 @@ export interface OverlayState {
   processing: ProcessingState;
   target: TargetState;
   level: number;
+  meters?: Record<string, number>;
+  peak_level?: number;
+  clipping?: boolean;
+  activity?: number;
   visible: boolean;
   message?: string | null;
+  message_id?: string | null;
+  awaiting_ack?: boolean;
+  attention?: boolean;
+  confidence?: number | null;
+  steps?: OverlayStep[];
+  words?: OverlayWord[];
+  segments?: OverlaySegment[];
+  command_feedback?: OverlayCommandFeedback | null;
+  video_region?: OverlayVideoRegion | null;
+  ready_countdown?: number | null;
+  text_scale?: number;
+  corner_radius?: number;
+  display_form?: "overlay" | "banner";
+  scale_factor?: number;
+  power_mode?: "performance" | "balanced" | "saver";
+  system_theme?: OverlayTheme;
+  interactive?: boolean;
+  entrance?: number;
+  show_animation?: "none" | "fade" | "slide";
+  streaming?: boolean;
+  collapsed?: boolean;
+  status_dot?: boolean;
+  split_view?: OverlaySplitThemes | null;
+  context?: string | null;
+  rendered_text?: string | null;
+}
+
+export interface OverlayAnnouncement {
+  text: string;
+  politeness: "polite" | "assertive";
+}
+
+export interface OverlayToast {
+  text: string;
+  severity: "info" | "error";
 }
 
 export const defaultOverlayState: OverlayState = {
@@ -21,4 +98,14 @@ export const defaultOverlayState: OverlayState = {
   level: 0,
   visible: false,
   message: null,
+  confidence: null,
+  steps: [],
+  words: [],
+  text_scale: 1,
+  corner_radius: 7,
+  display_form: "overlay",
+  scale_factor: 1,
+  power_mode: "balanced",
+  system_theme: "dark",
+  interactive: false,
 };
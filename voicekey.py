@@ -11,6 +11,7 @@ import json
 import os
 import math
 import queue
+import random
 import socket
 import struct
 import subprocess
@@ -20,6 +21,7 @@ import time
 import wave
 import ctypes
 from ctypes import wintypes
+from dataclasses import dataclass
 from urllib.parse import urlparse
 
 # ---------------------------------------------------------------------------
@@ -164,6 +166,47 @@ DEBUG_OVERLAY_STATES = _env_flag("VOICEKEY_DEBUG_OVERLAY")
 DEBUG_OVERLAY_VERBOSE = _env_flag("VOICEKEY_DEBUG_OVERLAY_VERBOSE")
 
 
+def _env_number(name: str, default: float) -> float:
+    raw = os.environ.get(name, "").strip()
+    try:
+        value = float(raw) if raw else default
+    except ValueError:
+        return default
+    return value if value >= 0 else default
+
+
+@dataclass(frozen=True)
+class BridgeReconnectPolicy:
+    """How the overlay bridge waits for an overlay that doesn't answer pings.
+
+    Pings back off exponentially with jitter from `base_seconds` up to
+    `max_delay_seconds`. After `max_attempts` pings or `timeout_seconds`
+    (0 for no limit), the bridge is reported unreachable once and keeps
+    probing at the max delay.
+    """
+
+    base_seconds: float = 0.25
+    max_delay_seconds: float = 10.0
+    max_attempts: int = 8
+    timeout_seconds: float = 30.0
+
+    @classmethod
+    def from_env(cls) -> "BridgeReconnectPolicy":
+        """Defaults, overridden by VOICEKEY_BRIDGE_RETRY_MAX_ATTEMPTS and VOICEKEY_BRIDGE_RETRY_TIMEOUT."""
+        return cls(
+            max_attempts=int(_env_number("VOICEKEY_BRIDGE_RETRY_MAX_ATTEMPTS", cls.max_attempts)),
+            timeout_seconds=_env_number("VOICEKEY_BRIDGE_RETRY_TIMEOUT", cls.timeout_seconds),
+        )
+
+    def delay(self, attempt: int) -> float:
+        """Seconds to wait after failed ping number `attempt` (from 1)."""
+        backoff = min(self.max_delay_seconds, self.base_seconds * (2 ** min(attempt - 1, 32)))
+        return backoff * random.uniform(0.5, 1.0)
+
+    def exhausted(self, attempts: int, elapsed: float) -> bool:
+        return (0 < self.max_attempts <= attempts) or (0 < self.timeout_seconds <= elapsed)
+
+
 def find_tauri_overlay_exe() -> str | None:
     """Return best candidate Tauri overlay executable path, if present."""
     explicit = os.environ.get("VOICEKEY_TAURI_OVERLAY_EXE", "").strip()
@@ -398,34 +441,131 @@ class StatusOverlay:
     BUBBLE_RADIUS = 7
     LEVEL_ACTIVE_THRESHOLD = 0.05
 
-    def __init__(self):
+    def __init__(self, reconnect: BridgeReconnectPolicy | None = None):
         self._queue: queue.Queue = queue.Queue()
         self._thread: threading.Thread | None = None
         self._ready = threading.Event()
         self._phase = 0.0
         self._level_filtered = 0.0
         self._native_enabled = not tauri_overlay_only_enabled()
+        # Without the Tauri overlay nothing answers pings, so sends go out unprobed.
+        self._bridge_expected = not self._native_enabled or find_tauri_overlay_exe() is not None
         self._bridge_hide_timer: threading.Timer | None = None
         self._bridge_socket: socket.socket | None = None
+        self._bridge_policy = reconnect or BridgeReconnectPolicy.from_env()
+        self._bridge_lock = threading.Lock()
+        # Healthy once the overlay answered a ping; until then patches merge
+        # into `_bridge_pending` and go out together when it does.
+        self._bridge_healthy = False
+        self._bridge_pending: dict = {}
+        self._bridge_probe: threading.Thread | None = None
+        self._bridge_stop = threading.Event()
+        self._bridge_error: str | None = None
         try:
             self._bridge_socket = socket.socket(socket.AF_INET, socket.SOCK_DGRAM)
         except OSError:
             self._bridge_socket = None
-
-    def _bridge_send(self, payload: dict) -> None:
-        """Best-effort UDP patch broadcast for the Tauri overlay bridge."""
+        with self._bridge_lock:
+            self._bridge_start_probe()
+
+    def with_reconnect(self, policy: BridgeReconnectPolicy) -> "StatusOverlay":
+        """Retry with `policy` from now on."""
+        self._bridge_policy = policy
+        return self
+
+    @property
+    def bridge_error(self) -> str | None:
+        """Why the bridge is unreachable, once the reconnect policy is exhausted."""
+        return self._bridge_error
+
+    def _bridge_start_probe(self) -> None:
+        """Start pinging for the overlay; call with `_bridge_lock` held."""
+        if not self._bridge_expected or self._bridge_probe is not None or self._bridge_stop.is_set():
+            return
+        self._bridge_probe = threading.Thread(target=self._bridge_probe_loop, daemon=True)
+        self._bridge_probe.start()
+
+    def _bridge_probe_loop(self) -> None:
+        started = time.monotonic()
+        attempts = 0
+        while not self._bridge_stop.is_set():
+            policy = self._bridge_policy
+            if self._bridge_ping():
+                with self._bridge_lock:
+                    self._bridge_healthy = True
+                    self._bridge_probe = None
+                    pending, self._bridge_pending = self._bridge_pending, {}
+                if self._bridge_error is not None:
+                    print("overlay bridge reachable again", flush=True)
+                    self._bridge_error = None
+                if pending:
+                    self._bridge_write(pending)
+                return
+            attempts += 1
+            elapsed = time.monotonic() - started
+            if self._bridge_error is None and policy.exhausted(attempts, elapsed):
+                self._bridge_error = (
+                    f"overlay bridge at {OVERLAY_BRIDGE_ADDR[0]}:{OVERLAY_BRIDGE_ADDR[1]} did not answer "
+                    f"{attempts} pings over {elapsed:.1f}s; is the overlay running? "
+                    f"Still retrying every {policy.max_delay_seconds:g}s"
+                )
+                print(self._bridge_error, flush=True)
+            delay = policy.max_delay_seconds if self._bridge_error else policy.delay(attempts)
+            self._bridge_stop.wait(delay)
+        with self._bridge_lock:
+            self._bridge_probe = None
+
+    def _bridge_unhealthy(self, payload: dict) -> None:
+        """Queue `payload` and look for the overlay again."""
+        with self._bridge_lock:
+            self._bridge_healthy = False
+            self._bridge_pending.update(payload)
+            self._bridge_start_probe()
+
+    def _bridge_write(self, payload: dict) -> None:
         if not self._bridge_socket:
             return
         try:
-            body = json.dumps(payload, ensure_ascii=True, separators=(",", ":")).encode("utf-8")
+            tagged = {"kind": "patch", **payload}
+            body = json.dumps(tagged, ensure_ascii=True, separators=(",", ":")).encode("utf-8")
             self._bridge_socket.sendto(body, OVERLAY_BRIDGE_ADDR)
             if DEBUG_OVERLAY_STATES:
                 if DEBUG_OVERLAY_VERBOSE or set(payload.keys()) != {"level"}:
                     overlay_debug(f"overlay-udp {payload}")
+        except OSError:
+            if DEBUG_OVERLAY_STATES:
+                overlay_debug("overlay-udp send failed; pinging for the overlay")
+            self._bridge_unhealthy(payload)
         except Exception:
             if DEBUG_OVERLAY_STATES:
                 overlay_debug("overlay-udp send failed")
 
+    def _bridge_send(self, payload: dict) -> None:
+        """UDP patch broadcast for the Tauri overlay bridge, held back while the overlay isn't answering."""
+        with self._bridge_lock:
+            if self._bridge_expected and not self._bridge_healthy:
+                self._bridge_pending.update(payload)
+                self._bridge_start_probe()
+                return
+        self._bridge_write(payload)
+
+    def _bridge_ping(self, timeout: float = 0.25) -> bool:
+        try:
+            with socket.socket(socket.AF_INET, socket.SOCK_DGRAM) as probe:
+                probe.settimeout(timeout)
+                probe.sendto(b"ping", OVERLAY_BRIDGE_ADDR)
+                reply, _ = probe.recvfrom(1024)
+            return json.loads(reply.decode("utf-8")).get("event") == "pong"
+        except (OSError, ValueError):
+            return False
+
+    def bridge_is_healthy(self, timeout: float = 0.25) -> bool:
+        """True when the overlay answers a bridge `ping` within `timeout` seconds; a miss holds sends back until it answers again."""
+        healthy = self._bridge_ping(timeout)
+        if not healthy:
+            self._bridge_unhealthy({})
+        return healthy
+
     def _bridge_hide_later(self, delay_ms: int) -> None:
         if self._bridge_hide_timer is not None:
             try:
@@ -452,7 +592,10 @@ class StatusOverlay:
         """Stop and destroy the overlay."""
         if self._native_enabled:
             self._queue.put(("stop", None))
-        self._bridge_send({"visible": False})
+        # Stop probing first, then write the hide past the health gate so it
+        # isn't left queued for a probe that will never flush it.
+        self._bridge_stop.set()
+        self._bridge_write({"visible": False})
         if self._bridge_hide_timer is not None:
             try:
                 self._bridge_hide_timer.cancel()
@@ -1316,6 +1459,7 @@ class VoiceKeyApp:
         while not self._connection_stop.is_set():
             endpoint = self.cfg.get("endpoint", DEFAULT_CONFIG["endpoint"])
             online = endpoint_reachable(endpoint)
+            self._overlay.bridge_is_healthy()
             self._connection_state = "online" if online else "offline"
             self._overlay.update(connection=self._connection_state)
             self._connection_kick.clear()